@@ -6,6 +6,8 @@ use core::{
 #[cfg(feature = "alloc")]
 use alloc::{borrow::Cow, vec::Vec};
 
+use crate::Pattern;
+
 #[cfg(feature = "alloc")]
 use crate::SharedBytes;
 
@@ -380,6 +382,40 @@ impl<'a> ByteData<'a> {
         crate::external::ExtBytes::create(dat)
     }
 
+    #[cfg(feature = "alloc")]
+    /// Returns a reference to the external value backing this `ByteData`, if it was created from an external value of type `T`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn downcast_external_ref<T: crate::external::ExternalBytes>(&self) -> Option<&T> {
+        if !matches!(self.kind(), Kind::External) {
+            return None;
+        }
+        // SAFETY: External state has been checked.
+        unsafe { self.external.inner_ref::<T>() }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Reclaims the external value of type `T` that this `ByteData` was created from.
+    ///
+    /// This only succeeds if this `ByteData` is the only reference to the external value and it covers the whole of its bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `ByteData` unchanged if it is not backed by an external `T`, if the data is shared, or if it has been sliced.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn try_into_external<T: crate::external::ExternalBytes>(self) -> Result<T, Self> {
+        if !matches!(self.kind(), Kind::External) {
+            return Err(self);
+        }
+        // SAFETY: External state has been checked.
+        let ext = unsafe { core::mem::transmute::<ByteData<'a>, crate::external::ExtBytes>(self) };
+        ext.into_inner::<T>().map_err(|ext| Self {
+            external: core::mem::ManuallyDrop::new(ext),
+        })
+    }
+
     #[cfg(feature = "alloc")]
     /// Creates a `ByteData` from a `Cow<'_, [u8]>`.
     #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -800,6 +836,26 @@ impl<'a> ByteData<'a> {
         }
     }
 
+    #[cfg(feature = "alloc")]
+    /// Converts this `ByteData` into a mutable [`SharedBytesBuilder`](crate::SharedBytesBuilder) without copying.
+    ///
+    /// This only succeeds when the data is held in a `SharedBytes` that has no other references.
+    /// The allocation is then reused in place, keeping any unused capacity after the data.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `ByteData` unchanged if a mutable builder can not be created without copying.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn try_make_mut(self) -> Result<crate::SharedBytesBuilder, Self> {
+        if !matches!(self.kind(), Kind::Shared) {
+            return Err(self);
+        }
+        // SAFETY: Shared state has been checked.
+        let dat = unsafe { core::mem::transmute::<ByteData<'a>, SharedBytes>(self) };
+        dat.try_into_builder().map_err(Self::from_shared)
+    }
+
     /// Split the `ByteData` at the given position.
     #[inline]
     #[allow(clippy::return_self_not_must_use)]
@@ -842,56 +898,76 @@ impl<'a> ByteData<'a> {
     /// If the byte sequence is not found, the original `ByteData` is returned.
     #[inline]
     pub fn split_once_on(self, needle: &[u8]) -> Result<(Self, Self), Self> {
-        let aa = match crate::const_split_once_bytes(self.as_slice(), needle) {
-            Some((aa, _)) => aa.len(),
-            None => return Err(self),
+        let Some(aa) = crate::search::find_bytes(self.as_slice(), needle) else {
+            return Err(self);
         };
         Ok(self.split_at(aa))
     }
 
-    /// Split the `ByteData` at the first occurrence of the given byte sequence.
+    /// Split the `ByteData` on each occurrence of the given byte sequence.
     #[inline]
     pub fn split_on<'b>(self, needle: &'b [u8]) -> impl Iterator<Item = ByteData<'a>> + Send + 'b
     where
         'a: 'b,
     {
-        struct It<'a, 'b>(ByteData<'a>, &'b [u8], bool);
-        impl<'a> Iterator for It<'a, '_> {
-            type Item = ByteData<'a>;
+        crate::pattern::SplitData::new(self, needle, usize::MAX, false)
+    }
 
-            fn next(&mut self) -> Option<Self::Item> {
-                if self.0.is_empty() {
-                    return None;
-                }
-                let aa = if let Some((aa, _)) =
-                    crate::const_split_once_bytes(self.0.as_slice(), self.1)
-                {
-                    aa.len()
-                } else {
-                    return Some(core::mem::replace(&mut self.0, const { ByteData::empty() }));
-                };
-                if aa == 0 && self.2 {
-                    self.2 = false;
-                    return const { Some(ByteData::empty()) };
-                }
-                self.2 = false;
-                let aa = self.0.take_bytes(aa);
-                Some(aa)
-            }
+    /// Returns the position of the first match of the pattern.
+    #[inline]
+    pub fn find<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        crate::pattern::find_match(&mut searcher, self.as_slice(), false).map(|(start, _)| start)
+    }
 
-            fn size_hint(&self) -> (usize, Option<usize>) {
-                if self.0.is_empty() {
-                    (0, Some(0))
-                } else if self.0.len() < self.1.len() {
-                    (1, Some(1))
-                } else if self.0.len() < self.1.len() * 2 {
-                    (1, Some(2))
-                } else {
-                    (1, None)
-                }
-            }
-        }
-        It(self, needle, true)
+    /// Returns the position of the last match of the pattern.
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        crate::pattern::rfind_match(&mut searcher, self.as_slice(), false).map(|(start, _)| start)
+    }
+
+    /// Split the `ByteData` on each match of the pattern.
+    #[inline]
+    pub fn split<P: Pattern>(self, pattern: P) -> impl Iterator<Item = ByteData<'a>> {
+        crate::pattern::SplitData::new(self, pattern.into_searcher(), usize::MAX, false)
+    }
+
+    /// Split the `ByteData` on each match of the pattern, returning at most `max` items.
+    /// The last item contains the remaining data.
+    #[inline]
+    pub fn splitn<P: Pattern>(self, max: usize, pattern: P) -> impl Iterator<Item = ByteData<'a>> {
+        crate::pattern::SplitData::new(self, pattern.into_searcher(), max, false)
+    }
+
+    /// Split the `ByteData` on each match of the pattern, starting from the end.
+    #[inline]
+    pub fn rsplit<P: Pattern>(self, pattern: P) -> impl Iterator<Item = ByteData<'a>> {
+        crate::pattern::SplitData::new(self, pattern.into_searcher(), usize::MAX, false).reversed()
+    }
+
+    /// Split the `ByteData` after each match of the pattern, keeping the match at the end of each item.
+    #[inline]
+    pub fn split_inclusive<P: Pattern>(self, pattern: P) -> impl Iterator<Item = ByteData<'a>> {
+        crate::pattern::SplitData::new(self, pattern.into_searcher(), usize::MAX, false).inclusive()
+    }
+
+    /// Returns the data after the pattern if it matches at the start.
+    #[inline]
+    #[must_use]
+    pub fn strip_prefix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = crate::pattern::prefix_match(&mut searcher, self.as_slice(), false)?;
+        Some(self.sliced(len..))
+    }
+
+    /// Returns the data before the pattern if it matches at the end.
+    #[inline]
+    #[must_use]
+    pub fn strip_suffix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = crate::pattern::suffix_match(&mut searcher, self.as_slice(), false)?;
+        Some(self.sliced(..self.len() - len))
     }
 }
 
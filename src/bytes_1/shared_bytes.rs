@@ -51,14 +51,8 @@ static SHARED_BYTES_BVT: super::SBytesVtable = super::SBytesVtable {
         meta.refcnt.load(Ordering::Relaxed) == 1
     },
     drop: |data, _ptr, _len| unsafe {
-        let p = data.load(Ordering::Relaxed) as *mut u8;
-        let meta = &*(p as *const crate::SharedBytesMeta);
-        if meta.refcnt.fetch_sub(1, Ordering::Relaxed) != 1 {
-            return;
-        }
-        let layout =
-            alloc::alloc::Layout::from_size_align(meta.len as usize, meta.align()).unwrap();
-        alloc::alloc::dealloc(p, layout);
+        let p = data.load(Ordering::Relaxed) as *const u8;
+        crate::SharedBytesMeta::release(p);
     },
 };
 
@@ -75,20 +69,49 @@ impl From<SharedBytes> for bytes::Bytes {
 #[cfg_attr(docsrs, doc(cfg(feature = "bytes_1")))]
 impl From<SharedBytes> for bytes::Bytes {
     fn from(dat: SharedBytes) -> Self {
-        if dat.len == 0 {
+        if dat.is_empty() {
             return bytes::Bytes::new();
         }
-        let dat = core::mem::ManuallyDrop::new(dat);
+        let (dat, off, len) = dat.into_parts();
         super::SBytes {
-            ptr: unsafe { dat.dat().add(dat.off as usize) },
-            len: dat.len as usize,
-            data: AtomicPtr::new(dat.dat() as *mut ()),
+            ptr: unsafe { dat.add(off) },
+            len,
+            data: AtomicPtr::new(dat as *mut ()),
             vtable: &SHARED_BYTES_BVT,
         }
         .into_bytes()
     }
 }
 
+#[cfg(not(feature = "bytes_1_safe"))]
+#[allow(clippy::multiple_inherent_impl)]
+impl SharedBytes {
+    /// Takes the buffer back out of a `Bytes` that was created from a `SharedBytes`, without copying.
+    pub(crate) fn try_from_bytes(dat: bytes::Bytes) -> Result<Self, bytes::Bytes> {
+        // SAFETY: `SBytes` has the same layout as `bytes::Bytes`.
+        let sbytes = unsafe { &*core::ptr::addr_of!(dat).cast::<super::SBytes>() };
+        if !core::ptr::eq(sbytes.vtable, &SHARED_BYTES_BVT) {
+            return Err(dat);
+        }
+        let meta = sbytes.data.load(Ordering::Relaxed) as *const u8;
+        let off = sbytes.ptr as usize - meta as usize;
+        let len = sbytes.len;
+        // the reference held by the `Bytes` is moved into the `SharedBytes`
+        core::mem::forget(dat);
+        Ok(Self::from_parts(meta, off, len))
+    }
+}
+
+#[cfg(not(feature = "bytes_1_safe"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes_1")))]
+impl From<bytes::Bytes> for SharedBytes {
+    #[inline]
+    fn from(dat: bytes::Bytes) -> Self {
+        Self::try_from_bytes(dat).unwrap_or_else(|dat| Self::from_slice(dat.as_ref()))
+    }
+}
+
+#[cfg(feature = "bytes_1_safe")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bytes_1")))]
 impl From<bytes::Bytes> for SharedBytes {
     #[inline]
@@ -101,7 +124,7 @@ impl From<bytes::Bytes> for SharedBytes {
 impl bytes::Buf for SharedBytes {
     #[inline]
     fn remaining(&self) -> usize {
-        self.len as usize
+        self.len()
     }
 
     #[inline]
@@ -111,24 +134,19 @@ impl bytes::Buf for SharedBytes {
 
     #[inline]
     fn advance(&mut self, cnt: usize) {
-        assert!(
-            cnt <= self.len as usize,
-            "SharedBytes::advance: index out of bounds"
-        );
-        #[allow(clippy::cast_possible_truncation)]
-        let cnt = cnt as u32;
-        self.off += cnt;
-        self.len -= cnt;
+        let len = self.len();
+        assert!(cnt <= len, "SharedBytes::advance: index out of bounds");
+        self.make_sliced(cnt, len - cnt);
     }
 
     #[inline]
     fn has_remaining(&self) -> bool {
-        self.len > 0
+        !self.is_empty()
     }
 
     #[inline]
     fn copy_to_bytes(&mut self, len: usize) -> bytes::Bytes {
-        let currlen = self.len as usize;
+        let currlen = self.len();
         assert!(
             len <= currlen,
             "SharedBytes::copy_to_bytes: index out of bounds"
@@ -140,10 +158,7 @@ impl bytes::Buf for SharedBytes {
             return core::mem::replace(self, Self::empty()).into();
         }
         let ret = self.sliced(0, len).into();
-        #[allow(clippy::cast_possible_truncation)]
-        let len = len as u32;
-        self.off += len;
-        self.len -= len;
+        self.make_sliced(len, currlen - len);
         ret
     }
 }
@@ -1,13 +1,13 @@
 use ::bytes_1 as bytes;
 
-use crate::SharedBytesBuilder;
+use crate::{shared_bytes::MAX_ALLOC_LEN, SharedBytesBuilder};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "bytes_1")))]
 // SAFETY: this impl is safe.
 unsafe impl bytes::BufMut for SharedBytesBuilder {
     #[inline]
     fn remaining_mut(&self) -> usize {
-        (0xFFFF_FFFF - self.off) as usize
+        MAX_ALLOC_LEN - self.off
     }
 
     #[inline]
@@ -16,8 +16,6 @@ unsafe impl bytes::BufMut for SharedBytesBuilder {
             cnt <= self.remaining_mut(),
             "SharedBytesBuilder::advance_mut: index out of bounds"
         );
-        #[allow(clippy::cast_possible_truncation)]
-        let cnt = cnt as u32;
         self.off += cnt;
     }
 
@@ -28,9 +26,9 @@ unsafe impl bytes::BufMut for SharedBytesBuilder {
         }
         let len = self.len - self.off;
         // SAFETY: `off` is within bounds.
-        let ptr = unsafe { self.dat.add(self.off as usize) };
+        let ptr = unsafe { self.dat.add(self.off) };
         // SAFETY: `len` is within bounds.
-        unsafe { bytes::buf::UninitSlice::from_raw_parts_mut(ptr, len as usize) }
+        unsafe { bytes::buf::UninitSlice::from_raw_parts_mut(ptr, len) }
     }
 
     #[inline]
@@ -69,13 +67,11 @@ unsafe impl bytes::BufMut for SharedBytesBuilder {
         );
         self.reserve(cnt);
         // SAFETY: `off` is within bounds.
-        let ptr = unsafe { self.dat.add(self.off as usize) };
+        let ptr = unsafe { self.dat.add(self.off) };
         // SAFETY: `cnt` is within bounds.
         unsafe {
             ptr.write_bytes(val, cnt);
         };
-        #[allow(clippy::cast_possible_truncation)]
-        let cnt = cnt as u32;
         self.off += cnt;
     }
 }
@@ -9,6 +9,54 @@ pub struct Utf8CharError {
     pub error_len: u8,
 }
 
+impl Utf8CharError {
+    /// Checks that `data` is valid UTF-8, reporting the first invalid sequence otherwise.
+    ///
+    /// Unlike [`core::str::Utf8Error`], an incomplete sequence at the end of the input reports the number of remaining bytes as its length.
+    ///
+    /// # Errors
+    ///
+    /// Returns the position and length of the first invalid UTF-8 sequence.
+    #[inline]
+    pub const fn check(data: &[u8]) -> Result<(), Self> {
+        match core::str::from_utf8(data) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let valid_up_to = err.valid_up_to();
+                let error_len = match err.error_len() {
+                    Some(len) => len,
+                    None => data.len() - valid_up_to,
+                };
+                #[allow(clippy::cast_possible_truncation)]
+                Err(Self {
+                    valid_up_to,
+                    // a UTF-8 sequence is at most 4 bytes long
+                    error_len: error_len as u8,
+                })
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for Utf8CharError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.error_len == 0 {
+            write!(f, "expected utf-8 character at index {}", self.valid_up_to)
+        } else {
+            write!(
+                f,
+                "invalid utf-8 sequence of {} bytes from index {}",
+                self.error_len, self.valid_up_to
+            )
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for Utf8CharError {}
+
 /// An iterator over the indices of the UTF-8 characters in a byte slice.
 ///
 /// Can be iterated over like any iterator, or used with the [`Utf8CharIndices::next_const`] method to get the next character in a const context.
@@ -163,6 +211,21 @@ impl core::iter::Iterator for Utf8CharIndices<'_> {
 
 impl core::iter::FusedIterator for Utf8CharIndices<'_> {}
 
+/// The iterator over the characters of a string input and their byte offsets.
+#[cfg(any(feature = "nom_8", feature = "winnow_0"))]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) type CharOffsets<I> =
+    core::iter::Scan<I, usize, fn(&mut usize, char) -> Option<(usize, char)>>;
+
+/// Pairs each character with its byte offset, for use with [`Iterator::scan`].
+#[cfg(any(feature = "nom_8", feature = "winnow_0"))]
+#[allow(clippy::redundant_pub_crate, clippy::unnecessary_wraps)]
+pub(crate) fn char_offset(offset: &mut usize, ch: char) -> Option<(usize, char)> {
+    let start = *offset;
+    *offset += ch.len_utf8();
+    Some((start, ch))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
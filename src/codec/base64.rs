@@ -0,0 +1,267 @@
+/// Marks bytes that are not part of the alphabet in a decode table.
+const INVALID: u8 = 0xFF;
+
+/// Builds the table mapping each symbol of `alphabet` back to its value.
+const fn decode_table(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < alphabet.len() {
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            table[alphabet[i] as usize] = i as u8;
+        };
+        i += 1;
+    }
+    table
+}
+
+const STANDARD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+static STANDARD_DECODE: [u8; 256] = decode_table(STANDARD_ALPHABET);
+static URL_SAFE_DECODE: [u8; 256] = decode_table(URL_SAFE_ALPHABET);
+
+/// Base64 encoding as described in RFC 4648, with either the standard or the URL-safe alphabet.
+///
+/// Padded variants write and require `=` padding, unpadded variants neither write nor accept it.
+/// Decoding rejects any other characters, including whitespace, and final symbols with unused bits set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64 {
+    alphabet: &'static [u8; 64],
+    decode: &'static [u8; 256],
+    padding: bool,
+}
+
+impl Base64 {
+    /// The standard alphabet with padding.
+    pub const STANDARD: Self = Self {
+        alphabet: STANDARD_ALPHABET,
+        decode: &STANDARD_DECODE,
+        padding: true,
+    };
+
+    /// The standard alphabet without padding.
+    pub const STANDARD_NO_PAD: Self = Self {
+        alphabet: STANDARD_ALPHABET,
+        decode: &STANDARD_DECODE,
+        padding: false,
+    };
+
+    /// The URL and filename safe alphabet with padding.
+    pub const URL_SAFE: Self = Self {
+        alphabet: URL_SAFE_ALPHABET,
+        decode: &URL_SAFE_DECODE,
+        padding: true,
+    };
+
+    /// The URL and filename safe alphabet without padding.
+    pub const URL_SAFE_NO_PAD: Self = Self {
+        alphabet: URL_SAFE_ALPHABET,
+        decode: &URL_SAFE_DECODE,
+        padding: false,
+    };
+
+    /// Returns the length of the encoded output for `len` bytes of input.
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(self, len: usize) -> usize {
+        if self.padding {
+            len.div_ceil(3) * 4
+        } else {
+            (len * 4).div_ceil(3)
+        }
+    }
+
+    /// Encodes `input`, passing the output to `sink` in blocks.
+    fn encode_with(self, input: &[u8], sink: &mut dyn FnMut(&[u8])) {
+        let alphabet = self.alphabet;
+        let mut buf = [0_u8; 64];
+        let mut blocks = input.chunks_exact(48);
+        for block in blocks.by_ref() {
+            for (out, group) in buf.chunks_exact_mut(4).zip(block.chunks_exact(3)) {
+                assert!(group.len() == 3, "group length mismatch");
+                let value = (u32::from(group[0]) << 16_u32)
+                    | (u32::from(group[1]) << 8_u32)
+                    | u32::from(group[2]);
+                for (i, symbol) in out.iter_mut().enumerate() {
+                    *symbol = alphabet[((value >> (18 - 6 * i)) & 0x3F) as usize];
+                }
+            }
+            sink(&buf);
+        }
+
+        let rest = blocks.remainder();
+        let mut len = 0;
+        let mut groups = rest.chunks(3);
+        for group in groups.by_ref() {
+            let value = group.iter().enumerate().fold(0_u32, |acc, (i, &byte)| {
+                acc | (u32::from(byte) << (16 - 8 * i))
+            });
+            let symbols = group.len() + 1;
+            for i in 0..symbols {
+                buf[len] = alphabet[((value >> (18 - 6 * i)) & 0x3F) as usize];
+                len += 1;
+            }
+            if self.padding {
+                buf[len..len + 4 - symbols].fill(b'=');
+                len += 4 - symbols;
+            }
+        }
+        sink(&buf[..len]);
+    }
+
+    /// Encodes `input` into a [`ByteChunk`](crate::ByteChunk) without allocating.
+    /// Returns `None` if the output does not fit in [`ByteChunk::LEN`](crate::ByteChunk::LEN) bytes.
+    #[inline]
+    #[must_use]
+    pub fn encode_chunk(self, input: &[u8]) -> Option<crate::ByteChunk> {
+        super::encode_to_chunk(self.encoded_len(input.len()), |sink| {
+            self.encode_with(input, sink);
+        })
+    }
+
+    /// Encodes `input` into a string, which is stored in a chunk if it fits.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn encode(self, input: &[u8]) -> crate::StringData<'static> {
+        super::encode_to_string(self.encoded_len(input.len()), |sink| {
+            self.encode_with(input, sink);
+        })
+    }
+
+    /// Appends the encoding of `input` to a string builder.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn encode_into(self, input: &[u8], out: &mut crate::SharedStrBuilder) {
+        super::encode_to_str_builder(out, self.encoded_len(input.len()), |sink| {
+            self.encode_with(input, sink);
+        });
+    }
+
+    /// Appends the encoding of `input` to a bytes builder.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn encode_into_bytes(self, input: &[u8], out: &mut crate::SharedBytesBuilder) {
+        out.reserve(self.encoded_len(input.len()));
+        self.encode_with(input, &mut |bytes| out.extend_from_slice(bytes));
+    }
+
+    /// Returns the maximum length of the decoded output for `len` bytes of input.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    const fn max_decoded_len(len: usize) -> usize {
+        (len * 3) >> 2_u32
+    }
+
+    /// Decodes the input chunks, passing the output to `sink` in blocks.
+    #[cfg(feature = "alloc")]
+    fn decode_with<'c, I: Iterator<Item = &'c [u8]>>(
+        self,
+        chunks: I,
+        sink: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), super::DecodeError> {
+        let mut buf = [0_u8; 48];
+        let mut filled = 0;
+        // the values of the symbols of the current group
+        let mut group = 0_u32;
+        let mut symbols = 0;
+        let mut pads = 0;
+        let mut position = 0;
+
+        for chunk in chunks {
+            for &byte in chunk {
+                if byte == b'=' && self.padding && symbols >= 2 && symbols + pads < 4 {
+                    pads += 1;
+                    position += 1;
+                    continue;
+                }
+                if byte == b'=' || pads != 0 {
+                    return Err(super::DecodeError::InvalidPadding { position });
+                }
+                let value = self.decode[usize::from(byte)];
+                if value == INVALID {
+                    return Err(super::DecodeError::InvalidByte { position, byte });
+                }
+                group = (group << 6_u32) | u32::from(value);
+                symbols += 1;
+                position += 1;
+                if symbols == 4 {
+                    buf[filled..filled + 3].copy_from_slice(&group.to_be_bytes()[1..]);
+                    filled += 3;
+                    group = 0;
+                    symbols = 0;
+                    if filled == buf.len() {
+                        sink(&buf);
+                        filled = 0;
+                    }
+                }
+            }
+        }
+
+        if symbols != 0 {
+            if symbols == 1 {
+                return Err(super::DecodeError::InvalidLength { length: position });
+            }
+            if self.padding && symbols + pads != 4 {
+                return Err(super::DecodeError::InvalidPadding { position });
+            }
+            // the unused low bits of the last symbol must be zero
+            let unused = if symbols == 2 { 4_u32 } else { 2_u32 };
+            if group & ((1_u32 << unused) - 1) != 0 {
+                return Err(super::DecodeError::InvalidLastSymbol {
+                    position: position - pads - 1,
+                });
+            }
+            let bytes = (group >> unused).to_be_bytes();
+            let len = symbols - 1;
+            buf[filled..filled + len].copy_from_slice(&bytes[4 - len..]);
+            filled += len;
+        }
+        sink(&buf[..filled]);
+        Ok(())
+    }
+
+    /// Decodes base64 input such as a [`ByteData`](crate::ByteData), [`StringData`](crate::StringData), or `&str`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not valid for this alphabet and padding.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode<T: AsRef<[u8]>>(
+        self,
+        input: T,
+    ) -> Result<crate::ByteData<'static>, super::DecodeError> {
+        let input = input.as_ref();
+        super::decode_to_data(
+            core::iter::once(input),
+            Self::max_decoded_len(input.len()),
+            |chunks, sink| self.decode_with(chunks, sink),
+        )
+    }
+
+    /// Decodes base64 input held in a queue, which may be split into chunks at any position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not valid for this alphabet and padding.
+    #[cfg(all(feature = "alloc", feature = "queue"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "queue"))))]
+    #[inline]
+    pub fn decode_queue(
+        self,
+        input: &crate::queue::ByteQueue<'_>,
+    ) -> Result<crate::ByteData<'static>, super::DecodeError> {
+        super::decode_to_data(
+            input.chunks().map(crate::ByteData::as_slice),
+            Self::max_decoded_len(input.len()),
+            |chunks, sink| self.decode_with(chunks, sink),
+        )
+    }
+}
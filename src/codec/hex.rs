@@ -0,0 +1,166 @@
+/// Hexadecimal encoding, two symbols per byte.
+///
+/// Decoding accepts both lower and upper case digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hex {
+    digits: &'static [u8; 16],
+}
+
+impl Hex {
+    /// Hex encoding using lower case digits.
+    pub const LOWER: Self = Self {
+        digits: b"0123456789abcdef",
+    };
+
+    /// Hex encoding using upper case digits.
+    pub const UPPER: Self = Self {
+        digits: b"0123456789ABCDEF",
+    };
+
+    /// Returns the length of the encoded output for `len` bytes of input.
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(self, len: usize) -> usize {
+        len * 2
+    }
+
+    /// Encodes `input`, passing the output to `sink` in blocks.
+    fn encode_with(self, input: &[u8], sink: &mut dyn FnMut(&[u8])) {
+        let mut buf = [0_u8; 64];
+        for block in input.chunks(32) {
+            for (out, &byte) in buf.chunks_exact_mut(2).zip(block) {
+                out.copy_from_slice(&[
+                    self.digits[usize::from(byte >> 4_u8)],
+                    self.digits[usize::from(byte & 0x0F)],
+                ]);
+            }
+            sink(&buf[..block.len() * 2]);
+        }
+    }
+
+    /// Encodes `input` into a [`ByteChunk`](crate::ByteChunk) without allocating.
+    /// Returns `None` if the output does not fit in [`ByteChunk::LEN`](crate::ByteChunk::LEN) bytes.
+    #[inline]
+    #[must_use]
+    pub fn encode_chunk(self, input: &[u8]) -> Option<crate::ByteChunk> {
+        super::encode_to_chunk(self.encoded_len(input.len()), |sink| {
+            self.encode_with(input, sink);
+        })
+    }
+
+    /// Encodes `input` into a string, which is stored in a chunk if it fits.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn encode(self, input: &[u8]) -> crate::StringData<'static> {
+        super::encode_to_string(self.encoded_len(input.len()), |sink| {
+            self.encode_with(input, sink);
+        })
+    }
+
+    /// Appends the encoding of `input` to a string builder.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn encode_into(self, input: &[u8], out: &mut crate::SharedStrBuilder) {
+        super::encode_to_str_builder(out, self.encoded_len(input.len()), |sink| {
+            self.encode_with(input, sink);
+        });
+    }
+
+    /// Appends the encoding of `input` to a bytes builder.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn encode_into_bytes(self, input: &[u8], out: &mut crate::SharedBytesBuilder) {
+        out.reserve(self.encoded_len(input.len()));
+        self.encode_with(input, &mut |bytes| out.extend_from_slice(bytes));
+    }
+
+    /// Returns the value of a hex digit.
+    #[cfg(feature = "alloc")]
+    pub(super) const fn digit_value(byte: u8) -> Option<u8> {
+        match byte {
+            b'0'..=b'9' => Some(byte - b'0'),
+            b'a'..=b'f' => Some(byte - b'a' + 10),
+            b'A'..=b'F' => Some(byte - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    /// Decodes the input chunks, passing the output to `sink` in blocks.
+    #[cfg(feature = "alloc")]
+    fn decode_with<'c, I: Iterator<Item = &'c [u8]>>(
+        chunks: I,
+        sink: &mut dyn FnMut(&[u8]),
+    ) -> Result<(), super::DecodeError> {
+        let mut buf = [0_u8; 32];
+        let mut filled = 0;
+        let mut high = None;
+        let mut position = 0;
+        for chunk in chunks {
+            for &byte in chunk {
+                let Some(value) = Self::digit_value(byte) else {
+                    return Err(super::DecodeError::InvalidByte { position, byte });
+                };
+                position += 1;
+                let Some(high_value) = high else {
+                    high = Some(value);
+                    continue;
+                };
+                high = None;
+                buf[filled] = (high_value << 4_u8) | value;
+                filled += 1;
+                if filled == buf.len() {
+                    sink(&buf);
+                    filled = 0;
+                }
+            }
+        }
+        if high.is_some() {
+            return Err(super::DecodeError::InvalidLength { length: position });
+        }
+        sink(&buf[..filled]);
+        Ok(())
+    }
+
+    /// Decodes hex input such as a [`ByteData`](crate::ByteData), [`StringData`](crate::StringData), or `&str`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input contains a non-hex digit or has an odd length.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode<T: AsRef<[u8]>>(
+        self,
+        input: T,
+    ) -> Result<crate::ByteData<'static>, super::DecodeError> {
+        let input = input.as_ref();
+        super::decode_to_data(
+            core::iter::once(input),
+            input.len() >> 1_u32,
+            Self::decode_with,
+        )
+    }
+
+    /// Decodes hex input held in a queue, which may be split into chunks at any position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input contains a non-hex digit or has an odd length.
+    #[cfg(all(feature = "alloc", feature = "queue"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "queue"))))]
+    #[inline]
+    pub fn decode_queue(
+        self,
+        input: &crate::queue::ByteQueue<'_>,
+    ) -> Result<crate::ByteData<'static>, super::DecodeError> {
+        super::decode_to_data(
+            input.chunks().map(crate::ByteData::as_slice),
+            input.len() >> 1_u32,
+            Self::decode_with,
+        )
+    }
+}
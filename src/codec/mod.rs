@@ -0,0 +1,163 @@
+//! # Codec
+//!
+//! This module contains encoders and decoders between binary data and text.
+//!
+//! Encoders write into a [`SharedStrBuilder`](crate::SharedStrBuilder) or [`SharedBytesBuilder`](crate::SharedBytesBuilder),
+//! or into a [`ByteChunk`](crate::ByteChunk) without allocating when the output fits in [`ByteChunk::LEN`](crate::ByteChunk::LEN) bytes.
+//! Decoders accept any `AsRef<[u8]>` input, such as [`ByteData`](crate::ByteData) and [`StringData`](crate::StringData), as well as chunked queues.
+//! Percent-decoding and `application/x-www-form-urlencoded` parsing borrow from the input when nothing has to be decoded.
+//!
+//! ```
+//! use ::bytedata::codec::{Base64, Hex};
+//!
+//! let digest = Hex::LOWER.encode_chunk(&[0xde, 0xad, 0xbe, 0xef]).unwrap();
+//! assert_eq!(digest.as_slice(), b"deadbeef");
+//! let token = Base64::URL_SAFE_NO_PAD.encode_chunk(b"\xfb\xff").unwrap();
+//! assert_eq!(token.as_slice(), b"-_8");
+//! ```
+
+mod base64;
+pub use base64::Base64;
+
+mod hex;
+pub use hex::Hex;
+
+#[cfg(feature = "alloc")]
+mod percent;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use percent::{FormPairs, Percent};
+#[cfg(all(feature = "alloc", feature = "queue"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "queue"))))]
+pub use percent::{FormQueuePairs, FormSerializer};
+
+/// An error returned when decoding invalid input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// A byte that is not part of the alphabet was found at `position`.
+    InvalidByte {
+        /// The position of the byte in the input.
+        position: usize,
+        /// The invalid byte.
+        byte: u8,
+    },
+    /// The input ended in the middle of an encoded value.
+    InvalidLength {
+        /// The length of the input.
+        length: usize,
+    },
+    /// The padding at `position` is missing, misplaced, or followed by more data.
+    InvalidPadding {
+        /// The position of the padding in the input.
+        position: usize,
+    },
+    /// The last symbol at `position` has bits set that are not part of the decoded data.
+    InvalidLastSymbol {
+        /// The position of the symbol in the input.
+        position: usize,
+    },
+}
+
+impl core::fmt::Display for DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Self::InvalidByte { position, byte } => {
+                write!(f, "invalid byte 0x{byte:02x} at position {position}")
+            }
+            Self::InvalidLength { length } => write!(f, "invalid input length {length}"),
+            Self::InvalidPadding { position } => {
+                write!(f, "invalid padding at position {position}")
+            }
+            Self::InvalidLastSymbol { position } => {
+                write!(f, "invalid last symbol at position {position}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for DecodeError {}
+
+/// Writes encoded or decoded bytes into a chunk. The caller has to make sure the output fits.
+struct ChunkSink<'s>(&'s mut crate::ByteChunk);
+
+impl ChunkSink<'_> {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let pushed = self.0.push(byte);
+            debug_assert!(pushed, "chunk output overflow");
+        }
+    }
+}
+
+/// Decodes the chunks of input with `decode`, into a chunk if at most `max_len` bytes fit in one or otherwise into a shared buffer.
+#[cfg(feature = "alloc")]
+fn decode_to_data<'c, I, F>(
+    chunks: I,
+    max_len: usize,
+    decode: F,
+) -> Result<crate::ByteData<'static>, DecodeError>
+where
+    I: Iterator<Item = &'c [u8]>,
+    F: FnOnce(I, &mut dyn FnMut(&[u8])) -> Result<(), DecodeError>,
+{
+    if max_len <= crate::ByteChunk::LEN {
+        let mut chunk = crate::ByteChunk::default();
+        let mut sink = ChunkSink(&mut chunk);
+        decode(chunks, &mut |bytes| sink.write(bytes))?;
+        return Ok(crate::ByteData::from_chunk_slice(chunk.as_slice()));
+    }
+    let mut builder = crate::SharedBytesBuilder::with_capacity(max_len);
+    decode(chunks, &mut |bytes| builder.extend_from_slice(bytes))?;
+    Ok(builder.into())
+}
+
+/// Encodes `input` into a chunk, if the `encoded_len` fits.
+fn encode_to_chunk<F>(encoded_len: usize, encode: F) -> Option<crate::ByteChunk>
+where
+    F: FnOnce(&mut dyn FnMut(&[u8])),
+{
+    if encoded_len > crate::ByteChunk::LEN {
+        return None;
+    }
+    let mut chunk = crate::ByteChunk::default();
+    let mut sink = ChunkSink(&mut chunk);
+    encode(&mut |bytes| sink.write(bytes));
+    Some(chunk)
+}
+
+/// Encodes into a `StringData`, using a chunk if the output fits and a shared buffer otherwise.
+#[cfg(feature = "alloc")]
+fn encode_to_string<F>(encoded_len: usize, encode: F) -> crate::StringData<'static>
+where
+    F: FnOnce(&mut dyn FnMut(&[u8])),
+{
+    let data = if encoded_len <= crate::ByteChunk::LEN {
+        let mut chunk = crate::ByteChunk::default();
+        let mut sink = ChunkSink(&mut chunk);
+        encode(&mut |bytes| sink.write(bytes));
+        crate::ByteData::from_chunk_slice(chunk.as_slice())
+    } else {
+        let mut builder = crate::SharedBytesBuilder::with_capacity(encoded_len);
+        encode(&mut |bytes| builder.extend_from_slice(bytes));
+        builder.into()
+    };
+    // SAFETY: encoders only output ASCII.
+    unsafe { crate::StringData::from_bytedata_unchecked(data) }
+}
+
+/// Appends encoded ASCII output to a string builder.
+#[cfg(feature = "alloc")]
+fn encode_to_str_builder<F>(out: &mut crate::SharedStrBuilder, encoded_len: usize, encode: F)
+where
+    F: FnOnce(&mut dyn FnMut(&[u8])),
+{
+    out.reserve(encoded_len);
+    encode(&mut |bytes| {
+        // SAFETY: encoders only output ASCII.
+        out.push_str(unsafe { core::str::from_utf8_unchecked(bytes) });
+    });
+}
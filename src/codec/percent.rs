@@ -0,0 +1,284 @@
+use crate::{ByteData, SharedBytesBuilder, SharedStrBuilder, StringData};
+
+/// Percent-encoding as used in URLs, either for URL components or for `application/x-www-form-urlencoded` data.
+///
+/// Decoding is lenient: a `%` that is not followed by two hex digits is kept as it is,
+/// and invalid UTF-8 in the decoded bytes is replaced with `U+FFFD`.
+/// Decoded data borrows from the input unless decoding actually changes any bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percent {
+    form: bool,
+}
+
+impl Percent {
+    /// Percent-encoding of URL components as described in RFC 3986.
+    ///
+    /// Encoding leaves the unreserved characters `A-Z a-z 0-9 - . _ ~` as they are, and decoding keeps `+` as it is.
+    pub const COMPONENT: Self = Self { form: false };
+
+    /// The `application/x-www-form-urlencoded` encoding, where a space is written as `+`.
+    ///
+    /// Encoding leaves the characters `A-Z a-z 0-9 * - . _` as they are.
+    pub const FORM: Self = Self { form: true };
+
+    /// Returns `true` if `byte` is written as it is when encoding.
+    const fn is_unescaped(self, byte: u8) -> bool {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' => true,
+            b'~' => !self.form,
+            b'*' => self.form,
+            _ => false,
+        }
+    }
+
+    /// Returns the decoded byte and the length of the sequence, if `input` starts with something that decoding replaces.
+    fn decoded_at(self, input: &[u8]) -> Option<(u8, usize)> {
+        match *input {
+            [b'+', ..] if self.form => Some((b' ', 1)),
+            [b'%', high, low, ..] => Some((
+                (super::Hex::digit_value(high)? << 4_u8) | super::Hex::digit_value(low)?,
+                3,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Decodes the escape sequences in `input`, and `+` as a space for [`Percent::FORM`].
+    ///
+    /// Returns the input itself if nothing has to be decoded and it is valid UTF-8.
+    /// Otherwise the decoded bytes are copied.
+    #[must_use]
+    #[inline]
+    pub fn decode<'a, T: Into<ByteData<'a>>>(self, input: T) -> StringData<'a> {
+        let input = input.into();
+        let bytes = input.as_slice();
+        let Some(first) =
+            (0..bytes.len()).find(|&index| self.decoded_at(&bytes[index..]).is_some())
+        else {
+            return StringData::from_utf8_lossy(input);
+        };
+
+        let mut out = SharedBytesBuilder::with_capacity(bytes.len());
+        out.extend_from_slice(&bytes[..first]);
+        let mut index = first;
+        let mut run = first;
+        while index < bytes.len() {
+            let Some((byte, len)) = self.decoded_at(&bytes[index..]) else {
+                index += 1;
+                continue;
+            };
+            out.extend_from_slice(&bytes[run..index]);
+            out.extend_from_slice(&[byte]);
+            index += len;
+            run = index;
+        }
+        out.extend_from_slice(&bytes[run..]);
+
+        StringData::from_utf8_lossy(out.into())
+    }
+
+    /// Appends the encoding of `input` to a string builder.
+    #[inline]
+    pub fn encode_into(self, input: &[u8], out: &mut SharedStrBuilder) {
+        let escapes = input
+            .iter()
+            .filter(|&&byte| !self.is_unescaped(byte))
+            .count();
+        out.reserve(input.len() + escapes * 2);
+        let mut run = 0;
+        for (index, &byte) in input.iter().enumerate() {
+            if self.is_unescaped(byte) {
+                continue;
+            }
+            // SAFETY: the run only contains ASCII characters.
+            out.push_str(unsafe { core::str::from_utf8_unchecked(&input[run..index]) });
+            if byte == b' ' && self.form {
+                out.push('+');
+            } else {
+                out.push('%');
+                super::Hex::UPPER.encode_into(&[byte], out);
+            }
+            run = index + 1;
+        }
+        // SAFETY: the run only contains ASCII characters.
+        out.push_str(unsafe { core::str::from_utf8_unchecked(&input[run..]) });
+    }
+
+    /// Encodes `input` into a string, which is stored in a chunk if it fits.
+    #[must_use]
+    #[inline]
+    pub fn encode(self, input: &[u8]) -> StringData<'static> {
+        let mut out = SharedStrBuilder::new();
+        self.encode_into(input, &mut out);
+        out.build_str()
+    }
+
+    /// Appends the encoding of `input` to a queue.
+    ///
+    /// The input itself is pushed if nothing has to be escaped, otherwise the encoded string is copied into a new buffer.
+    #[cfg(feature = "queue")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+    #[inline]
+    pub fn encode_into_queue<'a>(self, input: StringData<'a>, out: &mut crate::StringQueue<'a>) {
+        if input.as_bytes().iter().all(|&byte| self.is_unescaped(byte)) {
+            out.push_back(input);
+        } else {
+            out.push_back(self.encode(input.as_bytes()));
+        }
+    }
+}
+
+/// Splits a pair at the first `=` and decodes the name and value.
+fn decode_pair(pair: ByteData<'_>) -> (StringData<'_>, StringData<'_>) {
+    let (name, value) = match pair.find(b'=') {
+        Some(pos) => (pair.sliced(..pos), pair.into_sliced(pos + 1..)),
+        None => (pair, ByteData::empty()),
+    };
+    (Percent::FORM.decode(name), Percent::FORM.decode(value))
+}
+
+/// An iterator over the name and value pairs of `application/x-www-form-urlencoded` data,
+/// such as a query string or a form body.
+///
+/// Pairs are separated by `&`, and the name is separated from the value by the first `=`.
+/// Empty pairs are skipped, and a pair without `=` has an empty value.
+/// Names and values are decoded with [`Percent::FORM`], so they are slices of the input unless decoding changes them.
+///
+/// ```
+/// use ::bytedata::codec::FormPairs;
+///
+/// let mut pairs = FormPairs::new(b"name=Ferris+the+crab&lang=rust&&flag".as_slice());
+/// let (name, value) = pairs.next().unwrap();
+/// assert_eq!((name.as_str(), value.as_str()), ("name", "Ferris the crab"));
+/// let (name, value) = pairs.next().unwrap();
+/// assert_eq!((name.as_str(), value.as_str()), ("lang", "rust"));
+/// let (name, value) = pairs.next().unwrap();
+/// assert_eq!((name.as_str(), value.as_str()), ("flag", ""));
+/// assert!(pairs.next().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FormPairs<'a> {
+    rest: ByteData<'a>,
+}
+
+impl<'a> FormPairs<'a> {
+    /// Creates an iterator over the pairs in `input`.
+    #[inline]
+    #[must_use]
+    pub fn new<T: Into<ByteData<'a>>>(input: T) -> Self {
+        Self { rest: input.into() }
+    }
+}
+
+impl<'a> Iterator for FormPairs<'a> {
+    type Item = (StringData<'a>, StringData<'a>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.rest.is_empty() {
+            let pair = if let Some(pos) = self.rest.find(b'&') {
+                let pair = self.rest.sliced(..pos);
+                self.rest.make_sliced(pos + 1..);
+                pair
+            } else {
+                core::mem::take(&mut self.rest)
+            };
+            if !pair.is_empty() {
+                return Some(decode_pair(pair));
+            }
+        }
+        None
+    }
+}
+
+impl core::iter::FusedIterator for FormPairs<'_> {}
+
+/// An iterator over the name and value pairs of `application/x-www-form-urlencoded` data held in a [`ByteQueue`](crate::ByteQueue).
+///
+/// This works like [`FormPairs`]. Pairs that are held in a single chunk of the queue are not copied unless decoding changes them.
+#[cfg(feature = "queue")]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+pub struct FormQueuePairs<'a, 'q> {
+    inner: crate::queue::SplitOn<'a, 'q, u8>,
+}
+
+#[cfg(feature = "queue")]
+impl<'a, 'q> FormQueuePairs<'a, 'q> {
+    /// Creates an iterator over the pairs in `input`.
+    #[inline]
+    #[must_use]
+    pub fn new(input: &'q crate::ByteQueue<'a>) -> Self {
+        Self {
+            inner: input.split(b'&'),
+        }
+    }
+}
+
+#[cfg(feature = "queue")]
+impl core::fmt::Debug for FormQueuePairs<'_, '_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FormQueuePairs").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "queue")]
+impl<'a> Iterator for FormQueuePairs<'a, '_> {
+    type Item = (StringData<'a>, StringData<'a>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .find(|pair| !pair.is_empty())
+            .map(|pair| decode_pair(pair.into()))
+    }
+}
+
+/// Writes `application/x-www-form-urlencoded` name and value pairs into a [`StringQueue`](crate::StringQueue).
+///
+/// Names and values that need no escaping are pushed into the queue without copying.
+///
+/// ```
+/// use ::bytedata::{codec::FormSerializer, StringData, StringQueue};
+///
+/// let mut queue = StringQueue::new();
+/// FormSerializer::new(&mut queue)
+///     .append_pair("q", "bytes & strings")
+///     .append_pair("page", "2");
+/// assert_eq!(StringData::from(queue).as_str(), "q=bytes+%26+strings&page=2");
+/// ```
+#[cfg(feature = "queue")]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[derive(Debug)]
+pub struct FormSerializer<'a, 'q> {
+    out: &'q mut crate::StringQueue<'a>,
+    first: bool,
+}
+
+#[cfg(feature = "queue")]
+impl<'a, 'q> FormSerializer<'a, 'q> {
+    /// Creates a serializer appending to `out`. If the queue is not empty, the first pair is preceded by a `&`.
+    #[inline]
+    #[must_use]
+    pub fn new(out: &'q mut crate::StringQueue<'a>) -> Self {
+        let first = out.is_empty();
+        Self { out, first }
+    }
+
+    /// Appends a name and value pair.
+    #[inline]
+    pub fn append_pair<N: Into<StringData<'a>>, V: Into<StringData<'a>>>(
+        &mut self,
+        name: N,
+        value: V,
+    ) -> &mut Self {
+        if !core::mem::take(&mut self.first) {
+            self.out.push_back("&");
+        }
+        Percent::FORM.encode_into_queue(name.into(), self.out);
+        self.out.push_back("=");
+        Percent::FORM.encode_into_queue(value.into(), self.out);
+        self
+    }
+}
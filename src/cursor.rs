@@ -0,0 +1,585 @@
+use crate::ByteData;
+
+/// An error returned when a [`ByteCursor`] read needs more bytes than are left.
+///
+/// The position of the cursor is left unchanged when a read fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnexpectedEnd {
+    /// The number of bytes the read needed.
+    pub needed: usize,
+    /// The number of bytes that were left after the position of the cursor.
+    pub remaining: usize,
+}
+
+impl core::fmt::Display for UnexpectedEnd {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "unexpected end of data: needed {} bytes but only {} remain",
+            self.needed, self.remaining
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for UnexpectedEnd {}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<UnexpectedEnd> for std::io::Error {
+    #[inline]
+    #[allow(clippy::std_instead_of_core)]
+    fn from(err: UnexpectedEnd) -> Self {
+        Self::new(std::io::ErrorKind::UnexpectedEof, err)
+    }
+}
+
+/// The data a [`ByteCursor`] reads from.
+#[derive(Clone)]
+enum Source<'a> {
+    Data(ByteData<'a>),
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    Queue(crate::ByteQueue<'a>),
+}
+
+/// A borrowed [`Source`], which can be matched without binding by reference.
+#[derive(Clone, Copy)]
+enum SourceRef<'s, 'a> {
+    Data(&'s ByteData<'a>),
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    Queue(&'s crate::ByteQueue<'a>),
+}
+
+/// A cursor over a [`ByteData`] or [`ByteQueue`](crate::ByteQueue) that reads bytes and integers without consuming the data.
+///
+/// Integer and float reads are big-endian unless their name ends in `_le`.
+/// Reads that need more bytes than are left return [`UnexpectedEnd`] and leave the position unchanged.
+/// The position may be moved past the end of the data, in which case all reads fail.
+///
+/// [`ByteCursor::read_bytes`] returns slices of the underlying data, without copying unless the bytes are split between chunks of a queue.
+///
+/// ```
+/// use ::bytedata::{ByteCursor, ByteData};
+///
+/// let mut cursor = ByteCursor::new(ByteData::from_static(b"\x00\x2A\x01\x00\x00\x00rest"));
+/// assert_eq!(cursor.read_u16(), Ok(42));
+/// assert_eq!(cursor.peek_u32_le(), Ok(1));
+/// assert_eq!(cursor.read_u32(), Ok(0x0100_0000));
+/// assert_eq!(cursor.read_bytes(4).unwrap().as_slice(), b"rest");
+/// assert!(cursor.read_u8().is_err());
+/// ```
+#[derive(Clone)]
+pub struct ByteCursor<'a> {
+    source: Source<'a>,
+    position: usize,
+    /// The index of a chunk of a queue that starts at or before the position, used to avoid walking the queue from the start.
+    chunk_index: usize,
+    /// The position in the queue at which the chunk at `chunk_index` starts.
+    chunk_start: usize,
+}
+
+macro_rules! cursor_read {
+    ($($ty:ident: $read:ident, $peek:ident, $read_le:ident, $peek_le:ident;)*) => {
+        $(
+            #[doc = concat!("Reads a big-endian `", stringify!($ty), "` and advances the position.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`UnexpectedEnd`] if not enough bytes are left.
+            #[inline]
+            pub fn $read(&mut self) -> Result<$ty, UnexpectedEnd> {
+                self.read_array().map($ty::from_be_bytes)
+            }
+
+            #[doc = concat!("Reads a big-endian `", stringify!($ty), "` without advancing the position.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`UnexpectedEnd`] if not enough bytes are left.
+            #[inline]
+            pub fn $peek(&self) -> Result<$ty, UnexpectedEnd> {
+                self.peek_array().map($ty::from_be_bytes)
+            }
+
+            #[doc = concat!("Reads a little-endian `", stringify!($ty), "` and advances the position.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`UnexpectedEnd`] if not enough bytes are left.
+            #[inline]
+            pub fn $read_le(&mut self) -> Result<$ty, UnexpectedEnd> {
+                self.read_array().map($ty::from_le_bytes)
+            }
+
+            #[doc = concat!("Reads a little-endian `", stringify!($ty), "` without advancing the position.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`UnexpectedEnd`] if not enough bytes are left.
+            #[inline]
+            pub fn $peek_le(&self) -> Result<$ty, UnexpectedEnd> {
+                self.peek_array().map($ty::from_le_bytes)
+            }
+        )*
+    };
+}
+
+impl<'a> ByteCursor<'a> {
+    /// Creates a cursor at the start of `data`.
+    #[inline]
+    #[must_use]
+    pub fn new<T: Into<ByteData<'a>>>(data: T) -> Self {
+        Self::with_source(Source::Data(data.into()))
+    }
+
+    /// Creates a cursor at the start of a queue.
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "queue", feature = "alloc"))))]
+    #[inline]
+    #[must_use]
+    pub const fn from_queue(queue: crate::ByteQueue<'a>) -> Self {
+        Self::with_source(Source::Queue(queue))
+    }
+
+    const fn with_source(source: Source<'a>) -> Self {
+        Self {
+            source,
+            position: 0,
+            chunk_index: 0,
+            chunk_start: 0,
+        }
+    }
+
+    #[allow(clippy::ref_patterns)]
+    const fn source(&self) -> SourceRef<'_, 'a> {
+        match self.source {
+            Source::Data(ref data) => SourceRef::Data(data),
+            #[cfg(all(feature = "queue", feature = "alloc"))]
+            Source::Queue(ref queue) => SourceRef::Queue(queue),
+        }
+    }
+
+    /// Returns the total length of the data, including the bytes before the position.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self.source() {
+            SourceRef::Data(data) => data.len(),
+            #[cfg(all(feature = "queue", feature = "alloc"))]
+            SourceRef::Queue(queue) => queue.len(),
+        }
+    }
+
+    /// Returns `true` if the data is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the position of the cursor in the data.
+    #[inline]
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of bytes left after the position, or `0` if the position is past the end.
+    #[inline]
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.len().saturating_sub(self.position)
+    }
+
+    /// Moves the cursor to `position`, which may be past the end of the data.
+    #[inline]
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+        if position < self.chunk_start {
+            self.chunk_index = 0;
+            self.chunk_start = 0;
+        }
+    }
+
+    /// Moves the cursor back to the start of the data.
+    #[inline]
+    pub fn rewind(&mut self) {
+        self.set_position(0);
+    }
+
+    /// Advances the position by `cnt` bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `cnt` bytes are left.
+    #[inline]
+    pub fn advance(&mut self, cnt: usize) -> Result<(), UnexpectedEnd> {
+        self.check(cnt)?;
+        self.set_position(self.position + cnt);
+        Ok(())
+    }
+
+    /// Returns the data from the position to the end, as a slice of the underlying data if it is held in a single chunk.
+    #[inline]
+    #[must_use]
+    pub fn remaining_data(&self) -> ByteData<'a> {
+        self.peek_bytes(self.remaining()).unwrap_or_default()
+    }
+
+    /// Returns the underlying data if the cursor was created from a [`ByteData`].
+    #[inline]
+    #[must_use]
+    pub const fn get_data(&self) -> Option<&ByteData<'a>> {
+        match self.source() {
+            SourceRef::Data(data) => Some(data),
+            #[cfg(all(feature = "queue", feature = "alloc"))]
+            SourceRef::Queue(_) => None,
+        }
+    }
+
+    /// Returns the underlying queue if the cursor was created from a [`ByteQueue`](crate::ByteQueue).
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "queue", feature = "alloc"))))]
+    #[inline]
+    #[must_use]
+    pub const fn get_queue(&self) -> Option<&crate::ByteQueue<'a>> {
+        match self.source() {
+            SourceRef::Data(_) => None,
+            SourceRef::Queue(queue) => Some(queue),
+        }
+    }
+
+    /// Checks that at least `needed` bytes are left.
+    const fn check(&self, needed: usize) -> Result<(), UnexpectedEnd> {
+        let remaining = self.remaining();
+        if needed > remaining {
+            return Err(UnexpectedEnd { needed, remaining });
+        }
+        Ok(())
+    }
+
+    /// Returns the index and start position of the queue chunk that holds `position`, starting the walk from the cached chunk.
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    fn locate(&self, queue: &crate::ByteQueue<'a>, position: usize) -> (usize, usize) {
+        let (mut index, mut start) = if position >= self.chunk_start {
+            (self.chunk_index, self.chunk_start)
+        } else {
+            (0, 0)
+        };
+        for chunk in queue.chunks().skip(index) {
+            if position < start + chunk.len() {
+                break;
+            }
+            start += chunk.len();
+            index += 1;
+        }
+        (index, start)
+    }
+
+    /// Copies the bytes after the position into `out`. The caller has to check that enough bytes are left.
+    fn copy_to(&self, out: &mut [u8]) {
+        if out.is_empty() {
+            return;
+        }
+        match self.source() {
+            SourceRef::Data(data) => {
+                out.copy_from_slice(&data.as_slice()[self.position..self.position + out.len()]);
+            }
+            #[cfg(all(feature = "queue", feature = "alloc"))]
+            SourceRef::Queue(queue) => {
+                let (index, start) = self.locate(queue, self.position);
+                let mut offset = self.position - start;
+                let mut filled = 0;
+                for chunk in queue.chunks().skip(index) {
+                    if filled == out.len() {
+                        break;
+                    }
+                    let chunk = &chunk.as_slice()[offset..];
+                    let len = chunk.len().min(out.len() - filled);
+                    out[filled..filled + len].copy_from_slice(&chunk[..len]);
+                    filled += len;
+                    offset = 0;
+                }
+            }
+        }
+    }
+
+    /// Moves the position forward after a read, caching the chunk that holds the new position.
+    fn advance_unchecked(&mut self, cnt: usize) {
+        self.position += cnt;
+        #[cfg(all(feature = "queue", feature = "alloc"))]
+        if let SourceRef::Queue(queue) = self.source() {
+            (self.chunk_index, self.chunk_start) = self.locate(queue, self.position);
+        }
+    }
+
+    /// Reads `N` bytes into an array without advancing the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `N` bytes are left.
+    #[inline]
+    pub fn peek_array<const N: usize>(&self) -> Result<[u8; N], UnexpectedEnd> {
+        self.check(N)?;
+        let mut out = [0_u8; N];
+        self.copy_to(&mut out);
+        Ok(out)
+    }
+
+    /// Reads `N` bytes into an array and advances the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `N` bytes are left.
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], UnexpectedEnd> {
+        let out = self.peek_array()?;
+        self.advance_unchecked(N);
+        Ok(out)
+    }
+
+    /// Fills `buf` with the bytes after the position and advances the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `buf.len()` bytes are left, in which case `buf` is left unchanged.
+    #[inline]
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), UnexpectedEnd> {
+        self.check(buf.len())?;
+        self.copy_to(buf);
+        self.advance_unchecked(buf.len());
+        Ok(())
+    }
+
+    /// Returns the next `len` bytes without advancing the position.
+    ///
+    /// The bytes are a slice of the underlying data if they are held in a single chunk.
+    /// Otherwise they are copied, into a [`ByteChunk`](crate::ByteChunk) if they fit and into a shared buffer if not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `len` bytes are left.
+    #[inline]
+    pub fn peek_bytes(&self, len: usize) -> Result<ByteData<'a>, UnexpectedEnd> {
+        self.check(len)?;
+        if len == 0 {
+            return Ok(ByteData::empty());
+        }
+        match self.source() {
+            SourceRef::Data(data) => Ok(data.sliced(self.position..self.position + len)),
+            #[cfg(all(feature = "queue", feature = "alloc"))]
+            SourceRef::Queue(queue) => Ok(self.queue_bytes(queue, len)),
+        }
+    }
+
+    /// Returns `len` bytes from a queue, which are known to be available.
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    fn queue_bytes(&self, queue: &crate::ByteQueue<'a>, len: usize) -> ByteData<'a> {
+        let (index, start) = self.locate(queue, self.position);
+        if let Some(chunk) = queue.chunks().nth(index) {
+            let offset = self.position - start;
+            if offset + len <= chunk.len() {
+                return chunk.sliced(offset..offset + len);
+            }
+        }
+        if len <= crate::ByteChunk::LEN {
+            let mut buf = [0_u8; crate::ByteChunk::LEN];
+            self.copy_to(&mut buf[..len]);
+            return ByteData::from_chunk_slice(&buf[..len]);
+        }
+        ByteData::from(queue.slice(self.position..self.position + len))
+    }
+
+    /// Returns the next `len` bytes and advances the position.
+    ///
+    /// The bytes are a slice of the underlying data if they are held in a single chunk.
+    /// Otherwise they are copied, into a [`ByteChunk`](crate::ByteChunk) if they fit and into a shared buffer if not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `len` bytes are left.
+    #[inline]
+    pub fn read_bytes(&mut self, len: usize) -> Result<ByteData<'a>, UnexpectedEnd> {
+        let out = self.peek_bytes(len)?;
+        self.advance_unchecked(len);
+        Ok(out)
+    }
+
+    /// Returns the next `len` bytes as a queue of slices of the underlying data and advances the position.
+    ///
+    /// This never copies the bytes, even if they are split between chunks of a queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if fewer than `len` bytes are left.
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "queue", feature = "alloc"))))]
+    #[inline]
+    pub fn read_queue(&mut self, len: usize) -> Result<crate::ByteQueue<'a>, UnexpectedEnd> {
+        self.check(len)?;
+        if len == 0 {
+            return Ok(crate::ByteQueue::new());
+        }
+        let out = match self.source() {
+            SourceRef::Data(data) => {
+                crate::ByteQueue::with_item(data.sliced(self.position..self.position + len))
+            }
+            SourceRef::Queue(queue) => queue.slice(self.position..self.position + len),
+        };
+        self.advance_unchecked(len);
+        Ok(out)
+    }
+
+    /// Reads a byte and advances the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if no bytes are left.
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, UnexpectedEnd> {
+        self.read_array().map(|[byte]| byte)
+    }
+
+    /// Reads a byte without advancing the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if no bytes are left.
+    #[inline]
+    pub fn peek_u8(&self) -> Result<u8, UnexpectedEnd> {
+        self.peek_array().map(|[byte]| byte)
+    }
+
+    /// Reads an `i8` and advances the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if no bytes are left.
+    #[inline]
+    pub fn read_i8(&mut self) -> Result<i8, UnexpectedEnd> {
+        self.read_array().map(i8::from_be_bytes)
+    }
+
+    /// Reads an `i8` without advancing the position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnexpectedEnd`] if no bytes are left.
+    #[inline]
+    pub fn peek_i8(&self) -> Result<i8, UnexpectedEnd> {
+        self.peek_array().map(i8::from_be_bytes)
+    }
+
+    cursor_read! {
+        u16: read_u16, peek_u16, read_u16_le, peek_u16_le;
+        u32: read_u32, peek_u32, read_u32_le, peek_u32_le;
+        u64: read_u64, peek_u64, read_u64_le, peek_u64_le;
+        i16: read_i16, peek_i16, read_i16_le, peek_i16_le;
+        i32: read_i32, peek_i32, read_i32_le, peek_i32_le;
+        i64: read_i64, peek_i64, read_i64_le, peek_i64_le;
+        f32: read_f32, peek_f32, read_f32_le, peek_f32_le;
+        f64: read_f64, peek_f64, read_f64_le, peek_f64_le;
+    }
+}
+
+impl<'a> From<ByteData<'a>> for ByteCursor<'a> {
+    #[inline]
+    fn from(data: ByteData<'a>) -> Self {
+        Self::new(data)
+    }
+}
+
+#[cfg(all(feature = "queue", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "queue", feature = "alloc"))))]
+impl<'a> From<crate::ByteQueue<'a>> for ByteCursor<'a> {
+    #[inline]
+    fn from(queue: crate::ByteQueue<'a>) -> Self {
+        Self::from_queue(queue)
+    }
+}
+
+impl core::fmt::Debug for ByteCursor<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ByteCursor")
+            .field("position", &self.position)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::io::Seek for ByteCursor<'_> {
+    #[inline]
+    #[allow(clippy::std_instead_of_core)]
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            std::io::SeekFrom::Start(offset) => {
+                let position = usize::try_from(offset).unwrap_or(usize::MAX);
+                self.set_position(position);
+                return Ok(offset);
+            }
+            std::io::SeekFrom::End(offset) => (self.len(), offset),
+            std::io::SeekFrom::Current(offset) => (self.position, offset),
+        };
+        let position = isize::try_from(offset)
+            .ok()
+            .and_then(|offset| base.checked_add_signed(offset))
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?;
+        self.set_position(position);
+        Ok(position as u64)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> std::io::Result<u64> {
+        Ok(self.position as u64)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::io::Read for ByteCursor<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(self.remaining());
+        self.copy_to(&mut buf[..len]);
+        self.advance_unchecked(len);
+        Ok(len)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::io::BufRead for ByteCursor<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.remaining() == 0 {
+            return Ok(&[]);
+        }
+        match self.source() {
+            SourceRef::Data(data) => Ok(&data.as_slice()[self.position..]),
+            #[cfg(all(feature = "queue", feature = "alloc"))]
+            SourceRef::Queue(queue) => {
+                let (index, start) = self.locate(queue, self.position);
+                Ok(queue
+                    .chunks()
+                    .nth(index)
+                    .map_or(&[], |chunk| &chunk.as_slice()[self.position - start..]))
+            }
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, amount: usize) {
+        self.advance_unchecked(amount.min(self.remaining()));
+    }
+}
@@ -306,10 +306,17 @@ impl ExtBytes {
         }
     }
 
+    #[cfg(feature = "bytes_1")]
     pub(crate) fn with_inner<T: core::any::Any, R, F: FnOnce(&T, &[u8]) -> R>(
         &self,
         fun: F,
     ) -> Option<R> {
+        let t_val = self.inner_ref::<T>()?;
+        Some(fun(t_val, self.as_slice()))
+    }
+
+    /// Get a reference to the inner value of the `ExtBytes` instance if the type matches.
+    pub(crate) fn inner_ref<T: core::any::Any>(&self) -> Option<&T> {
         debug_assert_eq!(
             self.magic[0], KIND_EXT_BYTES,
             "invalid magic number in ExtBytes"
@@ -332,12 +339,37 @@ impl ExtBytes {
         }
         // SAFETY: `dd.data` is a valid pointer to the container data located at `offset`.
         let t_val = unsafe { dd.data.cast::<u8>().add(offset) };
-        // SAFETY: `t_val` should now be cast to `*const T`.
-        let t_val = unsafe { &*t_val.cast::<T>() };
-        // SAFETY: `dd.ptr` is a valid pointer to the slice start.
-        Some(fun(t_val, unsafe {
-            core::slice::from_raw_parts(dd.ptr, dd.len)
-        }))
+        // SAFETY: `t_val` points to a live `T` as the type id has been checked.
+        Some(unsafe { &*t_val.cast::<T>() })
+    }
+
+    /// Move the inner value out of the `ExtBytes` instance if the type matches, there is only one reference, and the slice covers all of the external data.
+    pub(crate) fn into_inner<T: ExternalBytes>(self) -> Result<T, Self> {
+        let Some(t_val) = self.inner_ref::<T>() else {
+            return Err(self);
+        };
+        // SAFETY: `data` is a valid pointer to `ExtBytesRef`.
+        let dd = unsafe { &*self.data };
+        // SAFETY: `ExtBytesRef.data` is a valid pointer to `ExtBytesWrapper`, checked by `inner_ref`.
+        let ee = unsafe { &*dd.data };
+        if ee.ref_count.load(core::sync::atomic::Ordering::Acquire) != 1 {
+            return Err(self);
+        }
+        let full = (T::OPS.as_slice)(t_val);
+        if !core::ptr::eq(full.as_ptr(), dd.ptr) || full.len() != dd.len {
+            return Err(self);
+        }
+        let t_ptr: *const T = t_val;
+        #[allow(clippy::unwrap_used, clippy::unwrap_in_result)]
+        let layout = core::alloc::Layout::from_size_align(ee.alloc, ee.align as usize).unwrap();
+        // SAFETY: the value is only read once and the container is deallocated without dropping it.
+        let value = unsafe { t_ptr.read() };
+        // SAFETY: `dd.data` is a valid pointer to an allocated area with the layout of the container.
+        unsafe { alloc::alloc::dealloc(dd.data.cast::<u8>().cast_mut(), layout) };
+        // SAFETY: `self.data` is a valid pointer to a boxed `ExtBytesRef`.
+        core::mem::drop(unsafe { alloc::boxed::Box::from_raw(self.data.cast_mut()) });
+        core::mem::forget(self);
+        Ok(value)
     }
 
     /// Take the inner value of the `ExtBytes` instance if the type matches and there is only one reference.
@@ -482,4 +514,43 @@ mod test {
             "pointers should be equal"
         );
     }
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    /// Check that the original external value can be reclaimed.
+    fn test_bytedata_ext_reclaim() {
+        use alloc::vec::Vec;
+        let data = (0..48).collect::<Vec<u8>>();
+        let ptr = data.as_ptr();
+        let data = ByteData::from_external(data);
+        assert!(data
+            .downcast_external_ref::<alloc::boxed::Box<[u8]>>()
+            .is_none());
+        assert_eq!(
+            data.downcast_external_ref::<Vec<u8>>().map(Vec::len),
+            Some(48)
+        );
+
+        let copy = data.clone();
+        let data = data.try_into_external::<Vec<u8>>().unwrap_err();
+        core::mem::drop(copy);
+
+        let sliced = data.sliced(1..);
+        let sliced = sliced.try_into_external::<Vec<u8>>().unwrap_err();
+        core::mem::drop(sliced);
+
+        let data = data
+            .try_into_external::<alloc::sync::Arc<[u8]>>()
+            .unwrap_err();
+        let data = data.try_into_external::<Vec<u8>>().unwrap();
+        assert_eq!(data.len(), 48);
+        assert!(
+            core::ptr::eq(ptr, data.as_ptr()),
+            "pointers should be equal"
+        );
+
+        let borrowed = ByteData::from_static(b"a static slice of bytes");
+        assert!(borrowed.downcast_external_ref::<Vec<u8>>().is_none());
+        let borrowed = borrowed.try_into_external::<Vec<u8>>().unwrap_err();
+        assert_eq!(borrowed.as_slice(), b"a static slice of bytes");
+    }
 }
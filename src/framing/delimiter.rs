@@ -0,0 +1,152 @@
+use crate::{ByteData, ByteQueue};
+
+use super::{Decoder, Encoder, FrameError};
+
+/// A codec for frames separated by a delimiter, such as `\0` or `\r\n\r\n`.
+///
+/// Decoded frames are slices of the input queue without the delimiter.
+/// At the end of the input any remaining data is returned as the last frame.
+/// Frames longer than the maximum length are reported as an error and skipped up to the next delimiter.
+/// Encoding pushes the frame followed by the delimiter.
+#[derive(Debug, Clone)]
+pub struct DelimiterCodec {
+    delimiter: ByteData<'static>,
+    max_length: usize,
+    /// The number of bytes at the front of the queue known not to start a delimiter.
+    searched: usize,
+    /// Set while skipping the rest of a frame that was too long.
+    discarding: bool,
+}
+
+impl DelimiterCodec {
+    /// Creates a codec for frames separated by `delimiter`, without a maximum frame length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delimiter is empty.
+    #[inline]
+    #[must_use]
+    pub fn new<D: Into<ByteData<'static>>>(delimiter: D) -> Self {
+        let delimiter = delimiter.into();
+        assert!(
+            !delimiter.is_empty(),
+            "DelimiterCodec: the delimiter must not be empty"
+        );
+        Self {
+            delimiter,
+            max_length: usize::MAX,
+            searched: 0,
+            discarding: false,
+        }
+    }
+
+    /// Sets the maximum length of a frame, excluding the delimiter.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_length(mut self, max: usize) -> Self {
+        self.max_length = max;
+        self
+    }
+
+    /// Returns the delimiter that separates the frames.
+    #[inline]
+    #[must_use]
+    pub const fn delimiter(&self) -> &ByteData<'static> {
+        &self.delimiter
+    }
+
+    /// Returns the maximum length of a frame, excluding the delimiter.
+    #[inline]
+    #[must_use]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl<'a> Decoder<'a> for DelimiterCodec {
+    type Item = ByteQueue<'a>;
+    type Error = FrameError;
+
+    #[inline]
+    fn decode(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        let delimiter = self.delimiter.as_slice();
+        loop {
+            let Some(position) = src.find_slice_after(delimiter, self.searched.min(src.len()))
+            else {
+                // a delimiter may start in the last bytes and end in data that has not been pushed yet
+                self.searched = (src.len() + 1).saturating_sub(delimiter.len());
+                if self.discarding {
+                    src.consume(core::mem::take(&mut self.searched));
+                } else if self.searched > self.max_length {
+                    self.discarding = true;
+                    let length = core::mem::take(&mut self.searched);
+                    src.consume(length);
+                    return Err(FrameError::TooLong {
+                        length,
+                        max: self.max_length,
+                    });
+                } else {
+                    // wait for more data
+                }
+                return Ok(None);
+            };
+            self.searched = 0;
+            if core::mem::take(&mut self.discarding) {
+                src.consume(position + delimiter.len());
+                continue;
+            }
+            if position > self.max_length {
+                src.consume(position + delimiter.len());
+                return Err(FrameError::TooLong {
+                    length: position,
+                    max: self.max_length,
+                });
+            }
+            let frame = super::take_frame(src, position);
+            src.consume(delimiter.len());
+            return Ok(Some(frame));
+        }
+    }
+
+    #[inline]
+    fn decode_eof(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+        self.searched = 0;
+        if core::mem::take(&mut self.discarding) || src.is_empty() {
+            *src = ByteQueue::new();
+            return Ok(None);
+        }
+        let frame = core::mem::take(src);
+        if frame.len() > self.max_length {
+            return Err(FrameError::TooLong {
+                length: frame.len(),
+                max: self.max_length,
+            });
+        }
+        Ok(Some(frame))
+    }
+}
+
+impl<'a> Encoder<'a, ByteData<'a>> for DelimiterCodec {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(&mut self, item: ByteData<'a>, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error> {
+        dst.push_back(item);
+        dst.push_back(self.delimiter.clone());
+        Ok(())
+    }
+}
+
+impl<'a> Encoder<'a, ByteQueue<'a>> for DelimiterCodec {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(&mut self, item: ByteQueue<'a>, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error> {
+        dst.append(item);
+        dst.push_back(self.delimiter.clone());
+        Ok(())
+    }
+}
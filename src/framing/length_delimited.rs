@@ -0,0 +1,181 @@
+use crate::{ByteData, ByteQueue};
+
+use super::{Decoder, Encoder, FrameError};
+
+/// A codec for frames that are prefixed with their length.
+///
+/// The length is an unsigned integer of 1 to 8 bytes, big-endian by default, that does not include the prefix itself.
+/// By default the prefix is 4 bytes long and frames are limited to 8 MiB.
+///
+/// Decoded frames are slices of the input queue, and encoding pushes the frame after a prefix held in a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthDelimitedCodec {
+    length_size: usize,
+    little_endian: bool,
+    max_frame_length: usize,
+}
+
+impl LengthDelimitedCodec {
+    /// Creates a codec with a 4 byte big-endian length prefix and a maximum frame length of 8 MiB.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            length_size: 4,
+            little_endian: false,
+            max_frame_length: 8 * 1024 * 1024,
+        }
+    }
+
+    /// Sets the size of the length prefix in bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not in the range `1..=8`.
+    #[inline]
+    #[must_use]
+    pub const fn with_length_size(mut self, size: usize) -> Self {
+        assert!(
+            size >= 1 && size <= 8,
+            "LengthDelimitedCodec: length size must be 1 to 8 bytes"
+        );
+        self.length_size = size;
+        self
+    }
+
+    /// Makes the codec read and write the length prefix in little-endian byte order.
+    #[inline]
+    #[must_use]
+    pub const fn little_endian(mut self) -> Self {
+        self.little_endian = true;
+        self
+    }
+
+    /// Sets the maximum length of a frame, excluding the prefix.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_frame_length(mut self, max: usize) -> Self {
+        self.max_frame_length = max;
+        self
+    }
+
+    /// Returns the size of the length prefix in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn length_size(&self) -> usize {
+        self.length_size
+    }
+
+    /// Returns the maximum length of a frame, excluding the prefix.
+    #[inline]
+    #[must_use]
+    pub const fn max_frame_length(&self) -> usize {
+        self.max_frame_length
+    }
+
+    /// Returns the largest frame length that can be written, which is limited by both the maximum frame length and the prefix size.
+    fn max_encoded_length(&self) -> usize {
+        let max_prefix = u64::MAX >> (64 - 8 * self.length_size);
+        usize::try_from(max_prefix).map_or(self.max_frame_length, |max_prefix| {
+            max_prefix.min(self.max_frame_length)
+        })
+    }
+
+    /// Encodes the length prefix for a frame of `len` bytes.
+    fn encode_length(&self, len: usize) -> Result<ByteData<'static>, FrameError> {
+        let max = self.max_encoded_length();
+        if len > max {
+            return Err(FrameError::TooLong { length: len, max });
+        }
+        let value = len as u64;
+        let prefix = if self.little_endian {
+            let bytes = value.to_le_bytes();
+            ByteData::from_chunk_slice(&bytes[..self.length_size])
+        } else {
+            let bytes = value.to_be_bytes();
+            ByteData::from_chunk_slice(&bytes[8 - self.length_size..])
+        };
+        Ok(prefix)
+    }
+
+    /// Reads the length prefix from the front of `src`, which holds at least `length_size` bytes.
+    fn decode_length(&self, src: &ByteQueue<'_>) -> Result<usize, FrameError> {
+        let mut value = 0_u64;
+        for (index, byte) in src.bytes().take(self.length_size).enumerate() {
+            if self.little_endian {
+                value |= u64::from(byte) << (8 * index);
+            } else {
+                value = (value << 8_u32) | u64::from(byte);
+            }
+        }
+        match usize::try_from(value) {
+            Ok(len) if len <= self.max_frame_length => Ok(len),
+            Ok(len) => Err(FrameError::TooLong {
+                length: len,
+                max: self.max_frame_length,
+            }),
+            Err(_) => Err(FrameError::TooLong {
+                length: usize::MAX,
+                max: self.max_frame_length,
+            }),
+        }
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Decoder<'a> for LengthDelimitedCodec {
+    type Item = ByteQueue<'a>;
+    type Error = FrameError;
+
+    #[inline]
+    fn decode(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < self.length_size {
+            return Ok(None);
+        }
+        let len = self.decode_length(src)?;
+        if src.len() - self.length_size < len {
+            return Ok(None);
+        }
+        src.consume(self.length_size);
+        Ok(Some(super::take_frame(src, len)))
+    }
+
+    #[inline]
+    fn decode_eof(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => Err(FrameError::Incomplete {
+                remaining: src.len(),
+            }),
+        }
+    }
+}
+
+impl<'a> Encoder<'a, ByteData<'a>> for LengthDelimitedCodec {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(&mut self, item: ByteData<'a>, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error> {
+        dst.push_back(self.encode_length(item.len())?);
+        dst.push_back(item);
+        Ok(())
+    }
+}
+
+impl<'a> Encoder<'a, ByteQueue<'a>> for LengthDelimitedCodec {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(&mut self, item: ByteQueue<'a>, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error> {
+        dst.push_back(self.encode_length(item.len())?);
+        dst.append(item);
+        Ok(())
+    }
+}
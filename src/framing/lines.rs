@@ -0,0 +1,204 @@
+use crate::{ByteQueue, StringData, StringQueue, Utf8StreamDecoder};
+
+use super::{Decoder, Encoder, FrameError};
+
+/// A codec for lines of UTF-8 text ending in `\n` or `\r\n`.
+///
+/// Decoding moves the data of the input queue into a text buffer held by the codec,
+/// and takes complete lines from it with [`StringQueue::take_line`].
+/// Lines share the buffers of the input, except for characters that are split between chunks of the queue.
+/// The line ending is not part of the decoded lines, and at the end of the input a last line without a line ending is returned as well.
+///
+/// Lines longer than the maximum length are reported as an error and skipped up to the next line ending.
+/// Encoding pushes the line followed by `\n`.
+///
+/// ```
+/// use ::bytedata::framing::{Decoder, LinesCodec};
+/// use ::bytedata::ByteQueue;
+///
+/// let mut codec = LinesCodec::new();
+/// let mut queue = ByteQueue::new();
+/// queue.push_back(b"GET / HTTP/1.1\r\nHost: exa".as_slice());
+/// assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "GET / HTTP/1.1");
+/// assert!(codec.decode(&mut queue).unwrap().is_none());
+/// queue.push_back(b"mple.com\r\n".as_slice());
+/// assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "Host: example.com");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinesCodec<'a> {
+    buffer: StringQueue<'a>,
+    utf8: Utf8StreamDecoder,
+    max_length: usize,
+    /// The number of bytes at the front of the buffer known not to contain a line ending.
+    searched: usize,
+    /// Set while skipping the rest of a line that was too long.
+    discarding: bool,
+}
+
+impl<'a> LinesCodec<'a> {
+    /// Creates a codec without a maximum line length.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: StringQueue::new(),
+            utf8: Utf8StreamDecoder::new(),
+            max_length: usize::MAX,
+            searched: 0,
+            discarding: false,
+        }
+    }
+
+    /// Sets the maximum length of a line, excluding the line ending.
+    #[inline]
+    #[must_use]
+    pub const fn with_max_length(mut self, max: usize) -> Self {
+        self.max_length = max;
+        self
+    }
+
+    /// Returns the maximum length of a line, excluding the line ending.
+    #[inline]
+    #[must_use]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Returns the decoded text that has not been returned as a line yet.
+    #[inline]
+    #[must_use]
+    pub const fn buffered(&self) -> &StringQueue<'a> {
+        &self.buffer
+    }
+
+    /// Takes the next complete line from the buffer, skipping lines that are too long.
+    fn next_line(&mut self) -> Result<Option<StringQueue<'a>>, FrameError> {
+        loop {
+            let found = self
+                .buffer
+                .as_bytequeue()
+                .find_slice_after(b"\n", self.searched);
+            if found.is_none() {
+                self.searched = self.buffer.len();
+                // a trailing `\r` may be the start of a `\r\n` line ending that has not been pushed yet
+                let length = self.searched - usize::from(self.buffer.ends_with("\r"));
+                if self.discarding {
+                    self.buffer = StringQueue::new();
+                    self.searched = 0;
+                } else if length > self.max_length {
+                    self.discarding = true;
+                    return Err(FrameError::TooLong {
+                        length,
+                        max: self.max_length,
+                    });
+                } else {
+                    // wait for more data
+                }
+                return Ok(None);
+            }
+            self.searched = 0;
+            let line = strip_line_ending(self.buffer.take_line());
+            if core::mem::take(&mut self.discarding) {
+                continue;
+            }
+            if line.len() > self.max_length {
+                return Err(FrameError::TooLong {
+                    length: line.len(),
+                    max: self.max_length,
+                });
+            }
+            return Ok(Some(line));
+        }
+    }
+}
+
+impl Default for LinesCodec<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Removes a trailing `\n` or `\r\n`.
+fn strip_line_ending(line: StringQueue<'_>) -> StringQueue<'_> {
+    let ending = if line.ends_with("\r\n") {
+        2
+    } else if line.ends_with("\n") {
+        1
+    } else {
+        return line;
+    };
+    line.slice(..line.len() - ending)
+}
+
+impl<'a> Decoder<'a> for LinesCodec<'a> {
+    type Item = StringQueue<'a>;
+    type Error = FrameError;
+
+    #[inline]
+    fn decode(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        if !src.is_empty() {
+            self.utf8
+                .decode_queue_into(core::mem::take(src), &mut self.buffer)?;
+        }
+        self.next_line()
+    }
+
+    #[inline]
+    fn decode_eof(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(line) = self.decode(src)? {
+            return Ok(Some(line));
+        }
+        self.buffer.push_back(self.utf8.finish()?);
+        self.searched = 0;
+        if core::mem::take(&mut self.discarding) || self.buffer.is_empty() {
+            self.buffer = StringQueue::new();
+            return Ok(None);
+        }
+        let line = core::mem::take(&mut self.buffer);
+        if line.len() > self.max_length {
+            return Err(FrameError::TooLong {
+                length: line.len(),
+                max: self.max_length,
+            });
+        }
+        Ok(Some(line))
+    }
+}
+
+impl<'a> Encoder<'a, StringData<'a>> for LinesCodec<'_> {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(&mut self, item: StringData<'a>, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error> {
+        dst.push_back(item);
+        dst.push_back(b"\n".as_slice());
+        Ok(())
+    }
+}
+
+impl<'a> Encoder<'a, StringQueue<'a>> for LinesCodec<'_> {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(
+        &mut self,
+        item: StringQueue<'a>,
+        dst: &mut ByteQueue<'a>,
+    ) -> Result<(), Self::Error> {
+        dst.append(item.into_bytequeue());
+        dst.push_back(b"\n".as_slice());
+        Ok(())
+    }
+}
+
+impl<'a> Encoder<'a, &'a str> for LinesCodec<'_> {
+    type Error = FrameError;
+
+    #[inline]
+    fn encode(&mut self, item: &'a str, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error> {
+        dst.push_back(item.as_bytes());
+        dst.push_back(b"\n".as_slice());
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+//! # Framing
+//!
+//! This module splits a stream of bytes held in a [`ByteQueue`] into frames, and writes frames back into a queue.
+//!
+//! Decoders take complete frames from the front of a queue with [`ByteQueue::split_off`], so frames share the buffers the data was read into instead of copying it.
+//! Encoders push the frames themselves into a queue, along with any headers or delimiters.
+//!
+//! Ready-made codecs are available for length-delimited frames ([`LengthDelimitedCodec`]), lines ([`LinesCodec`]),
+//! and frames separated by an arbitrary delimiter ([`DelimiterCodec`]).
+//! With the `tokio-util_07` feature, [`TokioCodec`] makes any of them usable with `tokio_util::codec::Framed`.
+//!
+//! ```
+//! use ::bytedata::framing::{Decoder, Encoder, LengthDelimitedCodec};
+//! use ::bytedata::{ByteData, ByteQueue};
+//!
+//! let mut codec = LengthDelimitedCodec::new();
+//! let mut queue = ByteQueue::new();
+//! codec.encode(ByteData::from_static(b"hello"), &mut queue).unwrap();
+//! codec.encode(ByteData::from_static(b"world"), &mut queue).unwrap();
+//! assert_eq!(queue.len(), 18);
+//!
+//! let frame = codec.decode(&mut queue).unwrap().unwrap();
+//! assert_eq!(frame, b"hello".as_slice());
+//! let frame = codec.decode(&mut queue).unwrap().unwrap();
+//! assert_eq!(frame, b"world".as_slice());
+//! assert!(codec.decode(&mut queue).unwrap().is_none());
+//! ```
+
+use crate::ByteQueue;
+
+mod delimiter;
+mod length_delimited;
+mod lines;
+
+pub use delimiter::DelimiterCodec;
+pub use length_delimited::LengthDelimitedCodec;
+pub use lines::LinesCodec;
+
+#[cfg(feature = "tokio-util_07")]
+mod tokio_util_07;
+#[cfg(feature = "tokio-util_07")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util_07")))]
+pub use tokio_util_07::TokioCodec;
+
+/// Decodes frames from the front of a [`ByteQueue`].
+pub trait Decoder<'a> {
+    /// The type of the decoded frames.
+    type Item;
+    /// The type of the errors returned when decoding fails.
+    type Error;
+
+    /// Attempts to decode a frame from the front of `src`.
+    ///
+    /// Returns `Ok(None)` if `src` does not hold a complete frame yet, in which case it should be called again after more data has been pushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data in `src` is not a valid frame.
+    fn decode(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// Attempts to decode a frame after the end of the input has been reached.
+    ///
+    /// The default implementation calls [`Decoder::decode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data in `src` is not a valid frame, or if it ends in the middle of a frame.
+    #[inline]
+    fn decode_eof(&mut self, src: &mut ByteQueue<'a>) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode(src)
+    }
+}
+
+/// Encodes frames of type `Item` into a [`ByteQueue`].
+pub trait Encoder<'a, Item> {
+    /// The type of the errors returned when encoding fails.
+    type Error;
+
+    /// Encodes `item` and pushes it to the back of `dst`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `item` cannot be encoded.
+    fn encode(&mut self, item: Item, dst: &mut ByteQueue<'a>) -> Result<(), Self::Error>;
+}
+
+/// An error returned by the codecs in this module.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FrameError {
+    /// A frame is longer than the maximum length of the codec.
+    TooLong {
+        /// The length of the frame, or of the data seen so far if the end of the frame has not been found.
+        length: usize,
+        /// The maximum length of a frame.
+        max: usize,
+    },
+    /// A line is not valid UTF-8.
+    InvalidUtf8(crate::Utf8CharError),
+    /// The input ended in the middle of a frame.
+    Incomplete {
+        /// The number of bytes left in the input.
+        remaining: usize,
+    },
+    /// An I/O error occurred while reading or writing frames.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    Io(std::io::Error),
+}
+
+impl core::fmt::Display for FrameError {
+    #[inline]
+    #[allow(clippy::pattern_type_mismatch)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLong { length, max } => {
+                write!(f, "frame of length {length} exceeds the maximum of {max}")
+            }
+            Self::InvalidUtf8(err) => write!(f, "invalid line: {err}"),
+            Self::Incomplete { remaining } => {
+                write!(
+                    f,
+                    "input ended with {remaining} bytes of an incomplete frame"
+                )
+            }
+            #[cfg(feature = "std")]
+            Self::Io(err) => core::fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for FrameError {
+    #[inline]
+    #[allow(clippy::pattern_type_mismatch)]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidUtf8(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::TooLong { .. } | Self::Incomplete { .. } => None,
+        }
+    }
+}
+
+impl From<crate::Utf8CharError> for FrameError {
+    #[inline]
+    fn from(err: crate::Utf8CharError) -> Self {
+        Self::InvalidUtf8(err)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl From<std::io::Error> for FrameError {
+    #[inline]
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Takes the first `len` bytes of `src` as a frame.
+fn take_frame<'a>(src: &mut ByteQueue<'a>, len: usize) -> ByteQueue<'a> {
+    let rest = src.split_off(len);
+    core::mem::replace(src, rest)
+}
@@ -0,0 +1,115 @@
+use ::bytes_1::BytesMut;
+
+use crate::{ByteData, ByteQueue};
+
+use super::{Decoder, Encoder};
+
+/// An adapter that implements the `Decoder` and `Encoder` traits of `tokio_util::codec` for a codec from this module.
+///
+/// Data read into the `BytesMut` buffer of `tokio_util` is moved into a [`ByteQueue`] without copying, so decoded frames still share the read buffers.
+/// Encoded frames are copied into the write buffer.
+///
+/// At the end of the input the adapter returns an error if the codec leaves data in the queue without returning a frame.
+#[derive(Debug, Clone, Default)]
+pub struct TokioCodec<C> {
+    codec: C,
+    buffer: ByteQueue<'static>,
+}
+
+impl<C> TokioCodec<C> {
+    /// Wraps a codec.
+    #[inline]
+    #[must_use]
+    pub const fn new(codec: C) -> Self {
+        Self {
+            codec,
+            buffer: ByteQueue::new(),
+        }
+    }
+
+    /// Returns a reference to the wrapped codec.
+    #[inline]
+    #[must_use]
+    pub const fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the wrapped codec.
+    #[inline]
+    #[must_use]
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Returns the data that has been read but not decoded yet.
+    #[inline]
+    #[must_use]
+    pub const fn buffered(&self) -> &ByteQueue<'static> {
+        &self.buffer
+    }
+
+    /// Returns the wrapped codec and the data that has been read but not decoded yet.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (C, ByteQueue<'static>) {
+        (self.codec, self.buffer)
+    }
+
+    /// Moves the data read by `tokio_util` into the queue.
+    fn fill(&mut self, src: &mut BytesMut) {
+        if !src.is_empty() {
+            self.buffer.push_back(ByteData::from(src.split().freeze()));
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util_07")))]
+impl<C> ::tokio_util_07::codec::Decoder for TokioCodec<C>
+where
+    C: Decoder<'static>,
+    C::Error: From<std::io::Error>,
+{
+    type Item = C::Item;
+    type Error = C::Error;
+
+    #[inline]
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.fill(src);
+        self.codec.decode(&mut self.buffer)
+    }
+
+    #[inline]
+    #[allow(clippy::std_instead_of_core)]
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.fill(buf);
+        match self.codec.decode_eof(&mut self.buffer)? {
+            Some(frame) => Ok(Some(frame)),
+            None if self.buffer.is_empty() => Ok(None),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "bytes remaining on stream",
+            )
+            .into()),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-util_07")))]
+impl<C, I> ::tokio_util_07::codec::Encoder<I> for TokioCodec<C>
+where
+    C: Encoder<'static, I>,
+    C::Error: From<std::io::Error>,
+{
+    type Error = C::Error;
+
+    #[inline]
+    fn encode(&mut self, item: I, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut queue = ByteQueue::new();
+        self.codec.encode(item, &mut queue)?;
+        dst.reserve(queue.len());
+        for chunk in queue.chunks() {
+            dst.extend_from_slice(chunk.as_slice());
+        }
+        Ok(())
+    }
+}
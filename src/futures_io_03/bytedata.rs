@@ -0,0 +1,42 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::futures_io_03::{AsyncBufRead, AsyncRead};
+
+use crate::ByteData;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io_03")))]
+impl AsyncRead for ByteData<'_> {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Read::read(self.get_mut(), buf))
+    }
+
+    #[inline]
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        bufs: &mut [std::io::IoSliceMut<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Read::read_vectored(self.get_mut(), bufs))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io_03")))]
+impl AsyncBufRead for ByteData<'_> {
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().as_slice()))
+    }
+
+    #[inline]
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().make_sliced(amt..);
+    }
+}
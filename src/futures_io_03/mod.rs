@@ -0,0 +1,115 @@
+//! Support for the asynchronous I/O traits of `futures-io@0.3`.
+//!
+//! `AsyncRead` and `AsyncBufRead` are implemented for [`ByteData`](crate::ByteData), [`SharedBytes`](crate::SharedBytes) and [`ByteQueue`](crate::ByteQueue),
+//! and `AsyncWrite` is implemented for [`SharedBytesBuilder`](crate::SharedBytesBuilder) and [`ByteQueue`](crate::ByteQueue).
+//! These are always ready, as all data is already in memory.
+//!
+//! The `AsyncRead` trait of `futures-io` only reads into initialized memory,
+//! so the helpers in this module read from an `AsyncBufRead` and copy its buffered data into shared buffers without zero-initialising them first.
+//! Any `AsyncRead` can be wrapped in a `BufReader` from the `futures` crate to be used with them.
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::futures_io_03::AsyncBufRead;
+
+use crate::{ByteData, SharedBytesBuilder};
+
+mod bytedata;
+#[cfg(feature = "queue")]
+mod queue;
+mod shared_bytes;
+mod shared_bytes_builder;
+
+#[cfg(test)]
+mod tests;
+
+/// Copies the buffered data of `reader` into the unfilled capacity of `builder`.
+fn poll_read_builder<R: AsyncBufRead + ?Sized>(
+    mut reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    builder: &mut SharedBytesBuilder,
+) -> Poll<std::io::Result<usize>> {
+    let available = match reader.as_mut().poll_fill_buf(cx) {
+        Poll::Ready(Ok(available)) => available,
+        Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+        Poll::Pending => return Poll::Pending,
+    };
+    let len = builder.apply_unfilled(|unfilled| {
+        let len = unfilled.len().min(available.len());
+        // SAFETY: both buffers are at least `len` bytes long and `unfilled` is owned by the builder.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                available.as_ptr(),
+                unfilled.as_mut_ptr().cast::<u8>(),
+                len,
+            );
+        };
+        (len, len)
+    });
+    reader.consume(len);
+    Poll::Ready(Ok(len))
+}
+
+#[cfg(feature = "queue")]
+/// Reads from `reader` until `builder` is full or the end of the input is reached.
+/// Returns `true` if the end of the input was reached.
+async fn fill_builder<R: AsyncBufRead + Unpin + ?Sized>(
+    reader: &mut R,
+    builder: &mut SharedBytesBuilder,
+) -> std::io::Result<bool> {
+    while builder.len() < builder.capacity() {
+        let len =
+            core::future::poll_fn(|cx| poll_read_builder(Pin::new(&mut *reader), cx, builder))
+                .await?;
+        if len == 0 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Performs a single read of at most `chunk_size` bytes from `reader` into a new shared buffer.
+///
+/// Returns empty data at the end of the input. Reads of at most [`ByteChunk::LEN`](crate::ByteChunk::LEN) bytes are returned as a chunk.
+/// A `chunk_size` of `0` is treated as `1`.
+///
+/// # Errors
+///
+/// Returns any error returned by the reader.
+#[inline]
+pub async fn read_chunk<R: AsyncBufRead + Unpin + ?Sized>(
+    reader: &mut R,
+    chunk_size: usize,
+) -> std::io::Result<ByteData<'static>> {
+    let mut builder = SharedBytesBuilder::with_capacity(chunk_size.max(1));
+    core::future::poll_fn(|cx| poll_read_builder(Pin::new(&mut *reader), cx, &mut builder)).await?;
+    Ok(builder.into())
+}
+
+/// Reads all of `reader` into a queue of shared buffers that each hold up to `chunk_size` bytes.
+///
+/// A `chunk_size` of `0` is treated as `1`.
+///
+/// # Errors
+///
+/// Returns any error returned by the reader. The data read before the error is dropped.
+#[cfg(feature = "queue")]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[inline]
+pub async fn read_into_queue<R: AsyncBufRead + Unpin + ?Sized>(
+    reader: &mut R,
+    chunk_size: usize,
+) -> std::io::Result<crate::ByteQueue<'static>> {
+    let mut queue = crate::ByteQueue::new();
+    loop {
+        let mut builder = SharedBytesBuilder::with_capacity(chunk_size.max(1));
+        let done = fill_builder(reader, &mut builder).await?;
+        queue.push_back(ByteData::from(builder));
+        if done {
+            return Ok(queue);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::futures_io_03::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+use crate::ByteQueue;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io_03")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+impl AsyncRead for ByteQueue<'_> {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Read::read(self.get_mut(), buf))
+    }
+
+    #[inline]
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        bufs: &mut [std::io::IoSliceMut<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Read::read_vectored(self.get_mut(), bufs))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io_03")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+impl AsyncBufRead for ByteQueue<'_> {
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        Poll::Ready(Ok(self
+            .get_mut()
+            .front()
+            .map_or(&[], |chunk| chunk.as_slice())))
+    }
+
+    #[inline]
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        ByteQueue::consume(self.get_mut(), amt);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io_03")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+impl AsyncWrite for ByteQueue<'_> {
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Write::write(self.get_mut(), buf))
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(std::io::Write::write_vectored(self.get_mut(), bufs))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,132 @@
+use core::pin::Pin;
+
+use ::futures_io_03::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+use crate::test::block_on;
+use crate::{ByteData, SharedBytes, SharedBytesBuilder};
+
+/// Reads once from `reader` into a buffer of `len` bytes.
+#[allow(clippy::unwrap_used)]
+fn read_once<R: AsyncRead + Unpin>(reader: &mut R, len: usize) -> alloc::vec::Vec<u8> {
+    let mut buf = alloc::vec![0_u8; len];
+    let filled = block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut *reader).poll_read(cx, &mut buf)
+    }))
+    .unwrap();
+    buf.truncate(filled);
+    buf
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_bytedata_read() {
+    let mut data = ByteData::from_static(b"hello async world");
+    assert_eq!(read_once(&mut data, 6), b"hello ");
+    assert_eq!(read_once(&mut data, 64), b"async world");
+    assert!(data.is_empty());
+    assert_eq!(read_once(&mut data, 64), b"");
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_shared_bytes_read() {
+    let mut data = SharedBytes::from_slice(b"hello async world");
+    let buf = block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut data).poll_fill_buf(cx).map_ok(<[u8]>::to_vec)
+    }))
+    .unwrap();
+    assert_eq!(buf, b"hello async world");
+    Pin::new(&mut data).consume(6);
+    assert_eq!(read_once(&mut data, 5), b"async");
+    assert_eq!(data.as_slice(), b" world");
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_queue_read() {
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(b"hello ".as_slice());
+    queue.push_back(b"async ".as_slice());
+    queue.push_back(b"world".as_slice());
+    assert_eq!(read_once(&mut queue, 8), b"hello as");
+    let buf = block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut queue)
+            .poll_fill_buf(cx)
+            .map_ok(<[u8]>::to_vec)
+    }))
+    .unwrap();
+    assert_eq!(buf, b"ync ");
+    Pin::new(&mut queue).consume(4);
+    assert_eq!(read_once(&mut queue, 64), b"world");
+    assert!(queue.is_empty());
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_builder_write() {
+    let mut builder = SharedBytesBuilder::new();
+    let written = block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut builder).poll_write(cx, b"hello ")
+    }))
+    .unwrap();
+    assert_eq!(written, 6);
+    let bufs = [
+        std::io::IoSlice::new(b"async "),
+        std::io::IoSlice::new(b"world"),
+    ];
+    let written = block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut builder).poll_write_vectored(cx, &bufs)
+    }))
+    .unwrap();
+    assert_eq!(written, 11);
+    assert_eq!(builder.as_slice(), b"hello async world");
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_queue_write() {
+    let mut queue = crate::ByteQueue::new();
+    block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut queue).poll_write(cx, b"hello async world")
+    }))
+    .unwrap();
+    block_on(core::future::poll_fn(|cx| {
+        Pin::new(&mut queue).poll_close(cx)
+    }))
+    .unwrap();
+    assert_eq!(queue.len(), 17);
+    assert_eq!(queue, b"hello async world".as_slice());
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_read_chunk() {
+    let mut reader = ByteData::from_static(b"hello async world");
+    let chunk = block_on(super::read_chunk(&mut reader, 6)).unwrap();
+    assert_eq!(chunk.as_slice(), b"hello ");
+    let rest = block_on(super::read_chunk(&mut reader, 0)).unwrap();
+    assert_eq!(rest.as_slice(), b"a");
+    let rest = block_on(super::read_chunk(&mut reader, 64)).unwrap();
+    assert_eq!(rest.as_slice(), b"sync world");
+    let rest = block_on(super::read_chunk(&mut reader, 64)).unwrap();
+    assert!(rest.is_empty());
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_read_into_queue() {
+    let mut source = crate::ByteQueue::new();
+    source.push_back(b"the quick brown fox ".as_slice());
+    source.push_back(b"jumps over the lazy dog".as_slice());
+    let queue = block_on(super::read_into_queue(&mut source, 16)).unwrap();
+    assert_eq!(queue.len(), 43);
+    assert_eq!(queue.chunk_len(), 3);
+    assert_eq!(
+        queue,
+        b"the quick brown fox jumps over the lazy dog".as_slice()
+    );
+    assert!(source.is_empty());
+}
@@ -0,0 +1,280 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{ByteData, SharedBytes};
+
+/// The state shared between clones of an [`ArcInterning`].
+struct Inner {
+    map: dashmap::DashMap<SharedBytes, ()>,
+    inserts: AtomicUsize,
+}
+
+/// A reference-counted interning structure that releases values once they are no longer used.
+///
+/// Interned values are kept in their own shared buffers. An entry is evicted by [`ArcInterning::sweep`]
+/// once the interning holds the only reference to its buffer, which happens when every [`ByteData`]
+/// returned for it has been dropped. A sweep also runs automatically after a configurable number of new entries.
+///
+/// Values that fit in a [`ByteChunk`](crate::ByteChunk) are returned as chunks and never stored.
+///
+/// ```
+/// use ::bytedata::{interning::{ArcInterning, ByteInterning}, ByteData};
+///
+/// let interning = ArcInterning::new(64);
+/// let tenant = interning.intern(ByteData::from_borrowed(b"tenant-0123456789abcdef")).unwrap();
+/// assert_eq!(interning.len(), 1);
+/// interning.sweep();
+/// assert_eq!(interning.len(), 1);
+/// drop(tenant);
+/// interning.sweep();
+/// assert!(interning.is_empty());
+/// ```
+#[derive(Clone)]
+pub struct ArcInterning {
+    max_len: usize,
+    sweep_interval: usize,
+    inner: Arc<Inner>,
+}
+
+impl ArcInterning {
+    /// The default number of new entries after which a sweep runs.
+    pub const DEFAULT_SWEEP_INTERVAL: usize = 1024;
+
+    /// Creates a new interning instance that interns values up to `max_len` bytes.
+    #[inline]
+    #[must_use]
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            max_len,
+            sweep_interval: Self::DEFAULT_SWEEP_INTERVAL,
+            inner: Arc::new(Inner {
+                map: dashmap::DashMap::new(),
+                inserts: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Sets the number of new entries after which a sweep runs automatically, `0` disables automatic sweeps.
+    ///
+    /// The interval is not shared with existing clones of this instance.
+    #[inline]
+    #[must_use]
+    pub const fn with_sweep_interval(mut self, inserts: usize) -> Self {
+        self.sweep_interval = inserts;
+        self
+    }
+
+    /// Removes all entries that are not referenced outside of the interning.
+    ///
+    /// Returns the number of removed entries.
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    pub fn sweep(&self) -> usize {
+        let before = self.inner.map.len();
+        // The entries are only handed out while holding a lock on their shard, so a unique entry
+        // cannot gain a new reference while `retain` holds the write lock.
+        self.inner.map.retain(|key, &mut ()| !key.is_unique());
+        before.saturating_sub(self.inner.map.len())
+    }
+
+    /// Returns the number of entries currently held.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.map.len()
+    }
+
+    /// Returns `true` if no entries are held.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.map.is_empty()
+    }
+
+    /// Clears the interning set. Values that were handed out stay valid.
+    #[inline]
+    pub fn clear(&self) {
+        self.inner.map.clear();
+    }
+
+    /// Counts a new entry and sweeps if the interval is reached.
+    fn inserted(&self) {
+        if self.sweep_interval == 0 {
+            return;
+        }
+        let inserts = self.inner.inserts.fetch_add(1, Ordering::Relaxed) + 1;
+        if inserts % self.sweep_interval == 0 {
+            self.sweep();
+        }
+    }
+}
+
+impl Default for ArcInterning {
+    #[inline]
+    fn default() -> Self {
+        Self::new(128)
+    }
+}
+
+impl core::fmt::Debug for ArcInterning {
+    #[allow(clippy::missing_inline_in_public_items, clippy::min_ident_chars)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArcInterning")
+            .field("max_len", &self.max_len)
+            .field("sweep_interval", &self.sweep_interval)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl super::ByteInterning<'static> for ArcInterning {
+    #[inline]
+    fn intern<'b>(&self, value: ByteData<'b>) -> Result<ByteData<'static>, ByteData<'b>> {
+        let len = value.len();
+        if len <= crate::ByteChunk::LEN {
+            return Ok(value.into_shared());
+        }
+        if len > self.max_len {
+            return Err(value);
+        }
+        Ok(self.intern_always(value))
+    }
+
+    #[allow(
+        single_use_lifetimes,
+        clippy::needless_lifetimes,
+        clippy::elidable_lifetime_names
+    )]
+    #[inline]
+    fn intern_always<'b>(&self, value: ByteData<'b>) -> ByteData<'static> {
+        if value.len() <= crate::ByteChunk::LEN {
+            return value.into_shared();
+        }
+        if let Some(entry) = self.inner.map.get(value.as_slice()) {
+            return entry.key().clone().into();
+        }
+        // The value gets its own buffer, so the entry does not keep a larger buffer alive
+        // and its reference count only tracks the interned value.
+        let (ret, inserted) = match self.inner.map.entry(SharedBytes::from_slice(&value)) {
+            dashmap::Entry::Occupied(entry) => (entry.key().clone(), false),
+            dashmap::Entry::Vacant(entry) => (entry.insert(()).key().clone(), true),
+        };
+        if inserted {
+            self.inserted();
+        }
+        ret.into()
+    }
+
+    #[inline]
+    fn get<'b>(&self, value: ByteData<'b>) -> Result<ByteData<'static>, ByteData<'b>> {
+        if value.len() <= crate::ByteChunk::LEN {
+            return Ok(value.into_shared());
+        }
+        self.inner
+            .map
+            .get(value.as_slice())
+            .map_or_else(|| Err(value), |entry| Ok(entry.key().clone().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::super::ByteInterning;
+    use super::*;
+
+    #[test]
+    fn test_arc_interning_shared() {
+        let interning = ArcInterning::new(32);
+        let value = interning
+            .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .unwrap();
+        let value2 = interning
+            .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .unwrap();
+        assert!(
+            core::ptr::addr_eq(value.as_slice().as_ptr(), value2.as_slice().as_ptr()),
+            "The interned value should return the same copy."
+        );
+        assert!(interning
+            .intern(ByteData::from_borrowed(
+                b"This value is longer than the maximum length."
+            ))
+            .ok()
+            .is_none());
+        assert_eq!(interning.len(), 1);
+    }
+
+    #[test]
+    fn test_arc_interning_sweep() {
+        let interning = ArcInterning::new(32).with_sweep_interval(0);
+        let value = interning
+            .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .unwrap();
+        let sliced = value.sliced(6..);
+        drop(value);
+        assert_eq!(interning.sweep(), 0, "A slice should keep the entry alive.");
+        drop(sliced);
+        assert_eq!(interning.sweep(), 1);
+        assert!(interning
+            .get(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .ok()
+            .is_none());
+    }
+
+    #[test]
+    fn test_arc_interning_sweep_interval() {
+        let interning = ArcInterning::new(32).with_sweep_interval(4);
+        let kept = interning
+            .intern(ByteData::from_borrowed(b"kept value, 1234567890"))
+            .unwrap();
+        for i in 0..3_u8 {
+            let mut value = *b"dropped value, 1234567890";
+            value[0] = i;
+            drop(interning.intern(ByteData::from_borrowed(&value)).unwrap());
+        }
+        // the value inserted last is still referenced while the sweep runs
+        assert_eq!(interning.len(), 2, "The fourth insert should sweep.");
+        assert_eq!(interning.get(kept.clone()).unwrap(), kept);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_arc_interning_concurrent() {
+        const THREADS: u8 = 4;
+        let interning = ArcInterning::new(64).with_sweep_interval(8);
+        std::thread::scope(|scope| {
+            for thread in 0..THREADS {
+                let interning = &interning;
+                scope.spawn(move || {
+                    let mut held = alloc::vec::Vec::new();
+                    for round in 0..500_u32 {
+                        let key = alloc::format!("tenant-{:016}", round % 24);
+                        let value = interning
+                            .intern(ByteData::from_borrowed(key.as_bytes()))
+                            .unwrap();
+                        assert_eq!(value.as_slice(), key.as_bytes());
+                        if round % u32::from(thread + 2) == 0 {
+                            held.push(value);
+                        }
+                        if held.len() > 8 {
+                            held.remove(0);
+                        }
+                        if round % 97 == 0 {
+                            interning.sweep();
+                        }
+                    }
+                    for value in &held {
+                        let interned = interning.get(value.clone()).unwrap();
+                        assert!(core::ptr::addr_eq(
+                            interned.as_slice().as_ptr(),
+                            value.as_slice().as_ptr()
+                        ));
+                    }
+                });
+            }
+        });
+        interning.sweep();
+        assert!(interning.is_empty());
+    }
+}
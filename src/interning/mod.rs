@@ -3,12 +3,20 @@
 //! This module contains types to handle interning of (byte)strings.
 //!
 //! The interning is done by storing the strings in a hash map, which allows for efficient storage and retrieval of strings.
+//! [`StaticInterning`] keeps every value until it is cleared, while [`ArcInterning`] releases values once they are no longer used.
+//! [`SymbolTable`] hands out small [`Symbol`] handles that resolve back to the interned values.
 
 mod byte_interning;
 pub use byte_interning::ByteInterning;
 
 mod r#static;
-pub use r#static::StaticInterning;
+pub use r#static::{StaticInterning, StaticInterningBuilder};
+
+mod arc;
+pub use arc::ArcInterning;
+
+mod symbol;
+pub use symbol::{Symbol, SymbolTable};
 
 //mod releasing;
 //pub use releasing::ReleasingInterning;
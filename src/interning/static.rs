@@ -1,59 +1,384 @@
 use alloc::sync::Arc;
-use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 
 use crate::ByteData;
 
+/// The rules for which values a [`StaticInterning`] accepts.
+#[derive(Clone, Copy)]
+struct Policy {
+    min_len: usize,
+    max_len: usize,
+    max_entries: usize,
+    max_total_bytes: usize,
+    filter: Option<fn(&[u8]) -> bool>,
+}
+
+impl Policy {
+    /// Returns `true` if the value may be interned.
+    fn allows(&self, value: &[u8]) -> bool {
+        (self.min_len..=self.max_len).contains(&value.len())
+            && self.filter.map_or(true, |filter| filter(value))
+    }
+}
+
+/// The interned values and statistics, shared between clones of a [`StaticInterning`].
+struct Inner {
+    map: dashmap::DashMap<ByteData<'static>, ()>,
+    entries: AtomicUsize,
+    total_bytes: AtomicUsize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl Inner {
+    fn new() -> Self {
+        Self {
+            map: dashmap::DashMap::new(),
+            entries: AtomicUsize::new(0),
+            total_bytes: AtomicUsize::new(0),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Reserves room for a new entry of `len` bytes, returns `false` if a limit would be exceeded.
+    fn reserve(&self, policy: &Policy, len: usize) -> bool {
+        if self
+            .entries
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |entries| {
+                (entries < policy.max_entries).then_some(entries + 1)
+            })
+            .is_err()
+        {
+            return false;
+        }
+        if self
+            .total_bytes
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |total| {
+                total
+                    .checked_add(len)
+                    .filter(|&total| total <= policy.max_total_bytes)
+            })
+            .is_err()
+        {
+            self.entries.fetch_sub(1, Ordering::AcqRel);
+            return false;
+        }
+        true
+    }
+
+    /// Inserts a value, or returns the existing entry. Limits are only checked if `limited` is set.
+    fn insert<'b>(
+        &self,
+        policy: &Policy,
+        value: ByteData<'b>,
+        limited: bool,
+    ) -> Result<ByteData<'static>, ByteData<'b>> {
+        let len = value.len();
+        if limited && !self.reserve(policy, len) {
+            return Err(value);
+        }
+        match self.map.entry(value.into_shared()) {
+            dashmap::Entry::Occupied(entry) => {
+                if limited {
+                    self.entries.fetch_sub(1, Ordering::AcqRel);
+                    self.total_bytes.fetch_sub(len, Ordering::AcqRel);
+                }
+                Ok(entry.key().clone())
+            }
+            dashmap::Entry::Vacant(entry) => {
+                if !limited {
+                    self.entries.fetch_add(1, Ordering::AcqRel);
+                    self.total_bytes.fetch_add(len, Ordering::AcqRel);
+                }
+                Ok(entry.insert(()).key().clone())
+            }
+        }
+    }
+}
+
 /// A static interning structure that never releases the interned values.
+///
+/// Use [`StaticInterning::builder`] to limit which values are interned and how many.
+/// The interned set can be written to a snapshot with [`StaticInterning::snapshot`],
+/// and loaded again at startup with [`StaticInterning::load_snapshot`].
 pub struct StaticInterning {
-    max_len: usize,
-    map: AtomicPtr<dashmap::DashMap<ByteData<'static>, ()>>,
+    policy: Policy,
+    map: AtomicPtr<Inner>,
+}
+
+/// A builder for a [`StaticInterning`] with limits on the values it accepts.
+///
+/// Values that are rejected by the limits are returned as they are by [`ByteInterning::intern`](super::ByteInterning::intern).
+/// [`ByteInterning::intern_always`](super::ByteInterning::intern_always) ignores all limits.
+///
+/// ```
+/// use ::bytedata::{interning::{ByteInterning, StaticInterning}, ByteData};
+///
+/// static INTERNING: StaticInterning = StaticInterning::builder()
+///     .min_len(4)
+///     .max_len(64)
+///     .max_entries(10_000)
+///     .filter(|value| value.is_ascii())
+///     .build();
+///
+/// assert!(INTERNING.intern(ByteData::from_borrowed(b"abc")).is_err());
+/// assert!(INTERNING.intern(ByteData::from_borrowed("grüße, grüße".as_bytes())).is_err());
+/// let value = INTERNING.intern(ByteData::from_borrowed(b"tenant-0123456789")).unwrap();
+/// assert_eq!(INTERNING.len(), 1);
+/// ```
+#[derive(Clone, Copy)]
+#[must_use]
+pub struct StaticInterningBuilder {
+    policy: Policy,
+}
+
+impl StaticInterningBuilder {
+    /// Sets the minimum length of interned values, which defaults to `0`.
+    #[inline]
+    pub const fn min_len(mut self, min_len: usize) -> Self {
+        self.policy.min_len = min_len;
+        self
+    }
+
+    /// Sets the maximum length of interned values, which defaults to `128`.
+    #[inline]
+    pub const fn max_len(mut self, max_len: usize) -> Self {
+        self.policy.max_len = max_len;
+        self
+    }
+
+    /// Sets the maximum number of stored values. Values that fit in a [`ByteChunk`](crate::ByteChunk) are not stored.
+    #[inline]
+    pub const fn max_entries(mut self, max_entries: usize) -> Self {
+        self.policy.max_entries = max_entries;
+        self
+    }
+
+    /// Sets the maximum number of bytes of all stored values together.
+    #[inline]
+    pub const fn max_total_bytes(mut self, max_total_bytes: usize) -> Self {
+        self.policy.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// Sets a predicate that a value has to match to be interned.
+    #[inline]
+    pub const fn filter(mut self, filter: fn(&[u8]) -> bool) -> Self {
+        self.policy.filter = Some(filter);
+        self
+    }
+
+    /// Builds the interning. The interning set is allocated when the first value is stored.
+    #[inline]
+    #[must_use]
+    pub const fn build(self) -> StaticInterning {
+        StaticInterning {
+            policy: self.policy,
+            map: AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+}
+
+impl core::fmt::Debug for StaticInterningBuilder {
+    #[allow(clippy::missing_inline_in_public_items, clippy::min_ident_chars)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticInterningBuilder")
+            .field("min_len", &self.policy.min_len)
+            .field("max_len", &self.policy.max_len)
+            .field("max_entries", &self.policy.max_entries)
+            .field("max_total_bytes", &self.policy.max_total_bytes)
+            .field("filter", &self.policy.filter.is_some())
+            .finish()
+    }
 }
 
 impl StaticInterning {
+    /// Returns a builder to configure the values that are interned.
+    #[inline]
+    pub const fn builder() -> StaticInterningBuilder {
+        StaticInterningBuilder {
+            policy: Policy {
+                min_len: 0,
+                max_len: 128,
+                max_entries: usize::MAX,
+                max_total_bytes: usize::MAX,
+                filter: None,
+            },
+        }
+    }
+
     /// Creates a new uninitialized static interning instance.
     #[inline]
     #[must_use]
     pub const fn new_const(max_len: usize) -> Self {
-        Self {
-            max_len,
-            map: AtomicPtr::new(core::ptr::null_mut()),
-        }
+        Self::builder().max_len(max_len).build()
     }
 
     /// Creates a new static interning instance.
     #[inline]
     #[must_use]
     pub fn new(max_len: usize) -> Self {
-        Self {
-            max_len,
-            map: AtomicPtr::new(Arc::into_raw(Arc::new(
-                dashmap::DashMap::<ByteData<'static>, ()>::new(),
-            ))
-                as *mut dashmap::DashMap<ByteData<'static>, ()>),
-        }
+        let ret = Self::new_const(max_len);
+        ret.init();
+        ret
     }
 
     /// Clears the interning set.
     #[inline]
     pub fn clear(&self) {
-        let ptr = self.map.load(core::sync::atomic::Ordering::Acquire);
-        // SAFETY: The pointer is expected to be valid or `null`.
-        let Some(ptr) = (unsafe { ptr.as_ref() }) else {
+        let Some(inner) = self.inner() else {
             return;
         };
-        ptr.clear();
+        // only the removed values are subtracted, as concurrent inserts may hold reservations in the counters
+        let mut entries = 0;
+        let mut total_bytes = 0;
+        inner.map.retain(|key, &mut ()| {
+            entries += 1;
+            total_bytes += key.len();
+            false
+        });
+        inner.entries.fetch_sub(entries, Ordering::AcqRel);
+        inner.total_bytes.fetch_sub(total_bytes, Ordering::AcqRel);
+    }
+
+    /// Returns the number of stored values.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner()
+            .map_or(0, |inner| inner.entries.load(Ordering::Acquire))
+    }
+
+    /// Returns `true` if no values are stored.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes of all stored values together.
+    #[inline]
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.inner()
+            .map_or(0, |inner| inner.total_bytes.load(Ordering::Acquire))
+    }
+
+    /// Returns how many times [`intern`](super::ByteInterning::intern) or [`get`](super::ByteInterning::get)
+    /// found a value that was already stored.
+    #[inline]
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.inner()
+            .map_or(0, |inner| inner.hits.load(Ordering::Relaxed))
+    }
+
+    /// Returns how many times [`intern`](super::ByteInterning::intern) or [`get`](super::ByteInterning::get)
+    /// looked for a value that was not stored yet.
+    #[inline]
+    #[must_use]
+    pub fn misses(&self) -> usize {
+        self.inner()
+            .map_or(0, |inner| inner.misses.load(Ordering::Relaxed))
+    }
+
+    /// Returns an iterator over the stored values, in no particular order.
+    ///
+    /// The iterator locks parts of the interning set, so interning values while iterating may deadlock.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = ByteData<'static>> + '_ {
+        self.inner()
+            .into_iter()
+            .flat_map(|inner| inner.map.iter().map(|entry| entry.key().clone()))
+    }
+
+    /// Writes the stored values into a snapshot that can be loaded with [`StaticInterning::load_snapshot`].
+    ///
+    /// The snapshot is a protobuf message with every value in a `bytes` field with number `1`.
+    #[inline]
+    #[must_use]
+    pub fn snapshot(&self) -> ByteData<'static> {
+        use crate::wire::WireWrite;
+
+        let mut out = crate::SharedBytesBuilder::with_capacity(self.total_bytes() + self.len() * 3);
+        for value in self.iter() {
+            out.write_bytes_field(1, value);
+        }
+        out.into()
+    }
+
+    /// Creates an interning instance with the limits of `builder` holding the values of a snapshot.
+    ///
+    /// Use [`StaticInterning::builder`] with the limits the snapshot was written with,
+    /// values that are not accepted by the limits are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot is not a valid protobuf message.
+    #[inline]
+    pub fn from_snapshot<'s, T: Into<ByteData<'s>>>(
+        builder: StaticInterningBuilder,
+        snapshot: T,
+    ) -> Result<Self, crate::wire::WireError> {
+        let ret = builder.build();
+        ret.load_snapshot(snapshot)?;
+        Ok(ret)
+    }
+
+    /// Stores the values of a snapshot that are accepted by the limits of this instance.
+    ///
+    /// Returns the number of values that were stored or already present, which does not count the hits or misses.
+    /// Values that fit in a [`ByteChunk`](crate::ByteChunk) are skipped, as they are never looked up.
+    /// Every stored value is copied into its own allocation, so the snapshot buffer is not kept alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot is not a valid protobuf message.
+    /// Values before the error are stored.
+    #[inline]
+    pub fn load_snapshot<'s, T: Into<ByteData<'s>>>(
+        &self,
+        snapshot: T,
+    ) -> Result<usize, crate::wire::WireError> {
+        let inner = self.inner().unwrap_or_else(|| self.init());
+        let mut count = 0;
+        for field in crate::wire::Fields::new(snapshot) {
+            let (1, crate::wire::WireValue::LengthDelimited(value)) = field? else {
+                continue;
+            };
+            if value.len() <= crate::ByteChunk::LEN || !self.policy.allows(value.as_slice()) {
+                continue;
+            }
+            if inner.map.contains_key(value.as_slice()) {
+                count += 1;
+                continue;
+            }
+            let value = ByteData::from_shared(crate::SharedBytes::from_slice(value.as_slice()));
+            if inner.insert(&self.policy, value, true).is_ok() {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Returns the interning set, if it has been initialized.
+    #[inline]
+    fn inner(&self) -> Option<&Inner> {
+        let ptr = self.map.load(Ordering::Acquire);
+        // SAFETY: The pointer is expected to be valid or `null`.
+        unsafe { ptr.as_ref() }
     }
 
     /// Initializes the interning set.
     #[inline]
-    fn init(&self) -> &dashmap::DashMap<ByteData<'static>, ()> {
-        let ptr = Arc::into_raw(Arc::new(dashmap::DashMap::<ByteData<'static>, ()>::new()))
-            as *mut dashmap::DashMap<ByteData<'static>, ()>;
+    fn init(&self) -> &Inner {
+        let ptr = Arc::into_raw(Arc::new(Inner::new())).cast_mut();
         match self.map.compare_exchange(
             core::ptr::null_mut(),
             ptr,
-            core::sync::atomic::Ordering::AcqRel,
-            core::sync::atomic::Ordering::Acquire,
+            Ordering::AcqRel,
+            Ordering::Acquire,
         ) {
             // SAFETY: If the pointer is inserted, it is valid.
             Ok(_) => unsafe { &*ptr },
@@ -70,9 +395,7 @@ impl StaticInterning {
 impl Drop for StaticInterning {
     #[inline]
     fn drop(&mut self) {
-        let ptr = self
-            .map
-            .swap(core::ptr::null_mut(), core::sync::atomic::Ordering::AcqRel);
+        let ptr = self.map.swap(core::ptr::null_mut(), Ordering::AcqRel);
         if ptr.is_null() {
             return;
         }
@@ -92,17 +415,12 @@ impl Default for StaticInterning {
 impl Clone for StaticInterning {
     #[inline]
     fn clone(&self) -> Self {
-        let ptr = self.map.load(core::sync::atomic::Ordering::Acquire);
-        // SAFETY: The pointer is expected to be valid or `null`.
-        let ptr = unsafe { ptr.as_ref() }.unwrap_or_else(|| self.init());
+        let ptr = self.inner().unwrap_or_else(|| self.init());
         // SAFETY: The pointer is valid and points to an `Arc`.
         unsafe { Arc::increment_strong_count(ptr) };
         Self {
-            max_len: self.max_len,
-            map: AtomicPtr::new(
-                ptr as *const dashmap::DashMap<ByteData<'static>, ()>
-                    as *mut dashmap::DashMap<ByteData<'static>, ()>,
-            ),
+            policy: self.policy,
+            map: AtomicPtr::new(core::ptr::NonNull::from(ptr).as_ptr()),
         }
     }
 }
@@ -110,28 +428,33 @@ impl Clone for StaticInterning {
 impl core::fmt::Debug for StaticInterning {
     #[allow(clippy::missing_inline_in_public_items, clippy::min_ident_chars)]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let map = self.map.load(core::sync::atomic::Ordering::Acquire);
-        let map =
-            // SAFETY: The pointer is expected to be valid or `null`.
-            unsafe { map.as_ref() }.map_or::<&dyn core::fmt::Debug, _>(&Option::<()>::None, |x| x);
+        let map = self
+            .inner()
+            .map_or::<&dyn core::fmt::Debug, _>(&Option::<()>::None, |inner| &inner.map);
         f.debug_struct("StaticInterning")
-            .field("max_len", &self.max_len)
+            .field("max_len", &self.policy.max_len)
+            .field("len", &self.len())
             .field("map", map)
-            .finish()
+            .finish_non_exhaustive()
     }
 }
 
 impl super::ByteInterning<'static> for StaticInterning {
     #[inline]
     fn intern<'b>(&self, value: ByteData<'b>) -> Result<ByteData<'static>, ByteData<'b>> {
-        let len = value.len();
-        if len <= crate::ByteChunk::LEN {
+        if !self.policy.allows(value.as_slice()) {
+            return Err(value);
+        }
+        if value.len() <= crate::ByteChunk::LEN {
             return Ok(value.into_shared());
         }
-        if len > self.max_len {
-            return Err(value);
+        let inner = self.inner().unwrap_or_else(|| self.init());
+        if let Some(entry) = inner.map.get(value.as_slice()) {
+            inner.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(entry.key().clone());
         }
-        Ok(self.intern_always(value))
+        inner.misses.fetch_add(1, Ordering::Relaxed);
+        inner.insert(&self.policy, value, true)
     }
 
     #[allow(
@@ -141,28 +464,26 @@ impl super::ByteInterning<'static> for StaticInterning {
     )]
     #[inline]
     fn intern_always<'b>(&self, value: ByteData<'b>) -> ByteData<'static> {
-        let ptr = self.map.load(core::sync::atomic::Ordering::Acquire);
-        // SAFETY: The pointer is expected to be valid or `null`.
-        let ptr = unsafe { ptr.as_ref() }.unwrap_or_else(|| self.init());
-        if let Some(value) = ptr.get(value.as_slice()) {
+        let inner = self.inner().unwrap_or_else(|| self.init());
+        if let Some(value) = inner.map.get(value.as_slice()) {
             return value.key().clone();
         }
-        let value = value.into_shared();
-        match ptr.entry(value) {
-            dashmap::Entry::Occupied(entry) => entry.key().clone(),
-            dashmap::Entry::Vacant(entry) => entry.insert(()).key().clone(),
+        match inner.insert(&self.policy, value, false) {
+            Ok(value) | Err(value) => value.into_shared(),
         }
     }
 
     #[inline]
     fn get<'b>(&self, value: ByteData<'b>) -> Result<ByteData<'static>, ByteData<'b>> {
-        let ptr = self.map.load(core::sync::atomic::Ordering::Acquire);
-        // SAFETY: The pointer is expected to be valid or `null`.
-        let Some(ptr) = (unsafe { ptr.as_ref() }) else {
+        let Some(inner) = self.inner() else {
             return Err(value);
         };
-        ptr.get(value.as_slice())
-            .map_or_else(|| Err(value), |entry| Ok(entry.key().clone()))
+        if let Some(entry) = inner.map.get(value.as_slice()) {
+            inner.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(entry.key().clone());
+        }
+        inner.misses.fetch_add(1, Ordering::Relaxed);
+        Err(value)
     }
 }
 
@@ -220,4 +541,176 @@ mod tests {
             "The interned value should return the same copy."
         );
     }
+
+    #[test]
+    fn test_static_interning_policy() {
+        let interning = StaticInterning::builder()
+            .min_len(16)
+            .max_len(32)
+            .max_entries(2)
+            .filter(|value| !value.starts_with(b"secret"))
+            .build();
+        assert!(interning
+            .intern(ByteData::from_borrowed(b"short"))
+            .ok()
+            .is_none());
+        assert!(interning
+            .intern(ByteData::from_borrowed(b"secret-token-0123456789"))
+            .ok()
+            .is_none());
+        assert!(interning
+            .intern(ByteData::from_borrowed(b"tenant-0123456789"))
+            .ok()
+            .is_some());
+        assert!(interning
+            .intern(ByteData::from_borrowed(b"tenant-abcdefghij"))
+            .ok()
+            .is_some());
+        assert!(
+            interning
+                .intern(ByteData::from_borrowed(b"tenant-klmnopqrst"))
+                .ok()
+                .is_none(),
+            "The entry limit should be reached."
+        );
+        assert_eq!(
+            interning.intern_always(ByteData::from_borrowed(b"tenant-klmnopqrst")),
+            b"tenant-klmnopqrst".as_slice()
+        );
+        assert_eq!(interning.len(), 3);
+        assert_eq!(interning.total_bytes(), 51);
+    }
+
+    #[test]
+    fn test_static_interning_total_bytes() {
+        let interning = StaticInterning::builder().max_total_bytes(40).build();
+        assert!(interning
+            .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .ok()
+            .is_some());
+        assert!(interning
+            .intern(ByteData::from_borrowed(b"Another value, 123456789"))
+            .ok()
+            .is_none());
+        assert!(
+            interning
+                .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+                .ok()
+                .is_some(),
+            "Stored values should still be found."
+        );
+        assert_eq!(interning.total_bytes(), 24);
+    }
+
+    #[test]
+    fn test_static_interning_stats() {
+        let interning = StaticInterning::new(32);
+        assert_eq!((interning.hits(), interning.misses()), (0, 0));
+        for _ in 0..3_u32 {
+            interning
+                .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+                .unwrap();
+        }
+        assert!(interning
+            .get(ByteData::from_borrowed(b"Not interned, 1234567890"))
+            .ok()
+            .is_none());
+        assert_eq!((interning.hits(), interning.misses()), (2, 2));
+        assert_eq!(interning.len(), 1);
+        interning.clear();
+        assert_eq!((interning.len(), interning.total_bytes()), (0, 0));
+    }
+
+    #[test]
+    fn test_static_interning_clear_during_insert() {
+        let interning = StaticInterning::new(64);
+        let value = ByteData::from_borrowed(b"a value that is already stored");
+        assert!(interning.intern(value.clone()).ok().is_some());
+        let inner = interning.inner().unwrap();
+        // an insert that has reserved its space but not reached the map yet
+        assert!(inner.reserve(&interning.policy, value.len()));
+        interning.clear();
+        assert_eq!(
+            (interning.len(), interning.total_bytes()),
+            (1, value.len()),
+            "The reservation of the pending insert should be kept."
+        );
+        // the pending insert stores its value under the reservation
+        assert!(inner.map.insert(value.clone().into_shared(), ()).is_none());
+        assert_eq!(interning.len(), interning.iter().count());
+        interning.clear();
+        assert_eq!((interning.len(), interning.total_bytes()), (0, 0));
+    }
+
+    #[test]
+    fn test_static_interning_snapshot() {
+        let interning = StaticInterning::new(64);
+        let mut values = alloc::vec::Vec::new();
+        for index in 0..20_u32 {
+            let value = alloc::format!("interned value number {index}");
+            values.push(interning.intern(value.as_bytes().into()).unwrap());
+        }
+        let mut iterated: alloc::vec::Vec<ByteData<'static>> = interning.iter().collect();
+        iterated.sort();
+        values.sort();
+        assert_eq!(iterated, values);
+
+        let snapshot = interning.snapshot();
+        let loaded = StaticInterning::from_snapshot(
+            StaticInterning::builder().max_len(64),
+            snapshot.clone(),
+        )
+        .unwrap();
+        assert_eq!(loaded.len(), 20);
+        assert_eq!(loaded.total_bytes(), interning.total_bytes());
+        assert!(loaded
+            .get(ByteData::from_borrowed(b"interned value number 7"))
+            .ok()
+            .is_some());
+
+        let limited = StaticInterning::builder().max_entries(5).build();
+        assert_eq!(limited.load_snapshot(snapshot).unwrap(), 5);
+        assert_eq!(limited.len(), 5);
+        assert!(StaticInterning::from_snapshot(
+            StaticInterning::builder(),
+            b"\x0a\x05abc".as_slice()
+        )
+        .ok()
+        .is_none());
+    }
+
+    #[test]
+    fn test_static_interning_snapshot_long_values() {
+        let interning = StaticInterning::builder().max_len(512).build();
+        let long = ByteData::from(alloc::vec![b'x'; 300]);
+        let value = interning.intern(long.clone()).unwrap();
+        assert_eq!(
+            interning.intern_always(b"short".as_slice().into()),
+            b"short".as_slice()
+        );
+        assert_eq!(interning.len(), 2);
+
+        let snapshot = interning.snapshot();
+        let loaded = StaticInterning::from_snapshot(
+            StaticInterning::builder().max_len(512),
+            snapshot.clone(),
+        )
+        .unwrap();
+        assert_eq!(loaded.len(), 1, "chunk sized values are not loaded");
+        let loaded_value = loaded.get(long.clone()).unwrap();
+        assert_eq!(loaded_value, value);
+        assert!(
+            !snapshot
+                .as_slice()
+                .as_ptr_range()
+                .contains(&loaded_value.as_slice().as_ptr()),
+            "The loaded value should not share the snapshot buffer."
+        );
+
+        let limited = StaticInterning::default();
+        assert_eq!(limited.load_snapshot(snapshot.clone()).unwrap(), 0);
+        assert!(limited.is_empty());
+        assert_eq!(loaded.load_snapshot(snapshot).unwrap(), 1);
+        assert_eq!(loaded.len(), 1);
+    }
 }
@@ -0,0 +1,512 @@
+use alloc::boxed::Box;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+use crate::{ByteData, StringData};
+
+/// A small handle for a value in a [`SymbolTable`].
+///
+/// Symbols are numbered in the order their values were added to the table,
+/// so comparing two symbols of the same table compares their insertion order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the number of the symbol.
+    #[inline]
+    #[must_use]
+    pub const fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the position of the symbol in the insertion order of its table.
+    #[inline]
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl From<Symbol> for u32 {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        value.0
+    }
+}
+
+/// The length of the first segment, every following segment is twice as long as the previous one.
+const FIRST_SEGMENT_LEN: usize = 32;
+/// The number of segments, enough to hold every `u32` symbol.
+const SEGMENTS: usize = 28;
+
+/// A value slot in a segment. The slot is written once, before `ready` is set.
+struct Slot {
+    ready: AtomicBool,
+    value: UnsafeCell<MaybeUninit<(ByteData<'static>, bool)>>,
+}
+
+impl Slot {
+    /// Returns the value and whether it is valid UTF-8, if the slot has been written.
+    fn get(&self) -> Option<&(ByteData<'static>, bool)> {
+        if !self.ready.load(Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: the value is never changed after `ready` is set, so it can be shared.
+        let value = unsafe { &*self.value.get() };
+        // SAFETY: the value is written before `ready` is set.
+        Some(unsafe { value.assume_init_ref() })
+    }
+}
+
+/// Returns the segment and the offset in the segment for a dynamic index.
+const fn slot_position(index: usize) -> (usize, usize) {
+    let pos = index + FIRST_SEGMENT_LEN;
+    let segment =
+        (usize::BITS - 1 - pos.leading_zeros() - FIRST_SEGMENT_LEN.trailing_zeros()) as usize;
+    (segment, pos - (FIRST_SEGMENT_LEN << segment))
+}
+
+/// An interning table that maps byte strings to [`Symbol`] handles.
+///
+/// Symbols are resolved back to their values in constant time without hashing,
+/// and are numbered by insertion order.
+/// A table can be created at compile time with a list of static strings,
+/// which are assigned the first symbols in the order of the list.
+///
+/// The table also implements [`ByteInterning`](super::ByteInterning), interning every value regardless of its length.
+///
+/// ```
+/// use ::bytedata::{interning::SymbolTable, StringData};
+///
+/// static KEYWORDS: SymbolTable = SymbolTable::from_static(&["select", "from", "where"]);
+///
+/// let from = KEYWORDS.symbol_str(StringData::from_borrowed("from"));
+/// assert_eq!(from.as_u32(), 1);
+/// let table = KEYWORDS.symbol_str(StringData::from_borrowed("orders"));
+/// assert_eq!(table.as_u32(), 3);
+/// assert!(from < table);
+/// assert_eq!(KEYWORDS.resolve_str(table).unwrap(), "orders");
+/// ```
+pub struct SymbolTable {
+    statics: &'static [&'static str],
+    map: AtomicPtr<dashmap::DashMap<ByteData<'static>, Symbol>>,
+    segments: [AtomicPtr<Slot>; SEGMENTS],
+    next: AtomicUsize,
+}
+
+impl SymbolTable {
+    /// Creates an empty symbol table.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::from_static(&[])
+    }
+
+    /// Creates a symbol table that starts with the given strings.
+    ///
+    /// The strings are assigned the symbols `0..symbols.len()` in order.
+    /// If a string occurs more than once, looking it up returns its first symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more strings than `u32` symbols.
+    #[inline]
+    #[must_use]
+    pub const fn from_static(symbols: &'static [&'static str]) -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const EMPTY: AtomicPtr<Slot> = AtomicPtr::new(core::ptr::null_mut());
+        assert!(symbols.len() <= u32::MAX as usize, "too many symbols");
+        Self {
+            statics: symbols,
+            map: AtomicPtr::new(core::ptr::null_mut()),
+            segments: [EMPTY; SEGMENTS],
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of symbols in the table.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.statics.len() + self.next.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the table has no symbols.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Initializes the lookup map with the static strings.
+    fn init(&self) -> &dashmap::DashMap<ByteData<'static>, Symbol> {
+        let map = dashmap::DashMap::with_capacity(self.statics.len());
+        for (index, &value) in self.statics.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            map.entry(ByteData::from_static(value.as_bytes()))
+                .or_insert(Symbol(index as u32));
+        }
+        let ptr = Box::into_raw(Box::new(map));
+        match self.map.compare_exchange(
+            core::ptr::null_mut(),
+            ptr,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            // SAFETY: If the pointer is inserted, it is valid.
+            Ok(_) => unsafe { &*ptr },
+            Err(ret) => {
+                // SAFETY: If there is a previous value, the new value should be dropped.
+                core::mem::drop(unsafe { Box::from_raw(ptr) });
+                // SAFETY: The pointer is expected to be valid.
+                unsafe { &*ret }
+            }
+        }
+    }
+
+    /// Returns the lookup map, initializing it if needed.
+    fn map(&self) -> &dashmap::DashMap<ByteData<'static>, Symbol> {
+        let ptr = self.map.load(Ordering::Acquire);
+        // SAFETY: The pointer is expected to be valid or `null`.
+        unsafe { ptr.as_ref() }.unwrap_or_else(|| self.init())
+    }
+
+    /// Returns the slot of a dynamic index, allocating its segment if needed.
+    fn slot_or_insert(&self, index: usize) -> &Slot {
+        let (segment, offset) = slot_position(index);
+        let mut ptr = self.segments[segment].load(Ordering::Acquire);
+        if ptr.is_null() {
+            let slots: Box<[Slot]> = (0..FIRST_SEGMENT_LEN << segment)
+                .map(|_| Slot {
+                    ready: AtomicBool::new(false),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect();
+            let new = Box::into_raw(slots).cast::<Slot>();
+            ptr = match self.segments[segment].compare_exchange(
+                core::ptr::null_mut(),
+                new,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => new,
+                Err(ret) => {
+                    let len = FIRST_SEGMENT_LEN << segment;
+                    // SAFETY: the segment was allocated above and is not shared.
+                    core::mem::drop(unsafe {
+                        Box::from_raw(core::ptr::slice_from_raw_parts_mut(new, len))
+                    });
+                    ret
+                }
+            };
+        }
+        // SAFETY: the segment was created from a boxed slice of `FIRST_SEGMENT_LEN << segment` slots.
+        let slots = unsafe { core::slice::from_raw_parts(ptr, FIRST_SEGMENT_LEN << segment) };
+        &slots[offset]
+    }
+
+    /// Returns the slot of a dynamic index, if its segment has been allocated.
+    fn slot(&self, index: usize) -> Option<&Slot> {
+        let (segment, offset) = slot_position(index);
+        let ptr = self.segments.get(segment)?.load(Ordering::Acquire);
+        if ptr.is_null() {
+            return None;
+        }
+        // SAFETY: the segment was created from a boxed slice of `FIRST_SEGMENT_LEN << segment` slots.
+        let slots = unsafe { core::slice::from_raw_parts(ptr, FIRST_SEGMENT_LEN << segment) };
+        slots.get(offset)
+    }
+
+    /// Returns the symbol for the value, adding the value to the table if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table already holds `u32::MAX` symbols.
+    #[inline]
+    pub fn symbol(&self, value: ByteData<'_>) -> Symbol {
+        self.insert(value, None)
+    }
+
+    /// Returns the symbol for the string, adding the string to the table if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table already holds `u32::MAX` symbols.
+    #[inline]
+    pub fn symbol_str(&self, value: StringData<'_>) -> Symbol {
+        self.insert(value.into_bytedata(), Some(true))
+    }
+
+    /// Looks up or adds a value. `is_str` is set if the value is known to be valid UTF-8.
+    fn insert(&self, value: ByteData<'_>, is_str: Option<bool>) -> Symbol {
+        let map = self.map();
+        if let Some(symbol) = map.get(value.as_slice()) {
+            return *symbol;
+        }
+        match map.entry(value.into_shared()) {
+            dashmap::Entry::Occupied(entry) => *entry.get(),
+            dashmap::Entry::Vacant(entry) => {
+                let statics = self.statics.len();
+                // the index is only reserved if its symbol is below `u32::MAX`, so `len` never overflows
+                #[allow(clippy::expect_used)]
+                let (index, symbol) = self
+                    .next
+                    .fetch_update(Ordering::AcqRel, Ordering::Acquire, |index| {
+                        u32::try_from(statics + index + 1).ok().map(|_| index + 1)
+                    })
+                    .ok()
+                    .and_then(|index| Some((index, u32::try_from(statics + index).ok()?)))
+                    .expect("symbol table is full");
+                let stored = entry.key().clone();
+                let is_str =
+                    is_str.unwrap_or_else(|| core::str::from_utf8(stored.as_slice()).is_ok());
+                let slot = self.slot_or_insert(index);
+                // SAFETY: the index is only handed out once, so nothing else accesses the slot before `ready` is set.
+                unsafe { (*slot.value.get()).write((stored, is_str)) };
+                slot.ready.store(true, Ordering::Release);
+                *entry.insert(Symbol(symbol))
+            }
+        }
+    }
+
+    /// Returns the symbol of a value if it is in the table.
+    #[inline]
+    #[must_use]
+    pub fn get_symbol(&self, value: &[u8]) -> Option<Symbol> {
+        self.map().get(value).map(|symbol| *symbol)
+    }
+
+    /// Returns the value of a symbol, or `None` if the symbol is not part of this table.
+    #[inline]
+    #[must_use]
+    pub fn resolve(&self, symbol: Symbol) -> Option<ByteData<'static>> {
+        self.resolve_entry(symbol).map(|(value, _)| value)
+    }
+
+    /// Returns the value of a symbol as a string, or `None` if the symbol is not part of this table or is not valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub fn resolve_str(&self, symbol: Symbol) -> Option<StringData<'static>> {
+        match self.resolve_entry(symbol)? {
+            // SAFETY: the value has been checked to be valid UTF-8 when it was added.
+            (value, true) => Some(unsafe { StringData::from_bytedata_unchecked(value) }),
+            (_, false) => None,
+        }
+    }
+
+    /// Returns the value of a symbol and whether it is valid UTF-8.
+    fn resolve_entry(&self, symbol: Symbol) -> Option<(ByteData<'static>, bool)> {
+        let index = symbol.index();
+        if let Some(&value) = self.statics.get(index) {
+            return Some((ByteData::from_static(value.as_bytes()), true));
+        }
+        let entry = self.slot(index - self.statics.len())?.get()?;
+        Some((entry.0.clone(), entry.1))
+    }
+
+    /// Returns an iterator over the symbols and their values in insertion order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, ByteData<'static>)> + '_ {
+        (0..self.len()).filter_map(|index| {
+            let symbol = Symbol(u32::try_from(index).ok()?);
+            Some((symbol, self.resolve(symbol)?))
+        })
+    }
+}
+
+impl Default for SymbolTable {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SymbolTable {
+    #[inline]
+    fn drop(&mut self) {
+        let map = core::mem::replace(self.map.get_mut(), core::ptr::null_mut());
+        if !map.is_null() {
+            // SAFETY: The pointer was created by `Box::into_raw`.
+            core::mem::drop(unsafe { Box::from_raw(map) });
+        }
+        for (segment, ptr) in self.segments.iter_mut().enumerate() {
+            let ptr = core::mem::replace(ptr.get_mut(), core::ptr::null_mut());
+            if ptr.is_null() {
+                continue;
+            }
+            let len = FIRST_SEGMENT_LEN << segment;
+            // SAFETY: the segment was created from a boxed slice of `len` slots.
+            let mut slots = unsafe { Box::from_raw(core::ptr::slice_from_raw_parts_mut(ptr, len)) };
+            for slot in slots.iter_mut() {
+                if *slot.ready.get_mut() {
+                    // SAFETY: ready slots hold an initialized value.
+                    unsafe { slot.value.get_mut().assume_init_drop() };
+                }
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for SymbolTable {
+    #[allow(clippy::missing_inline_in_public_items, clippy::min_ident_chars)]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl super::ByteInterning<'static> for SymbolTable {
+    #[inline]
+    fn intern<'b>(&self, value: ByteData<'b>) -> Result<ByteData<'static>, ByteData<'b>> {
+        Ok(self.intern_always(value))
+    }
+
+    #[inline]
+    fn intern_str<'b>(&self, value: StringData<'b>) -> Result<StringData<'static>, StringData<'b>> {
+        Ok(self.intern_always_str(value))
+    }
+
+    #[allow(
+        single_use_lifetimes,
+        clippy::needless_lifetimes,
+        clippy::elidable_lifetime_names
+    )]
+    #[inline]
+    fn intern_always<'b>(&self, value: ByteData<'b>) -> ByteData<'static> {
+        let symbol = self.symbol(value);
+        self.resolve(symbol)
+            .unwrap_or_else(|| unreachable!("symbol is missing from its table"))
+    }
+
+    #[allow(
+        single_use_lifetimes,
+        clippy::needless_lifetimes,
+        clippy::elidable_lifetime_names
+    )]
+    #[inline]
+    fn intern_always_str<'b>(&self, value: StringData<'b>) -> StringData<'static> {
+        let symbol = self.symbol_str(value);
+        self.resolve_entry(symbol).map_or_else(
+            || unreachable!("symbol is missing from its table"),
+            // SAFETY: the bytes are the same as the input string.
+            |(interned, _)| unsafe { StringData::from_bytedata_unchecked(interned) },
+        )
+    }
+
+    #[inline]
+    fn get<'b>(&self, value: ByteData<'b>) -> Result<ByteData<'static>, ByteData<'b>> {
+        self.get_symbol(value.as_slice())
+            .and_then(|symbol| self.resolve(symbol))
+            .map_or_else(|| Err(value), Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::super::ByteInterning;
+    use super::*;
+
+    static KEYWORDS: SymbolTable = SymbolTable::from_static(&["select", "from", "where", "from"]);
+
+    #[test]
+    fn test_symbol_table_static() {
+        assert_eq!(KEYWORDS.get_symbol(b"where"), Some(Symbol(2)));
+        assert_eq!(KEYWORDS.get_symbol(b"from"), Some(Symbol(1)));
+        assert_eq!(KEYWORDS.resolve_str(Symbol(3)).unwrap(), "from");
+        let symbol = KEYWORDS.symbol_str(StringData::from_borrowed("select"));
+        assert_eq!(symbol, Symbol(0));
+    }
+
+    #[test]
+    fn test_symbol_table_insertion_order() {
+        let table = SymbolTable::from_static(&["id"]);
+        let names = ["orders", "customers", "a longer table name than a chunk"];
+        let symbols: alloc::vec::Vec<Symbol> = names
+            .iter()
+            .map(|&name| table.symbol_str(StringData::from_borrowed(name)))
+            .collect();
+        assert_eq!(symbols, [Symbol(1), Symbol(2), Symbol(3)]);
+        assert_eq!(
+            table.symbol(ByteData::from_borrowed(b"customers")),
+            Symbol(2)
+        );
+        assert_eq!(table.len(), 4);
+        let values: alloc::vec::Vec<ByteData<'static>> =
+            table.iter().map(|(_, value)| value).collect();
+        assert_eq!(values[0], b"id".as_slice());
+        assert_eq!(values[3], names[2].as_bytes());
+        assert!(table.resolve(Symbol(4)).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_symbol_table_full() {
+        let table = SymbolTable::from_static(&["id"]);
+        let full = usize::try_from(u32::MAX).unwrap();
+        table.next.store(full - 1, Ordering::Release);
+        let result =
+            std::panic::catch_unwind(|| table.symbol(ByteData::from_borrowed(b"overflow")));
+        assert!(result.ok().is_none());
+        assert_eq!(table.len(), full);
+        assert!(table.get_symbol(b"overflow").is_none());
+        assert_eq!(table.symbol(ByteData::from_borrowed(b"id")), Symbol(0));
+    }
+
+    #[test]
+    fn test_symbol_table_segments() {
+        let table = SymbolTable::new();
+        for index in 0..1000_u32 {
+            let value = alloc::format!("symbol {index}");
+            assert_eq!(table.symbol(value.as_bytes().into()), Symbol(index));
+        }
+        for index in (0..1000_u32).step_by(37) {
+            let value = alloc::format!("symbol {index}");
+            assert_eq!(
+                table.resolve_str(Symbol(index)).unwrap().as_str(),
+                value.as_str()
+            );
+        }
+        assert_eq!(slot_position(0), (0, 0));
+        assert_eq!(slot_position(31), (0, 31));
+        assert_eq!(slot_position(32), (1, 0));
+        assert_eq!(slot_position(96), (2, 0));
+    }
+
+    #[test]
+    fn test_symbol_table_bytes() {
+        let table = SymbolTable::new();
+        let symbol = table.symbol(ByteData::from_borrowed(b"\xff\xfe"));
+        assert_eq!(table.resolve(symbol).unwrap(), b"\xff\xfe".as_slice());
+        assert!(table.resolve_str(symbol).is_none());
+        let interned = table
+            .intern(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .unwrap();
+        let interned2 = table
+            .get(ByteData::from_borrowed(b"Hello World, 1234567890!"))
+            .unwrap();
+        assert!(core::ptr::addr_eq(
+            interned.as_slice().as_ptr(),
+            interned2.as_slice().as_ptr()
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_symbol_table_concurrent() {
+        let table = SymbolTable::from_static(&["static"]);
+        std::thread::scope(|scope| {
+            for _ in 0..4_u32 {
+                scope.spawn(|| {
+                    for index in 0..200_u32 {
+                        let value = alloc::format!("value {index}");
+                        let symbol = table.symbol(value.as_bytes().into());
+                        assert_eq!(table.resolve(symbol).unwrap(), value.as_bytes());
+                    }
+                });
+            }
+        });
+        assert_eq!(table.len(), 201);
+        assert_eq!(table.iter().count(), 201);
+    }
+}
@@ -148,6 +148,16 @@ pub use byte_string_render::*;
 mod char_indicies;
 pub use char_indicies::*;
 
+mod cursor;
+pub use self::cursor::{ByteCursor, UnexpectedEnd};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+mod shared_allocator;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use self::shared_allocator::SharedAllocator;
+
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 mod shared_bytes;
@@ -172,6 +182,9 @@ pub use self::shared_str_builder::*;
 mod stringdata;
 pub use self::stringdata::*;
 
+mod utf8_stream;
+pub use self::utf8_stream::{Utf8StreamChunks, Utf8StreamDecoder};
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 mod std;
@@ -187,6 +200,19 @@ pub use self::macros::*;
 mod byte_chunk;
 pub use byte_chunk::ByteChunk;
 
+mod search;
+
+pub mod pattern;
+pub use self::pattern::Pattern;
+
+pub mod codec;
+
+#[cfg(feature = "queue")]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+pub mod framing;
+
+pub mod wire;
+
 #[cfg(feature = "interning")]
 #[cfg_attr(docsrs, doc(cfg(feature = "interning")))]
 pub mod interning;
@@ -227,18 +253,48 @@ mod http_body_1;
 #[cfg_attr(docsrs, doc(cfg(feature = "http_1")))]
 mod http_1;
 
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+mod mmap;
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub use self::mmap::MappedFile;
+
 #[cfg(feature = "nom_7")]
 #[cfg_attr(docsrs, doc(cfg(feature = "nom_7")))]
 mod nom_7;
 
+#[cfg(feature = "nom_8")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nom_8")))]
+mod nom_8;
+
+#[cfg(feature = "winnow_0")]
+#[cfg_attr(docsrs, doc(cfg(feature = "winnow_0")))]
+mod winnow_0;
+
 #[cfg(feature = "serde_1")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde_1")))]
 mod serde_1;
+#[cfg(all(feature = "serde_1", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde_1", feature = "alloc"))))]
+pub use self::serde_1::helpers as serde;
 
 #[cfg(feature = "postgres-types_02")]
 #[cfg_attr(docsrs, doc(cfg(feature = "postgres-types_02")))]
 mod postgres_02;
 
+#[cfg(feature = "tokio_1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio_1")))]
+pub mod tokio_1;
+
+#[cfg(feature = "futures-io_03")]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures-io_03")))]
+pub mod futures_io_03;
+
+#[cfg(feature = "prost_0")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prost_0")))]
+pub mod prost_0;
+
 /// Checks if two byte slices are equal in a `const` context.
 /// This is however not a *constant time* equality check, as it will return `false` as early as possible.
 #[must_use]
@@ -0,0 +1,181 @@
+use std::{fs::File, io, path::Path};
+
+use crate::{
+    external::{ExternalBytes, ExternalOps},
+    ByteData,
+};
+
+/// A read-only memory map of a file which can be used as external byte data.
+///
+/// When wrapped in a [`ByteData`] all slices share the same mapping, which is unmapped when the last slice is dropped.
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub struct MappedFile {
+    map: memmap2::Mmap,
+}
+
+impl MappedFile {
+    /// Maps the file at `path` into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can not be opened or mapped.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, as that would change the contents of the returned bytes.
+    #[inline]
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the caller guarantees that the file is not modified while mapped.
+        unsafe { Self::map(&file) }
+    }
+
+    /// Maps an open file into memory. The file handle is not needed after this call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can not be mapped.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, as that would change the contents of the returned bytes.
+    #[inline]
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        // SAFETY: the caller guarantees that the file is not modified while mapped.
+        let map = unsafe { memmap2::Mmap::map(file) }?;
+        Ok(Self { map })
+    }
+
+    /// Returns the mapped bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Returns the number of mapped bytes.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the mapping is empty.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Wraps the mapping in a [`ByteData`] without copying, unless the file is small enough to fit in a chunk.
+    #[inline]
+    #[must_use]
+    pub fn into_byte_data(self) -> ByteData<'static> {
+        ByteData::from_external(self)
+    }
+}
+
+impl ExternalBytes for MappedFile {
+    const OPS: ExternalOps<Self> = ExternalOps::new(Self::as_slice);
+}
+
+impl AsRef<[u8]> for MappedFile {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl core::ops::Deref for MappedFile {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl core::fmt::Debug for MappedFile {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MappedFile")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+#[allow(clippy::multiple_inherent_impl)]
+impl ByteData<'static> {
+    /// Maps the file at `path` into memory and returns its contents as a `ByteData`.
+    ///
+    /// Slicing the result is zero-copy and the file is unmapped when the last slice is dropped.
+    /// Files that fit in a chunk are copied and unmapped immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can not be opened or mapped.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, as that would change the contents of the returned bytes.
+    #[inline]
+    pub unsafe fn map_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        // SAFETY: the caller guarantees that the file is not modified while mapped.
+        let map = unsafe { MappedFile::open(path) }?;
+        Ok(map.into_byte_data())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ByteData;
+
+    #[allow(clippy::expect_used)]
+    fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("bytedata-mmap-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).expect("failed to write temporary file");
+        path
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)]
+    fn test_bytedata_map_file() {
+        let contents = b"hello world, this is a memory mapped file with some content";
+        let path = temp_file("large", contents);
+        // SAFETY: the file is not modified during the test.
+        let data = unsafe { ByteData::map_file(&path) }.expect("failed to map file");
+        assert_eq!(data.as_slice(), contents);
+        assert!(data.downcast_external_ref::<super::MappedFile>().is_some());
+
+        let ptr = data.as_slice().as_ptr();
+        let sliced = data.sliced(6..46);
+        core::mem::drop(data);
+        assert_eq!(sliced.as_slice(), &contents[6..46]);
+        // SAFETY: `ptr` points into the mapping which is still alive.
+        assert_eq!(sliced.as_slice().as_ptr(), unsafe { ptr.add(6) });
+        let word = sliced.sliced(..5);
+        core::mem::drop(sliced);
+        assert_eq!(word.as_slice(), b"world");
+        core::mem::drop(word);
+        std::fs::remove_file(&path).expect("failed to remove temporary file");
+    }
+
+    #[test]
+    #[allow(clippy::expect_used)]
+    fn test_bytedata_map_small_file() {
+        let path = temp_file("small", b"tiny");
+        // SAFETY: the file is not modified during the test.
+        let data = unsafe { ByteData::map_file(&path) }.expect("failed to map file");
+        assert_eq!(data.as_slice(), b"tiny");
+        assert!(data.downcast_external_ref::<super::MappedFile>().is_none());
+        std::fs::remove_file(&path).expect("failed to remove temporary file");
+
+        let empty_path = temp_file("empty", b"");
+        // SAFETY: the file is not modified during the test.
+        let empty = unsafe { ByteData::map_file(&empty_path) }.expect("failed to map file");
+        assert!(empty.is_empty());
+        std::fs::remove_file(&empty_path).expect("failed to remove temporary file");
+    }
+}
@@ -0,0 +1,176 @@
+use nom_8 as nom;
+
+impl nom::AsBytes for crate::ByteData<'_> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<'b> nom::Compare<crate::ByteData<'b>> for crate::ByteData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(&self.as_slice(), t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(&self.as_slice(), t.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::ByteData<'b>> for crate::ByteData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(&self.as_slice(), t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(&self.as_slice(), t.as_slice())
+    }
+}
+
+impl<'b> nom::Compare<&'b [u8]> for crate::ByteData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b [u8]) -> nom::CompareResult {
+        nom::Compare::<&'b [u8]>::compare(&self.as_slice(), t)
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b [u8]) -> nom::CompareResult {
+        nom::Compare::<&'b [u8]>::compare_no_case(&self.as_slice(), t)
+    }
+}
+
+impl<'b> nom::Compare<&'b str> for crate::ByteData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::<&'b str>::compare(&self.as_slice(), t)
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::<&'b str>::compare_no_case(&self.as_slice(), t)
+    }
+}
+
+impl<'b> nom::FindSubstring<crate::ByteData<'b>> for crate::ByteData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: crate::ByteData<'b>) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_slice(), substr.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::FindSubstring<&'c crate::ByteData<'b>> for crate::ByteData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'c crate::ByteData<'b>) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_slice(), substr.as_slice())
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b [u8]> for crate::ByteData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b [u8]) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_slice(), substr)
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b str> for crate::ByteData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_slice(), substr)
+    }
+}
+
+impl nom::FindToken<u8> for crate::ByteData<'_> {
+    #[inline]
+    fn find_token(&self, token: u8) -> bool {
+        nom::FindToken::find_token(&self.as_slice(), token)
+    }
+}
+
+impl<'b> nom::FindToken<&'b u8> for crate::ByteData<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b u8) -> bool {
+        nom::FindToken::find_token(&self.as_slice(), token)
+    }
+}
+
+impl nom::FindToken<char> for crate::ByteData<'_> {
+    #[inline]
+    fn find_token(&self, token: char) -> bool {
+        nom::FindToken::find_token(&self.as_slice(), token)
+    }
+}
+
+impl<'b> nom::FindToken<&'b char> for crate::ByteData<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b char) -> bool {
+        nom::FindToken::find_token(&self.as_slice(), *token)
+    }
+}
+
+impl nom::Input for crate::ByteData<'_> {
+    type Item = u8;
+    type Iter = Self;
+    type IterIndices = core::iter::Enumerate<Self>;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        self.sliced(..index)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.sliced(index..)
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.sliced(index..), self.sliced(..index))
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        nom::Input::position(&self.as_slice(), predicate)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.clone()
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        Iterator::enumerate(self.clone())
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, nom::Needed> {
+        nom::Input::slice_index(&self.as_slice(), count)
+    }
+}
+
+impl nom::Offset for crate::ByteData<'_> {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        self.len() - second.len()
+    }
+}
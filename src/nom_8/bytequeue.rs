@@ -0,0 +1,232 @@
+use nom_8 as nom;
+
+impl<'b> nom::Compare<crate::ByteData<'b>> for crate::ByteQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::ByteData<'b>> for crate::ByteQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_slice())
+    }
+}
+
+impl<'b> nom::Compare<crate::StringData<'b>> for crate::ByteQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_str())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_str())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::StringData<'b>> for crate::ByteQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_str())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_str())
+    }
+}
+
+impl<'b> nom::Compare<&'b [u8]> for crate::ByteQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b [u8]) -> nom::CompareResult {
+        let mut rest = t;
+        for s in self.chunks() {
+            let (a, rest2) = rest.split_at(s.len().min(rest.len()));
+            rest = rest2;
+            match nom::Compare::compare(&s.as_slice(), a) {
+                nom::CompareResult::Ok => {}
+                nom::CompareResult::Error => return nom::CompareResult::Error,
+                nom::CompareResult::Incomplete => return nom::CompareResult::Incomplete,
+            }
+        }
+        if rest.is_empty() {
+            nom::CompareResult::Ok
+        } else {
+            nom::CompareResult::Incomplete
+        }
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b [u8]) -> nom::CompareResult {
+        let mut rest = t;
+        for s in self.chunks() {
+            let (a, rest2) = rest.split_at(s.len().min(rest.len()));
+            rest = rest2;
+            match nom::Compare::compare_no_case(&s.as_slice(), a) {
+                nom::CompareResult::Ok => {}
+                nom::CompareResult::Error => return nom::CompareResult::Error,
+                nom::CompareResult::Incomplete => return nom::CompareResult::Incomplete,
+            }
+        }
+        if rest.is_empty() {
+            nom::CompareResult::Ok
+        } else {
+            nom::CompareResult::Incomplete
+        }
+    }
+}
+
+impl<'b> nom::Compare<&'b str> for crate::ByteQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_bytes())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_bytes())
+    }
+}
+
+impl<'b> nom::FindSubstring<crate::ByteData<'b>> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: crate::ByteData<'b>) -> Option<usize> {
+        self.find_slice(substr.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::FindSubstring<&'c crate::ByteData<'b>> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'c crate::ByteData<'b>) -> Option<usize> {
+        self.find_slice(substr.as_slice())
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b [u8]> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b [u8]) -> Option<usize> {
+        self.find_slice(substr)
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b str> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.find_slice(substr.as_bytes())
+    }
+}
+
+impl nom::FindToken<u8> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: u8) -> bool {
+        self.bytes().any(|bv| bv == token)
+    }
+}
+
+impl<'b> nom::FindToken<&'b u8> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b u8) -> bool {
+        let token = *token;
+        self.bytes().any(|bv| bv == token)
+    }
+}
+
+impl nom::FindToken<char> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: char) -> bool {
+        let mut utf8 = [0; 4];
+        let utf8 = token.encode_utf8(&mut utf8);
+        self.find_slice(utf8.as_bytes()).is_some()
+    }
+}
+
+impl<'b> nom::FindToken<&'b char> for crate::ByteQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b char) -> bool {
+        nom::FindToken::find_token(self, *token)
+    }
+}
+
+impl<'a> nom::Input for crate::ByteQueue<'a> {
+    type Item = u8;
+    type Iter = crate::queue::OwnedByteIter<'a>;
+    type IterIndices = core::iter::Enumerate<crate::queue::OwnedByteIter<'a>>;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        self.slice(..index)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.slice(index..)
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.slice(index..), self.slice(..index))
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.find_byte(predicate)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.clone().into_bytes()
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        Iterator::enumerate(self.clone().into_bytes())
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, nom::Needed> {
+        if self.len() >= count {
+            Ok(count)
+        } else {
+            Err(nom::Needed::new(count - self.len()))
+        }
+    }
+}
+
+impl nom::Offset for crate::ByteQueue<'_> {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        self.len() - second.len()
+    }
+}
@@ -0,0 +1,10 @@
+mod bytedata;
+mod stringdata;
+
+#[cfg(feature = "queue")]
+mod bytequeue;
+#[cfg(feature = "queue")]
+mod stringqueue;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,218 @@
+use nom_8 as nom;
+
+impl nom::AsBytes for crate::StringData<'_> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'b> nom::Compare<crate::ByteData<'b>> for crate::StringData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(&self.as_bytes(), t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(&self.as_bytes(), t.as_slice())
+    }
+}
+
+impl<'b> nom::Compare<crate::StringData<'b>> for crate::StringData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(&self.as_str(), t.as_str())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(&self.as_str(), t.as_str())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::ByteData<'b>> for crate::StringData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(&self.as_bytes(), t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(&self.as_bytes(), t.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::StringData<'b>> for crate::StringData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(&self.as_str(), t.as_str())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(&self.as_str(), t.as_str())
+    }
+}
+
+impl<'b> nom::Compare<&'b [u8]> for crate::StringData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b [u8]) -> nom::CompareResult {
+        nom::Compare::<&'b [u8]>::compare(&self.as_bytes(), t)
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b [u8]) -> nom::CompareResult {
+        nom::Compare::<&'b [u8]>::compare_no_case(&self.as_bytes(), t)
+    }
+}
+
+impl<'b> nom::Compare<&'b str> for crate::StringData<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::<&'b str>::compare(&self.as_str(), t)
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::<&'b str>::compare_no_case(&self.as_str(), t)
+    }
+}
+
+impl<'b> nom::FindSubstring<crate::ByteData<'b>> for crate::StringData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: crate::ByteData<'b>) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_bytes(), substr.as_slice())
+    }
+}
+
+impl<'b> nom::FindSubstring<crate::StringData<'b>> for crate::StringData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: crate::StringData<'b>) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_str(), substr.as_str())
+    }
+}
+
+impl<'b: 'c, 'c> nom::FindSubstring<&'c crate::ByteData<'b>> for crate::StringData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'c crate::ByteData<'b>) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_bytes(), substr.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::FindSubstring<&'c crate::StringData<'b>> for crate::StringData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'c crate::StringData<'b>) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_str(), substr.as_str())
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b [u8]> for crate::StringData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b [u8]) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_bytes(), substr)
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b str> for crate::StringData<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        nom::FindSubstring::find_substring(&self.as_str(), substr)
+    }
+}
+
+impl nom::FindToken<u8> for crate::StringData<'_> {
+    #[inline]
+    fn find_token(&self, token: u8) -> bool {
+        nom::FindToken::find_token(&self.as_bytes(), token)
+    }
+}
+
+impl<'b> nom::FindToken<&'b u8> for crate::StringData<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b u8) -> bool {
+        nom::FindToken::find_token(&self.as_bytes(), token)
+    }
+}
+
+impl nom::FindToken<char> for crate::StringData<'_> {
+    #[inline]
+    fn find_token(&self, token: char) -> bool {
+        nom::FindToken::find_token(&self.as_str(), token)
+    }
+}
+
+impl<'b> nom::FindToken<&'b char> for crate::StringData<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b char) -> bool {
+        nom::FindToken::find_token(&self.as_str(), *token)
+    }
+}
+
+impl nom::Input for crate::StringData<'_> {
+    type Item = char;
+    type Iter = Self;
+    type IterIndices = crate::char_indicies::CharOffsets<Self>;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        self.sliced(..index)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.sliced(index..)
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.sliced(index..), self.sliced(..index))
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        nom::Input::position(&self.as_str(), predicate)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.clone()
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.clone().scan(0, crate::char_indicies::char_offset)
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, nom::Needed> {
+        nom::Input::slice_index(&self.as_str(), count)
+    }
+}
+
+impl nom::Offset for crate::StringData<'_> {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        self.len() - second.len()
+    }
+}
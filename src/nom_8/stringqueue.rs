@@ -0,0 +1,223 @@
+use nom_8 as nom;
+
+impl<'b> nom::Compare<crate::ByteData<'b>> for crate::StringQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self.as_bytequeue(), t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self.as_bytequeue(), t.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::ByteData<'b>> for crate::StringQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self.as_bytequeue(), t.as_slice())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::ByteData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self.as_bytequeue(), t.as_slice())
+    }
+}
+
+impl<'b> nom::Compare<crate::StringData<'b>> for crate::StringQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_str())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_str())
+    }
+}
+
+impl<'b: 'c, 'c> nom::Compare<&'c crate::StringData<'b>> for crate::StringQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'c crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_str())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'c crate::StringData<'b>) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self, t.as_str())
+    }
+}
+
+impl<'b> nom::Compare<&'b [u8]> for crate::StringQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b [u8]) -> nom::CompareResult {
+        nom::Compare::compare(self.as_bytequeue(), t)
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b [u8]) -> nom::CompareResult {
+        nom::Compare::compare_no_case(self.as_bytequeue(), t)
+    }
+}
+
+impl<'b> nom::Compare<&'b str> for crate::StringQueue<'_> {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: &'b str) -> nom::CompareResult {
+        nom::Compare::compare(self, t.as_bytes())
+    }
+
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare_no_case(&self, t: &'b str) -> nom::CompareResult {
+        let pos = self
+            .chars()
+            .zip(t.chars())
+            .position(|(a, b)| a.to_lowercase().ne(b.to_lowercase()));
+
+        match pos {
+            Some(_) => nom::CompareResult::Error,
+            None => {
+                if self.len() >= t.len() {
+                    nom::CompareResult::Ok
+                } else {
+                    nom::CompareResult::Incomplete
+                }
+            }
+        }
+    }
+}
+
+impl<'b> nom::FindSubstring<crate::ByteData<'b>> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: crate::ByteData<'b>) -> Option<usize> {
+        self.as_bytequeue().find_slice(substr.as_slice())
+    }
+}
+
+impl<'b: 'c, 'c> nom::FindSubstring<&'c crate::ByteData<'b>> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'c crate::ByteData<'b>) -> Option<usize> {
+        self.as_bytequeue().find_slice(substr.as_slice())
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b [u8]> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b [u8]) -> Option<usize> {
+        self.as_bytequeue().find_slice(substr)
+    }
+}
+
+impl<'b> nom::FindSubstring<&'b str> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.as_bytequeue().find_slice(substr.as_bytes())
+    }
+}
+
+impl nom::FindToken<u8> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: u8) -> bool {
+        self.bytes().any(|bv| bv == token)
+    }
+}
+
+impl<'b> nom::FindToken<&'b u8> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b u8) -> bool {
+        let token = *token;
+        self.bytes().any(|bv| bv == token)
+    }
+}
+
+impl nom::FindToken<char> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: char) -> bool {
+        let mut utf8 = [0; 4];
+        let utf8 = token.encode_utf8(&mut utf8);
+        self.as_bytequeue().find_slice(utf8.as_bytes()).is_some()
+    }
+}
+
+impl<'b> nom::FindToken<&'b char> for crate::StringQueue<'_> {
+    #[inline]
+    fn find_token(&self, token: &'b char) -> bool {
+        nom::FindToken::find_token(self, *token)
+    }
+}
+
+impl<'a> nom::Input for crate::StringQueue<'a> {
+    type Item = char;
+    type Iter = crate::queue::OwnedCharIter<'a>;
+    type IterIndices = crate::char_indicies::CharOffsets<crate::queue::OwnedCharIter<'a>>;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        self.slice(..index)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.slice(index..)
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.slice(index..), self.slice(..index))
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.chars_indecies()
+            .find(|&(_, ch)| predicate(ch))
+            .map(|(index, _)| index)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.clone().into_chars()
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.clone()
+            .into_chars()
+            .scan(0, crate::char_indicies::char_offset)
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, nom::Needed> {
+        let mut chars = self.chars_indecies();
+        match chars.nth(count) {
+            Some((index, _)) => Ok(index),
+            None if self.chars().count() == count => Ok(self.len()),
+            None => Err(nom::Needed::Unknown),
+        }
+    }
+}
+
+impl nom::Offset for crate::StringQueue<'_> {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        self.len() - second.len()
+    }
+}
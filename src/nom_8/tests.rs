@@ -0,0 +1,131 @@
+use nom_8 as nom;
+
+fn read_alpha1<T>(st: T) -> nom::IResult<T, T>
+where
+    T: nom::Input,
+    <T as nom::Input>::Item: nom::AsChar,
+{
+    nom::character::streaming::alpha1(st)
+}
+
+fn read_alpha1_final<T>(st: T) -> nom::IResult<T, T>
+where
+    T: nom::Input,
+    <T as nom::Input>::Item: nom::AsChar,
+{
+    nom::character::complete::alpha1(st)
+}
+
+fn read_ws1<T>(st: T) -> nom::IResult<T, T>
+where
+    T: nom::Input,
+    <T as nom::Input>::Item: nom::AsChar + core::clone::Clone,
+{
+    nom::character::streaming::space1(st)
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_nom_string() {
+    let st = crate::StringData::from_static("hello world");
+    let (st, res) = read_alpha1(st).unwrap();
+    assert_eq!(res, "hello");
+    let (st, res) = read_ws1(st).unwrap();
+    assert_eq!(res, " ");
+    let (st, res) = read_alpha1_final(st).unwrap();
+    assert_eq!(res, "world");
+    assert_eq!(st, "");
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_nom_string_offsets() {
+    use nom::{Input, Offset};
+
+    let st = crate::StringData::from_static("grüße welt");
+    let (rest, res) = nom::bytes::complete::take_while1::<_, _, nom::error::Error<_>>(
+        char::is_alphabetic,
+    )(st.clone())
+    .unwrap();
+    assert_eq!(res, "grüße");
+    assert_eq!(st.offset(&rest), 7);
+    assert_eq!(st.slice_index(4), Ok(6));
+    assert_eq!(st.iter_indices().nth(3), Some((4, 'ß')));
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_nom_bytes() {
+    let st = crate::ByteData::from_static(b"hello world");
+    let (st, res) = read_alpha1(st).unwrap();
+    assert_eq!(res, b"hello".as_slice());
+    let (st, res) = read_ws1(st).unwrap();
+    assert_eq!(res, b" ".as_slice());
+    let (st, res) = read_alpha1_final(st).unwrap();
+    assert_eq!(res, b"world".as_slice());
+    assert_eq!(st, b"".as_slice());
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_nom_string_queue() {
+    let st = crate::queue::StringQueue::from_iter([
+        crate::StringData::from_static("hel"),
+        crate::StringData::from_static("lo"),
+        crate::StringData::from_static(" w"),
+        crate::StringData::from_static("orld"),
+    ]);
+    let (st, res) = read_alpha1(st).unwrap();
+    assert_eq!(res, "hello");
+    let (st, res) = read_ws1(st).unwrap();
+    assert_eq!(res, " ");
+    let (st, res) = read_alpha1_final(st).unwrap();
+    assert_eq!(res, "world");
+    assert_eq!(st, "");
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_nom_byte_queue_streaming() {
+    use nom::{bytes::streaming::tag, bytes::streaming::take_until, Offset};
+
+    #[allow(clippy::result_large_err)]
+    fn header(
+        input: crate::ByteQueue<'static>,
+    ) -> nom::IResult<
+        crate::ByteQueue<'static>,
+        (crate::ByteQueue<'static>, crate::ByteQueue<'static>),
+    > {
+        let (input, name) = take_until(": ")(input)?;
+        let (input, _) = tag(": ")(input)?;
+        let (input, value) = take_until("\r\n")(input)?;
+        let (input, _) = tag("\r\n")(input)?;
+        Ok((input, (name, value)))
+    }
+
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(crate::ByteData::from_static(b"Host"));
+    assert!(matches!(
+        header(queue.clone()),
+        Err(nom::Err::Incomplete(_))
+    ));
+    queue.push_back(crate::ByteData::from_static(b": exam"));
+    assert!(matches!(
+        header(queue.clone()),
+        Err(nom::Err::Incomplete(_))
+    ));
+    queue.push_back(crate::ByteData::from_static(b"ple.com\r"));
+    assert!(matches!(
+        header(queue.clone()),
+        Err(nom::Err::Incomplete(_))
+    ));
+    queue.push_back(crate::ByteData::from_static(b"\nAccept: */*\r\n"));
+    let (rest, (name, value)) = header(queue.clone()).unwrap();
+    assert_eq!(name, b"Host".as_slice());
+    assert_eq!(value, b"example.com".as_slice());
+    assert_eq!(value.chunk_len(), 2);
+    assert_eq!(queue.offset(&rest), 19);
+    assert_eq!(rest, b"Accept: */*\r\n".as_slice());
+}
@@ -0,0 +1,580 @@
+//! Patterns used to search and split byte and string data.
+//!
+//! The [`Pattern`] trait is accepted by the `find`, `rfind`, `split`, `splitn`, `rsplit`, `split_inclusive`,
+//! `strip_prefix`, and `strip_suffix` methods of [`ByteData`](crate::ByteData), [`StringData`](crate::StringData),
+//! [`ByteQueue`](crate::queue::ByteQueue), and [`StringQueue`](crate::queue::StringQueue).
+
+/// A pattern that can be searched for in byte and string data.
+///
+/// | Pattern                      | Matches                                      |
+/// |------------------------------|----------------------------------------------|
+/// | `u8`                         | the byte                                     |
+/// | `char`                       | the UTF-8 encoding of the char               |
+/// | `&[u8]`                      | the byte sequence                            |
+/// | `&str`                       | the UTF-8 encoding of the string             |
+/// | `&[u8; N]`                   | the byte sequence                            |
+/// | [`AnyOf`]                    | any of the bytes in the array                |
+/// | `F: FnMut(char) -> bool`     | any char for which the closure returns true  |
+/// | [`BytePredicate`]            | any byte for which the closure returns true  |
+///
+/// Byte string literals such as `b"\r\n"` match the whole sequence, like the methods of `[u8]` do.
+/// Use `AnyOf(*b"\r\n")` to match any one of their bytes.
+///
+/// When searching string data only matches that start and end on char boundaries are considered.
+/// A pattern that matches an empty sequence is found by `find`, but never splits the data.
+///
+/// ```
+/// use ::bytedata::{pattern::AnyOf, ByteData};
+///
+/// let data = ByteData::from_static(b"key=value; other=thing");
+/// assert_eq!(data.find(b'='), Some(3));
+/// assert_eq!(data.rfind(b"=;"), None);
+/// assert_eq!(data.rfind(AnyOf(*b"=;")), Some(16));
+/// assert_eq!(data.find("other"), Some(11));
+/// ```
+pub trait Pattern: Sized {
+    /// The searcher used to find the pattern.
+    type Searcher: Searcher;
+
+    /// Converts the pattern into its searcher.
+    fn into_searcher(self) -> Self::Searcher;
+}
+
+/// Finds the matches of a [`Pattern`] in a byte slice.
+pub trait Searcher {
+    /// Returns the start and end of the first match in `haystack`.
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)>;
+
+    /// Returns the start and end of the last match in `haystack`.
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)>;
+
+    /// Returns the length of the match at the start of `haystack`.
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        match self.find_in(haystack) {
+            Some((0, end)) => Some(end),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the match at the end of `haystack`.
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        match self.rfind_in(haystack) {
+            Some((start, end)) if end == haystack.len() => Some(end - start),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte sequence if the searcher only matches that exact sequence.
+    ///
+    /// Queues use the sequence to find matches that span multiple chunks, other searchers only match within a single chunk.
+    #[inline]
+    fn needle(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
+impl<S: Searcher> Pattern for S {
+    type Searcher = Self;
+
+    #[inline]
+    fn into_searcher(self) -> Self {
+        self
+    }
+}
+
+impl Searcher for u8 {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let needle = *self;
+        let pos = haystack.iter().position(|&byte| byte == needle)?;
+        Some((pos, pos + 1))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let needle = *self;
+        let pos = haystack.iter().rposition(|&byte| byte == needle)?;
+        Some((pos, pos + 1))
+    }
+
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        (haystack.first() == Some(self)).then_some(1)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        (haystack.last() == Some(self)).then_some(1)
+    }
+
+    #[inline]
+    fn needle(&self) -> Option<&[u8]> {
+        Some(core::slice::from_ref(self))
+    }
+}
+
+impl Searcher for &[u8] {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let pos = crate::search::find_bytes(haystack, self)?;
+        Some((pos, pos + self.len()))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let pos = crate::search::rfind_bytes(haystack, self)?;
+        Some((pos, pos + self.len()))
+    }
+
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        haystack.starts_with(self).then_some(self.len())
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        haystack.ends_with(self).then_some(self.len())
+    }
+
+    #[inline]
+    fn needle(&self) -> Option<&[u8]> {
+        Some(self)
+    }
+}
+
+impl Searcher for &str {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.as_bytes().find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.as_bytes().rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        self.as_bytes().prefix_len(haystack)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        self.as_bytes().suffix_len(haystack)
+    }
+
+    #[inline]
+    fn needle(&self) -> Option<&[u8]> {
+        Some(self.as_bytes())
+    }
+}
+
+impl<const N: usize> Searcher for &[u8; N] {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.as_slice().find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.as_slice().rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        self.as_slice().prefix_len(haystack)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        self.as_slice().suffix_len(haystack)
+    }
+
+    #[inline]
+    fn needle(&self) -> Option<&[u8]> {
+        Some(self.as_slice())
+    }
+}
+
+/// A pattern that matches any one of the bytes in the array.
+///
+/// ```
+/// use ::bytedata::{pattern::AnyOf, ByteData};
+///
+/// let data = ByteData::from_static(b"a,b;c");
+/// let parts: Vec<_> = data.split(AnyOf(*b",;")).collect();
+/// assert_eq!(parts, [b"a".as_slice(), b"b", b"c"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::exhaustive_structs)]
+pub struct AnyOf<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> Searcher for AnyOf<N> {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let pos = haystack.iter().position(|byte| self.0.contains(byte))?;
+        Some((pos, pos + 1))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let pos = haystack.iter().rposition(|byte| self.0.contains(byte))?;
+        Some((pos, pos + 1))
+    }
+
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        haystack
+            .first()
+            .is_some_and(|byte| self.0.contains(byte))
+            .then_some(1)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        haystack
+            .last()
+            .is_some_and(|byte| self.0.contains(byte))
+            .then_some(1)
+    }
+}
+
+/// The searcher for a `char`, which matches the UTF-8 encoding of the char.
+#[derive(Debug, Clone, Copy)]
+pub struct CharSearcher {
+    encoded: [u8; 4],
+    len: usize,
+}
+
+impl CharSearcher {
+    /// Creates a searcher for the UTF-8 encoding of `ch`.
+    #[inline]
+    #[must_use]
+    pub fn new(ch: char) -> Self {
+        let mut encoded = [0; 4];
+        let len = ch.encode_utf8(&mut encoded).len();
+        Self { encoded, len }
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        &self.encoded[..self.len]
+    }
+}
+
+impl Pattern for char {
+    type Searcher = CharSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> CharSearcher {
+        CharSearcher::new(self)
+    }
+}
+
+impl Searcher for CharSearcher {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.as_slice().find_in(haystack)
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.as_slice().rfind_in(haystack)
+    }
+
+    #[inline]
+    fn prefix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        self.as_slice().prefix_len(haystack)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, haystack: &[u8]) -> Option<usize> {
+        self.as_slice().suffix_len(haystack)
+    }
+
+    #[inline]
+    fn needle(&self) -> Option<&[u8]> {
+        Some(self.as_slice())
+    }
+}
+
+/// Decodes `bytes` if it holds exactly one UTF-8 encoded char.
+#[inline]
+fn decode_char(bytes: &[u8]) -> Option<char> {
+    let mut chars = core::str::from_utf8(bytes).ok()?.chars();
+    let ch = chars.next()?;
+    chars.next().is_none().then_some(ch)
+}
+
+/// Returns the length of the UTF-8 sequence starting with `lead`, treating invalid lead bytes as a single byte.
+#[inline]
+pub(crate) const fn utf8_width(lead: u8) -> usize {
+    match lead {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    }
+}
+
+/// Matches any char for which the closure returns `true`. Bytes that are not valid UTF-8 never match.
+impl<F: FnMut(char) -> bool> Searcher for F {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let mut pos = 0;
+        while pos < haystack.len() {
+            let end = (pos + utf8_width(haystack[pos])).min(haystack.len());
+            match decode_char(&haystack[pos..end]) {
+                Some(ch) if self(ch) => return Some((pos, end)),
+                Some(_) => pos = end,
+                None => pos += 1,
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let mut end = haystack.len();
+        while end != 0 {
+            let mut start = end - 1;
+            while start != 0 && end - start < 4 && haystack[start] & 0xC0 == 0x80 {
+                start -= 1;
+            }
+            match decode_char(&haystack[start..end]) {
+                Some(ch) if self(ch) => return Some((start, end)),
+                Some(_) => end = start,
+                None => end -= 1,
+            }
+        }
+        None
+    }
+}
+
+/// A pattern matching any byte for which the closure returns `true`.
+///
+/// ```
+/// use ::bytedata::{pattern::BytePredicate, ByteData};
+///
+/// let data = ByteData::from_static(b"abc123");
+/// assert_eq!(data.find(BytePredicate(|b: u8| b.is_ascii_digit())), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::exhaustive_structs)]
+pub struct BytePredicate<F>(pub F);
+
+impl<F: FnMut(u8) -> bool> Searcher for BytePredicate<F> {
+    #[inline]
+    fn find_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let pos = haystack.iter().position(|&byte| (self.0)(byte))?;
+        Some((pos, pos + 1))
+    }
+
+    #[inline]
+    fn rfind_in(&mut self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let pos = haystack.iter().rposition(|&byte| (self.0)(byte))?;
+        Some((pos, pos + 1))
+    }
+}
+
+/// Checks if `index` is on a char boundary of the UTF-8 data in `haystack`.
+#[inline]
+const fn is_char_boundary(haystack: &[u8], index: usize) -> bool {
+    index >= haystack.len() || haystack[index] & 0xC0 != 0x80
+}
+
+/// Checks if a needle can match in UTF-8 data.
+///
+/// A needle of valid UTF-8 can only match on char boundaries, while a needle of invalid UTF-8 never does.
+#[inline]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const fn needle_utf8(needle: &[u8]) -> bool {
+    core::str::from_utf8(needle).is_ok()
+}
+
+/// Finds the first match in `haystack`. When `utf8` is set the haystack must be valid UTF-8, and only matches on char boundaries are returned.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn find_match<S: Searcher>(
+    searcher: &mut S,
+    haystack: &[u8],
+    utf8: bool,
+) -> Option<(usize, usize)> {
+    if !utf8 {
+        return searcher.find_in(haystack);
+    }
+    if let Some(valid) = searcher.needle().map(needle_utf8) {
+        return if valid {
+            searcher.find_in(haystack)
+        } else {
+            None
+        };
+    }
+    let mut pos = 0;
+    while let Some((start, end)) = searcher.find_in(&haystack[pos..]) {
+        let (start, end) = (pos + start, pos + end);
+        if is_char_boundary(haystack, start) && is_char_boundary(haystack, end) {
+            return Some((start, end));
+        }
+        pos = start + 1;
+    }
+    None
+}
+
+/// Finds the last match in `haystack`. When `utf8` is set the haystack must be valid UTF-8, and only matches on char boundaries are returned.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn rfind_match<S: Searcher>(
+    searcher: &mut S,
+    haystack: &[u8],
+    utf8: bool,
+) -> Option<(usize, usize)> {
+    if !utf8 {
+        return searcher.rfind_in(haystack);
+    }
+    if let Some(valid) = searcher.needle().map(needle_utf8) {
+        return if valid {
+            searcher.rfind_in(haystack)
+        } else {
+            None
+        };
+    }
+    let mut limit = haystack.len();
+    while let Some((start, end)) = searcher.rfind_in(&haystack[..limit]) {
+        if is_char_boundary(haystack, start) && is_char_boundary(haystack, end) {
+            return Some((start, end));
+        }
+        if end == 0 {
+            break;
+        }
+        limit = end - 1;
+    }
+    None
+}
+
+/// Returns the length of the match at the start of `haystack`, see [`find_match`].
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn prefix_match<S: Searcher>(
+    searcher: &mut S,
+    haystack: &[u8],
+    utf8: bool,
+) -> Option<usize> {
+    if utf8 && searcher.needle().is_some_and(|needle| !needle_utf8(needle)) {
+        return None;
+    }
+    let len = searcher.prefix_len(haystack)?;
+    (!utf8 || is_char_boundary(haystack, len)).then_some(len)
+}
+
+/// Returns the length of the match at the end of `haystack`, see [`find_match`].
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn suffix_match<S: Searcher>(
+    searcher: &mut S,
+    haystack: &[u8],
+    utf8: bool,
+) -> Option<usize> {
+    if utf8 && searcher.needle().is_some_and(|needle| !needle_utf8(needle)) {
+        return None;
+    }
+    let len = searcher.suffix_len(haystack)?;
+    (!utf8 || is_char_boundary(haystack, haystack.len() - len)).then_some(len)
+}
+
+/// The state of a split over a single [`ByteData`](crate::ByteData), shared by the byte and string variants.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) struct SplitData<'a, S> {
+    data: crate::ByteData<'a>,
+    searcher: S,
+    /// The number of items that may still be returned.
+    remaining: usize,
+    /// Split from the end of the data.
+    reverse: bool,
+    /// Keep the match at the end of each item.
+    inclusive: bool,
+    /// The data is valid UTF-8 and matches must be on char boundaries.
+    utf8: bool,
+}
+
+impl<'a, S: Searcher> SplitData<'a, S> {
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) const fn new(
+        data: crate::ByteData<'a>,
+        searcher: S,
+        max: usize,
+        utf8: bool,
+    ) -> Self {
+        let remaining = if data.is_empty() { 0 } else { max };
+        Self {
+            data,
+            searcher,
+            remaining,
+            reverse: false,
+            inclusive: false,
+            utf8,
+        }
+    }
+
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) const fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    #[allow(clippy::redundant_pub_crate)]
+    pub(crate) const fn inclusive(mut self) -> Self {
+        self.inclusive = true;
+        self
+    }
+
+    fn take_rest(&mut self) -> crate::ByteData<'a> {
+        self.remaining = 0;
+        core::mem::replace(&mut self.data, const { crate::ByteData::empty() })
+    }
+}
+
+impl<'a, S: Searcher> Iterator for SplitData<'a, S> {
+    type Item = crate::ByteData<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.remaining == 1 {
+            return Some(self.take_rest());
+        }
+        let found = if self.reverse {
+            rfind_match(&mut self.searcher, self.data.as_slice(), self.utf8)
+        } else {
+            find_match(&mut self.searcher, self.data.as_slice(), self.utf8)
+        };
+        let Some((start, end)) = found.filter(|&(start, end)| start != end) else {
+            return Some(self.take_rest());
+        };
+        self.remaining -= 1;
+        if self.reverse {
+            let item = self.data.sliced(end..);
+            self.data.make_sliced(..start);
+            return Some(item);
+        }
+        if self.inclusive {
+            let item = self.data.take_bytes(end);
+            if self.data.is_empty() {
+                self.remaining = 0;
+            }
+            return Some(item);
+        }
+        let item = self.data.take_bytes(start);
+        self.data.make_sliced(end - start..);
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining == 0 {
+            (0, Some(0))
+        } else {
+            (1, Some(self.remaining.min(self.data.len() + 1)))
+        }
+    }
+}
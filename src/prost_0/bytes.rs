@@ -0,0 +1,87 @@
+//! Field functions for `bytes` fields held in a [`BytesAdapter`], with the same signatures as `prost::encoding::bytes`.
+
+use alloc::vec::Vec;
+
+use ::bytes_1::{Buf, BufMut};
+use ::prost_0::encoding::{
+    check_wire_type, decode_varint, encode_key, encode_varint, encoded_len_varint, key_len,
+    DecodeContext, WireType,
+};
+use ::prost_0::DecodeError;
+
+use super::BytesAdapter;
+
+/// Encodes a `bytes` field.
+#[inline]
+pub fn encode<T: BytesAdapter, B: BufMut>(tag: u32, value: &T, buf: &mut B) {
+    let bytes = value.as_slice();
+    encode_key(tag, WireType::LengthDelimited, buf);
+    encode_varint(bytes.len() as u64, buf);
+    buf.put_slice(bytes);
+}
+
+/// Decodes the value of a `bytes` field, which replaces `value`.
+///
+/// The value is taken from `buf` with `Buf::copy_to_bytes`, which does not copy it
+/// if `buf` is a [`ByteData`](crate::ByteData), [`SharedBytes`](crate::SharedBytes) or [`ByteQueue`](crate::ByteQueue).
+///
+/// # Errors
+///
+/// Returns an error if the wire type is not length-delimited or if `buf` ends before the value does.
+#[inline]
+pub fn merge<T: BytesAdapter, B: Buf>(
+    wire_type: WireType,
+    value: &mut T,
+    buf: &mut B,
+    _ctx: DecodeContext,
+) -> Result<(), DecodeError> {
+    check_wire_type(WireType::LengthDelimited, wire_type)?;
+    let len = decode_varint(buf)?;
+    let len = match usize::try_from(len) {
+        Ok(len) if len <= buf.remaining() => len,
+        _ => return Err(super::decode_error("buffer underflow")),
+    };
+    *value = T::from_bytes(buf.copy_to_bytes(len));
+    Ok(())
+}
+
+/// Encodes a repeated `bytes` field.
+#[inline]
+pub fn encode_repeated<T: BytesAdapter, B: BufMut>(tag: u32, values: &[T], buf: &mut B) {
+    for value in values {
+        encode(tag, value, buf);
+    }
+}
+
+/// Decodes a value of a repeated `bytes` field and appends it to `values`.
+///
+/// # Errors
+///
+/// Returns an error if the wire type is not length-delimited or if `buf` ends before the value does.
+#[inline]
+pub fn merge_repeated<T: BytesAdapter, B: Buf>(
+    wire_type: WireType,
+    values: &mut Vec<T>,
+    buf: &mut B,
+    ctx: DecodeContext,
+) -> Result<(), DecodeError> {
+    let mut value = T::default();
+    merge(wire_type, &mut value, buf, ctx)?;
+    values.push(value);
+    Ok(())
+}
+
+/// Returns the encoded length of a `bytes` field.
+#[inline]
+#[must_use]
+pub fn encoded_len<T: BytesAdapter>(tag: u32, value: &T) -> usize {
+    let len = value.as_slice().len();
+    key_len(tag) + encoded_len_varint(len as u64) + len
+}
+
+/// Returns the encoded length of a repeated `bytes` field.
+#[inline]
+#[must_use]
+pub fn encoded_len_repeated<T: BytesAdapter>(tag: u32, values: &[T]) -> usize {
+    values.iter().map(|value| encoded_len(tag, value)).sum()
+}
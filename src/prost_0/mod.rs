@@ -0,0 +1,71 @@
+//! Support for `prost@0` messages with [`ByteData`], [`SharedBytes`](crate::SharedBytes) and [`StringData`](crate::StringData) fields.
+//!
+//! The `bytes` fields of messages generated by `prost` can only hold `Vec<u8>` or `bytes::Bytes`, as the adapter trait of `prost` is sealed.
+//! Messages that implement [`Message`] themselves can use the [`bytes`] and [`string`] modules instead,
+//! which provide the same field functions as `prost::encoding::bytes` and `prost::encoding::string`
+//! for any [`BytesAdapter`] and for [`StringData<'static>`](crate::StringData).
+//!
+//! `Buf` is implemented for [`ByteData`], [`SharedBytes`](crate::SharedBytes) and [`ByteQueue`](crate::ByteQueue),
+//! so a message can be decoded from any of them with [`Message::decode`].
+//! The decoded fields are then slices shared with the input, except for short values that are stored in a chunk.
+//! [`SharedBytesBuilder`] implements `BufMut`, and [`encode_to_bytedata`] encodes a message into one.
+
+use ::bytes_1::Bytes;
+use ::prost_0::{DecodeError, Message};
+
+use crate::{ByteData, SharedBytesBuilder};
+
+pub mod bytes;
+pub mod string;
+
+#[cfg(test)]
+mod tests;
+
+/// A type that can hold the value of a `bytes` field, like the sealed `BytesAdapter` trait of `prost`.
+pub trait BytesAdapter: Default + 'static {
+    /// Returns the bytes of the value.
+    fn as_slice(&self) -> &[u8];
+
+    /// Creates a value from bytes taken from the input of a decoder.
+    fn from_bytes(bytes: Bytes) -> Self;
+}
+
+impl BytesAdapter for ByteData<'static> {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        ByteData::as_slice(self)
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Bytes) -> Self {
+        bytes.into()
+    }
+}
+
+impl BytesAdapter for crate::SharedBytes {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        Self::as_slice(self)
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Bytes) -> Self {
+        bytes.into()
+    }
+}
+
+/// Encodes a message into a [`ByteData`] backed by a single shared buffer.
+#[inline]
+#[must_use]
+pub fn encode_to_bytedata<M: Message>(message: &M) -> ByteData<'static> {
+    let mut builder = SharedBytesBuilder::with_capacity(message.encoded_len());
+    let result = message.encode(&mut builder);
+    debug_assert!(result.is_ok(), "the builder grows as needed");
+    builder.into()
+}
+
+/// Creates a decode error with the same descriptions that `prost` uses.
+#[allow(deprecated)]
+fn decode_error(description: &'static str) -> DecodeError {
+    DecodeError::new(description)
+}
@@ -0,0 +1,96 @@
+//! Field functions for `string` fields held in a [`StringData<'static>`](StringData), with the same signatures as `prost::encoding::string`.
+
+use alloc::vec::Vec;
+
+use ::bytes_1::{Buf, BufMut};
+use ::prost_0::encoding::{DecodeContext, WireType};
+use ::prost_0::DecodeError;
+
+use crate::{ByteData, StringData};
+
+impl super::BytesAdapter for StringData<'static> {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    /// Creates a string from the bytes, replacing invalid UTF-8 with `U+FFFD`.
+    ///
+    /// [`merge`] rejects invalid UTF-8 instead.
+    #[inline]
+    fn from_bytes(bytes: ::bytes_1::Bytes) -> Self {
+        Self::from_utf8_lossy(bytes.into())
+    }
+}
+
+/// Encodes a `string` field.
+#[inline]
+pub fn encode<B: BufMut>(tag: u32, value: &StringData<'static>, buf: &mut B) {
+    super::bytes::encode(tag, value, buf);
+}
+
+/// Decodes the value of a `string` field, which replaces `value`.
+///
+/// The value is a slice of `buf` in the same cases as for [`bytes::merge`](super::bytes::merge).
+///
+/// # Errors
+///
+/// Returns an error if the wire type is not length-delimited, if `buf` ends before the value does,
+/// or if the value is not valid UTF-8, in which case `value` is cleared.
+#[inline]
+pub fn merge<B: Buf>(
+    wire_type: WireType,
+    value: &mut StringData<'static>,
+    buf: &mut B,
+    ctx: DecodeContext,
+) -> Result<(), DecodeError> {
+    let mut bytes = ByteData::empty();
+    super::bytes::merge(wire_type, &mut bytes, buf, ctx)?;
+    let Ok(string) = StringData::try_from_bytedata(bytes) else {
+        *value = StringData::empty();
+        return Err(super::decode_error(
+            "invalid string value: data is not UTF-8 encoded",
+        ));
+    };
+    *value = string;
+    Ok(())
+}
+
+/// Encodes a repeated `string` field.
+#[inline]
+pub fn encode_repeated<B: BufMut>(tag: u32, values: &[StringData<'static>], buf: &mut B) {
+    super::bytes::encode_repeated(tag, values, buf);
+}
+
+/// Decodes a value of a repeated `string` field and appends it to `values`.
+///
+/// # Errors
+///
+/// Returns an error if the wire type is not length-delimited, if `buf` ends before the value does,
+/// or if the value is not valid UTF-8.
+#[inline]
+pub fn merge_repeated<B: Buf>(
+    wire_type: WireType,
+    values: &mut Vec<StringData<'static>>,
+    buf: &mut B,
+    ctx: DecodeContext,
+) -> Result<(), DecodeError> {
+    let mut value = StringData::empty();
+    merge(wire_type, &mut value, buf, ctx)?;
+    values.push(value);
+    Ok(())
+}
+
+/// Returns the encoded length of a `string` field.
+#[inline]
+#[must_use]
+pub fn encoded_len(tag: u32, value: &StringData<'static>) -> usize {
+    super::bytes::encoded_len(tag, value)
+}
+
+/// Returns the encoded length of a repeated `string` field.
+#[inline]
+#[must_use]
+pub fn encoded_len_repeated(tag: u32, values: &[StringData<'static>]) -> usize {
+    super::bytes::encoded_len_repeated(tag, values)
+}
@@ -0,0 +1,158 @@
+use alloc::{string::ToString, vec::Vec};
+
+use ::bytes_1::{Buf, BufMut};
+use ::prost_0::encoding::{skip_field, DecodeContext, WireType};
+use ::prost_0::{DecodeError, Message};
+
+use crate::{ByteData, SharedBytes, StringData};
+
+/// A message with the fields that `prost` would generate from:
+///
+/// ```proto
+/// message Record {
+///     bytes payload = 1;
+///     string name = 2;
+///     repeated string tags = 3;
+///     bytes raw = 4;
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Record {
+    payload: ByteData<'static>,
+    name: StringData<'static>,
+    tags: Vec<StringData<'static>>,
+    raw: SharedBytes,
+}
+
+impl Message for Record {
+    fn encode_raw(&self, buf: &mut impl BufMut) {
+        if !self.payload.is_empty() {
+            super::bytes::encode(1, &self.payload, buf);
+        }
+        if !self.name.is_empty() {
+            super::string::encode(2, &self.name, buf);
+        }
+        super::string::encode_repeated(3, &self.tags, buf);
+        if !self.raw.is_empty() {
+            super::bytes::encode(4, &self.raw, buf);
+        }
+    }
+
+    fn merge_field(
+        &mut self,
+        tag: u32,
+        wire_type: WireType,
+        buf: &mut impl Buf,
+        ctx: DecodeContext,
+    ) -> Result<(), DecodeError> {
+        match tag {
+            1 => super::bytes::merge(wire_type, &mut self.payload, buf, ctx),
+            2 => super::string::merge(wire_type, &mut self.name, buf, ctx),
+            3 => super::string::merge_repeated(wire_type, &mut self.tags, buf, ctx),
+            4 => super::bytes::merge(wire_type, &mut self.raw, buf, ctx),
+            _ => skip_field(wire_type, tag, buf, ctx),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        let mut len = super::string::encoded_len_repeated(3, &self.tags);
+        if !self.payload.is_empty() {
+            len += super::bytes::encoded_len(1, &self.payload);
+        }
+        if !self.name.is_empty() {
+            len += super::string::encoded_len(2, &self.name);
+        }
+        if !self.raw.is_empty() {
+            len += super::bytes::encoded_len(4, &self.raw);
+        }
+        len
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn record() -> Record {
+    Record {
+        payload: ByteData::from_static(b"a payload longer than a chunk"),
+        name: StringData::from_static("a name longer than a chunk"),
+        tags: alloc::vec![
+            StringData::from_static("short"),
+            StringData::from_static("tag")
+        ],
+        raw: SharedBytes::from_slice(b"raw bytes longer than a chunk"),
+    }
+}
+
+/// Returns `true` if `inner` lies within `outer`.
+fn is_within(outer: &[u8], inner: &[u8]) -> bool {
+    let outer = outer.as_ptr_range();
+    let inner = inner.as_ptr_range();
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_roundtrip_shares_input() {
+    let record = record();
+    let encoded = super::encode_to_bytedata(&record);
+    assert_eq!(encoded.len(), record.encoded_len());
+    assert_eq!(encoded.as_slice(), record.encode_to_vec().as_slice());
+
+    let decoded = Record::decode(encoded.clone()).unwrap();
+    assert_eq!(decoded, record);
+    if cfg!(feature = "bytes_1_safe") {
+        // shared buffers are copied when converted to `Bytes`
+        return;
+    }
+    assert!(is_within(encoded.as_slice(), decoded.payload.as_slice()));
+    assert!(is_within(encoded.as_slice(), decoded.name.as_bytes()));
+    assert!(is_within(encoded.as_slice(), decoded.raw.as_slice()));
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_decode_static() {
+    static ENCODED: &[u8] = b"\x0A\x1Da payload longer than a chunk\x12\x03abc";
+    let decoded = Record::decode(ByteData::from_static(ENCODED)).unwrap();
+    assert_eq!(decoded.payload.as_slice(), b"a payload longer than a chunk");
+    assert_eq!(decoded.name.as_str(), "abc");
+    #[cfg(not(feature = "bytes_1_safe"))]
+    assert!(is_within(ENCODED, decoded.payload.as_slice()));
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_decode_queue() {
+    let encoded = super::encode_to_bytedata(&record());
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(encoded.sliced(..10));
+    queue.push_back(encoded.sliced(10..));
+    let decoded = Record::decode(queue).unwrap();
+    assert_eq!(decoded, record());
+    #[cfg(not(feature = "bytes_1_safe"))]
+    assert!(is_within(encoded.as_slice(), decoded.name.as_bytes()));
+}
+
+#[test]
+fn test_decode_errors() {
+    let invalid_utf8 = Record::decode(ByteData::from_static(b"\x12\x02\xC3\x28"));
+    assert_eq!(
+        invalid_utf8.map_err(|err| err.to_string().contains("UTF-8")),
+        Err(true)
+    );
+
+    let underflow = Record::decode(ByteData::from_static(b"\x0A\x05abc"));
+    assert_eq!(
+        underflow.map_err(|err| err.to_string().contains("buffer underflow")),
+        Err(true)
+    );
+
+    let wire_type = Record::decode(ByteData::from_static(b"\x08\x01"));
+    assert_eq!(
+        wire_type.map_err(|err| err.to_string().contains("wire type")),
+        Err(true)
+    );
+}
@@ -1,11 +1,57 @@
 use core::{ops::RangeBounds, panic};
 
-use crate::ByteData;
+use crate::{pattern::Searcher, ByteData, Pattern};
 
 use super::byte_iter::{ByteIter, OwnedByteIter};
 use super::linked_root::LinkedRoot;
 use crate::queue::ChunkIter;
 
+/// Checks if the chunks continue the match of `needle` which started with `head`.
+fn continues_with<'a: 'b, 'b>(
+    head: &[u8],
+    mut chunks: impl Iterator<Item = &'b ByteData<'a>>,
+    needle: &[u8],
+) -> bool {
+    let Some(mut rest) = needle.strip_prefix(head) else {
+        return false;
+    };
+    while !rest.is_empty() {
+        let Some(chunk) = chunks.next() else {
+            return false;
+        };
+        let chunk = chunk.as_slice();
+        let len = chunk.len().min(rest.len());
+        if chunk[..len] != rest[..len] {
+            return false;
+        }
+        rest = &rest[len..];
+    }
+    true
+}
+
+/// Returns `true` if `tail` ends the needle and the chunks, iterated from the back, end with the rest of it.
+fn preceded_by<'a: 'b, 'b>(
+    tail: &[u8],
+    mut chunks: impl Iterator<Item = &'b ByteData<'a>>,
+    needle: &[u8],
+) -> bool {
+    let Some(mut rest) = needle.strip_suffix(tail) else {
+        return false;
+    };
+    while !rest.is_empty() {
+        let Some(chunk) = chunks.next() else {
+            return false;
+        };
+        let chunk = chunk.as_slice();
+        let len = chunk.len().min(rest.len());
+        if chunk[chunk.len() - len..] != rest[rest.len() - len..] {
+            return false;
+        }
+        rest = &rest[..rest.len() - len];
+    }
+    true
+}
+
 /// A queue of byte chunks.
 #[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
 #[derive(Clone)]
@@ -290,6 +336,9 @@ impl<'a> ByteQueue<'a> {
     }
 
     /// Check if the queue contains a certain byte sequence and return its starting position.
+    ///
+    /// The search starts at `start_offset` bytes into chunk `start_chunk`, which is `start_byte` bytes into the queue.
+    /// Returns the position of the match in bytes from the start of the queue, the chunk it starts in, and the offset within that chunk.
     #[inline]
     pub(crate) fn find_slice_pos(
         &self,
@@ -299,7 +348,7 @@ impl<'a> ByteQueue<'a> {
         start_byte: usize,
     ) -> Option<(usize, usize, usize)> {
         if data.is_empty() {
-            return Some((0, 0, 0));
+            return Some((start_byte, start_chunk, start_offset));
         }
         self.find_slice_pos_nonempty(data, start_chunk, start_offset, start_byte)
     }
@@ -310,89 +359,288 @@ impl<'a> ByteQueue<'a> {
         data: &[u8],
         mut start_chunk: usize,
         mut start_offset: usize,
-        mut start_byte: usize,
+        start_byte: usize,
     ) -> Option<(usize, usize, usize)> {
         debug_assert!(!data.is_empty(), "data is empty");
-        // the first byte in the sequence to match possible sequences
-        let first_byte = data[0];
+        if data.len() > self.remain.saturating_sub(start_byte) {
+            return None;
+        }
+        let finder = (data.len() > 1).then(|| crate::search::TwoWay::new(data));
+        // the position of the start of the current chunk in the queue
+        let mut chunk_byte = start_byte - start_offset;
+        let mut chunks = self.chunks().skip(start_chunk);
 
-        'outer: loop {
-            let mut chunks = self.chunks().skip(start_chunk);
-            let mut sect = chunks.next()?;
+        loop {
+            let sect = chunks.next()?.as_slice();
             let len = sect.len();
             if start_offset >= len {
                 start_offset -= len;
                 start_chunk += 1;
+                chunk_byte += len;
                 continue;
             }
-            let marked_byte = loop {
-                let sl = &sect.as_slice()[start_offset..];
-                let Some(av) = sl.iter().position(|x| *x == first_byte) else {
-                    sect = match chunks.next() {
-                        Some(vv) => vv,
-                        _ => return None,
-                    };
-                    start_offset = 0;
-                    start_chunk += 1;
-                    continue;
-                };
-                start_offset += av;
-                start_byte += av;
-                break start_byte;
-            };
-            let marked_chunk = start_chunk;
-            let marked_offset = start_offset;
-            let mut found_in = false;
-            let mut skipped_bytes = 0;
-            let data = data.iter().skip(1);
-            let mut sl = sect.as_slice()[(start_offset + 1)..].iter();
-            for av in data {
-                let bv = loop {
-                    let nxt = sl.next();
-                    let Some(bv) = nxt else {
-                        sect = match chunks.next() {
-                            Some(vv) => vv,
-                            _ => return None,
-                        };
-                        sl = sect.as_slice().iter();
-                        continue;
-                    };
-                    break *bv;
-                };
-                if !found_in && bv == first_byte {
-                    found_in = true;
-                    start_byte += skipped_bytes;
-                    start_offset += skipped_bytes;
-                    start_offset -= 1;
-                }
-                if bv == *av {
-                    skipped_bytes += 1;
-                    continue;
+
+            // matches within the current chunk
+            let rest = &sect[start_offset..];
+            let found = finder.as_ref().map_or_else(
+                || rest.iter().position(|&byte| byte == data[0]),
+                |finder| finder.find(rest),
+            );
+            if let Some(av) = found {
+                let offset = start_offset + av;
+                return Some((chunk_byte + offset, start_chunk, offset));
+            }
+
+            // matches that start in the current chunk and continue into the following chunks
+            let tail_start = (len + 1).saturating_sub(data.len()).max(start_offset);
+            for offset in tail_start..len {
+                if continues_with(&sect[offset..], chunks.clone(), data) {
+                    return Some((chunk_byte + offset, start_chunk, offset));
                 }
-                if !found_in {
-                    start_byte += skipped_bytes;
-                    start_offset += skipped_bytes;
+            }
+
+            start_offset = 0;
+            start_chunk += 1;
+            chunk_byte += len;
+        }
+    }
+
+    /// Finds the last occurrence of a byte sequence which ends at or before `end`.
+    ///
+    /// The chunks are searched from the back, so the search stops at the chunk holding the end of the last match.
+    fn rfind_slice_before(&self, data: &[u8], end: usize) -> Option<usize> {
+        if data.len() > end {
+            return None;
+        }
+        if data.is_empty() {
+            return Some(end);
+        }
+        let finder = (data.len() > 1).then(|| crate::search::TwoWay::new_reverse(data));
+        // the position of the end of the current chunk in the queue
+        let mut chunk_end = self.remain;
+        let mut chunks = self.queue.iter_rev();
+
+        while let Some(chunk) = chunks.next() {
+            let full = chunk.as_slice();
+            let chunk_byte = chunk_end - full.len();
+            chunk_end = chunk_byte;
+            if chunk_byte >= end {
+                continue;
+            }
+            let sect = &full[..full.len().min(end - chunk_byte)];
+
+            // matches within the current chunk
+            let found = finder.as_ref().map_or_else(
+                || sect.iter().rposition(|&byte| byte == data[0]),
+                |finder| finder.find(sect),
+            );
+            if let Some(offset) = found {
+                return Some(chunk_byte + offset);
+            }
+
+            // matches that end in the current chunk and start in the previous chunks, latest first
+            let tail_end = sect.len().min(data.len() - 1);
+            let spanning = (1..=tail_end)
+                .rev()
+                .find(|&len| preceded_by(&sect[..len], chunks.clone(), data));
+            if let Some(len) = spanning {
+                return Some(chunk_byte + len - data.len());
+            }
+        }
+        None
+    }
+
+    /// Finds the first match of a searcher, starting `start_offset` bytes into chunk `start_chunk`, which is `start_byte` bytes into the queue.
+    ///
+    /// Returns the position of the match in bytes from the start of the queue, the chunk it starts in, the offset within that chunk, and its length.
+    /// When `utf8` is set the queue must hold valid UTF-8, and only matches on char boundaries are returned.
+    pub(crate) fn find_pattern_pos<S: Searcher>(
+        &self,
+        searcher: &mut S,
+        start_chunk: usize,
+        start_offset: usize,
+        start_byte: usize,
+        utf8: bool,
+    ) -> Option<(usize, usize, usize, usize)> {
+        if let Some(needle) = searcher.needle() {
+            if utf8 && !crate::pattern::needle_utf8(needle) {
+                return None;
+            }
+            let len = needle.len();
+            return self
+                .find_slice_pos(needle, start_chunk, start_offset, start_byte)
+                .map(|(byte, chunk, offset)| (byte, chunk, offset, len));
+        }
+
+        // other searchers only match within a single chunk
+        let mut chunk_byte = start_byte - start_offset;
+        let mut offset = start_offset;
+        for (index, chunk) in self.chunks().enumerate().skip(start_chunk) {
+            let sect = chunk.as_slice();
+            if offset < sect.len() {
+                if let Some((start, end)) =
+                    crate::pattern::find_match(searcher, &sect[offset..], utf8)
+                {
+                    return Some((
+                        chunk_byte + offset + start,
+                        index,
+                        offset + start,
+                        end - start,
+                    ));
                 }
-                continue 'outer;
             }
-            return Some((marked_byte, marked_chunk, marked_offset));
+            offset = offset.saturating_sub(sect.len());
+            chunk_byte += sect.len();
+        }
+        None
+    }
+
+    /// Finds the last match of a searcher which ends at or before `end`, returning its position and length.
+    ///
+    /// When `utf8` is set the queue must hold valid UTF-8, and only matches on char boundaries are returned.
+    pub(crate) fn rfind_pattern_pos<S: Searcher>(
+        &self,
+        searcher: &mut S,
+        end: usize,
+        utf8: bool,
+    ) -> Option<(usize, usize)> {
+        if let Some(needle) = searcher.needle() {
+            if utf8 && !crate::pattern::needle_utf8(needle) {
+                return None;
+            }
+            let len = needle.len();
+            return self.rfind_slice_before(needle, end).map(|pos| (pos, len));
+        }
+
+        // other searchers only match within a single chunk
+        let mut chunk_end = self.remain;
+        for chunk in self.queue.iter_rev() {
+            let chunk_byte = chunk_end - chunk.len();
+            chunk_end = chunk_byte;
+            if chunk_byte >= end {
+                continue;
+            }
+            let sect = chunk.as_slice();
+            let sect = &sect[..sect.len().min(end - chunk_byte)];
+            if let Some((start, stop)) = crate::pattern::rfind_match(searcher, sect, utf8) {
+                return Some((chunk_byte + start, stop - start));
+            }
+        }
+        None
+    }
+
+    /// Returns the length of the match of a searcher at the start of the queue.
+    pub(crate) fn prefix_pattern_len<S: Searcher>(
+        &self,
+        searcher: &mut S,
+        utf8: bool,
+    ) -> Option<usize> {
+        if let Some(needle) = searcher.needle() {
+            if utf8 && !crate::pattern::needle_utf8(needle) {
+                return None;
+            }
+            return self.starts_with(needle).then_some(needle.len());
         }
+        crate::pattern::prefix_match(searcher, self.front()?.as_slice(), utf8)
+    }
+
+    /// Returns the length of the match of a searcher at the end of the queue.
+    pub(crate) fn suffix_pattern_len<S: Searcher>(
+        &self,
+        searcher: &mut S,
+        utf8: bool,
+    ) -> Option<usize> {
+        if let Some(needle) = searcher.needle() {
+            if utf8 && !crate::pattern::needle_utf8(needle) {
+                return None;
+            }
+            return self.ends_with(needle).then_some(needle.len());
+        }
+        crate::pattern::suffix_match(searcher, self.back()?.as_slice(), utf8)
+    }
+
+    /// Returns the position of the first match of the pattern.
+    ///
+    /// Only patterns matching a single byte sequence, such as `&[u8]` or `char`, can match across chunks.
+    #[inline]
+    pub fn find<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        self.find_pattern_pos(&mut searcher, 0, 0, 0, false)
+            .map(|(pos, ..)| pos)
+    }
+
+    /// Returns the position of the last match of the pattern.
+    ///
+    /// Only patterns matching a single byte sequence, such as `&[u8]` or `char`, can match across chunks.
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        self.rfind_pattern_pos(&mut searcher, self.remain, false)
+            .map(|(pos, _)| pos)
     }
 
     /// Split the queue on a certain byte sequence.
     #[inline]
     #[must_use]
     pub const fn split_on<'b>(&'b self, needle: &'b [u8]) -> super::SplitOn<'a, 'b> {
-        super::SplitOn::new(self, needle, 0)
+        super::SplitOn::new(self, needle, usize::MAX)
     }
 
-    /// Split the queue on a certain byte sequence.
+    /// Split the queue on a certain byte sequence, returning at most `max` items. A `max` of `0` means no limit.
     #[inline]
     #[must_use]
     pub const fn splitn_on<'b>(&'b self, needle: &'b [u8], max: usize) -> super::SplitOn<'a, 'b> {
+        let max = if max == 0 { usize::MAX } else { max };
         super::SplitOn::new(self, needle, max)
     }
 
+    /// Split the queue on each match of the pattern.
+    #[inline]
+    pub fn split<P: Pattern>(&self, pattern: P) -> super::SplitOn<'a, '_, P::Searcher> {
+        super::SplitOn::new(self, pattern.into_searcher(), usize::MAX)
+    }
+
+    /// Split the queue on each match of the pattern, returning at most `max` items.
+    /// The last item contains the remaining data.
+    #[inline]
+    pub fn splitn<P: Pattern>(
+        &self,
+        max: usize,
+        pattern: P,
+    ) -> super::SplitOn<'a, '_, P::Searcher> {
+        super::SplitOn::new(self, pattern.into_searcher(), max)
+    }
+
+    /// Split the queue on each match of the pattern, starting from the end.
+    #[inline]
+    pub fn rsplit<P: Pattern>(&self, pattern: P) -> super::SplitOn<'a, '_, P::Searcher> {
+        super::SplitOn::new(self, pattern.into_searcher(), usize::MAX).reversed()
+    }
+
+    /// Split the queue after each match of the pattern, keeping the match at the end of each item.
+    #[inline]
+    pub fn split_inclusive<P: Pattern>(&self, pattern: P) -> super::SplitOn<'a, '_, P::Searcher> {
+        super::SplitOn::new(self, pattern.into_searcher(), usize::MAX).inclusive()
+    }
+
+    /// Returns the data after the pattern if it matches at the start of the queue.
+    #[inline]
+    #[must_use]
+    pub fn strip_prefix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = self.prefix_pattern_len(&mut searcher, false)?;
+        Some(self.slice(len..))
+    }
+
+    /// Returns the data before the pattern if it matches at the end of the queue.
+    #[inline]
+    #[must_use]
+    pub fn strip_suffix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = self.suffix_pattern_len(&mut searcher, false)?;
+        Some(self.slice(..self.remain - len))
+    }
+
     /// Iterates over each chunk of bytedata in the queue.
     #[inline]
     #[must_use]
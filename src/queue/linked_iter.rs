@@ -2,6 +2,7 @@ use core::mem::MaybeUninit;
 
 /// An iterator over byte chunks.
 #[allow(missing_debug_implementations)]
+#[derive(Clone)]
 pub struct LinkedIter<'a, 'b> {
     #[cfg(feature = "alloc")]
     chamber: Option<&'b crate::ByteData<'a>>,
@@ -200,6 +201,83 @@ impl<'a: 'b, 'b> ExactSizeIterator for LinkedIter<'a, 'b> {
 #[allow(single_use_lifetimes)]
 impl<'a: 'b, 'b> core::iter::FusedIterator for LinkedIter<'a, 'b> {}
 
+/// An iterator over byte chunks from the back of the queue to the front.
+#[derive(Clone)]
+pub(super) struct LinkedRevIter<'a, 'b> {
+    #[cfg(feature = "alloc")]
+    chamber: Option<&'b crate::ByteData<'a>>,
+    #[cfg(feature = "alloc")]
+    node: Option<&'b super::linked_node_leaf::LinkedNodeLeaf<'a>>,
+    #[cfg(not(feature = "alloc"))]
+    data: &'b super::linked_node_data::LinkedNodeData<'a>,
+    /// The number of items left in the current node.
+    offset: usize,
+}
+
+impl<'a: 'b, 'b> LinkedRevIter<'a, 'b> {
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(super) fn new(
+        chamber: Option<&'b crate::ByteData<'a>>,
+        node: Option<&'b super::linked_node_leaf::LinkedNodeLeaf<'a>>,
+    ) -> Self {
+        Self {
+            chamber,
+            node,
+            offset: node.map_or(0, |last| last.data.len as usize),
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[inline]
+    pub(super) const fn new(data: &'b super::linked_node_data::LinkedNodeData<'a>) -> Self {
+        Self {
+            data,
+            offset: data.len as usize,
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Iterator for LinkedRevIter<'a, 'b> {
+    type Item = &'b crate::ByteData<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            #[cfg(feature = "alloc")]
+            let Some(node) = self.node
+            else {
+                return self.chamber.take();
+            };
+            #[cfg(feature = "alloc")]
+            let data = &node.data;
+            #[cfg(not(feature = "alloc"))]
+            let data = self.data;
+
+            if self.offset != 0 {
+                self.offset -= 1;
+                let ret: &MaybeUninit<crate::ByteData<'a>> =
+                    &data.data[(data.beg as usize + self.offset) % data.data.len()];
+                // SAFETY: the beg and len indicate a valid slot
+                return Some(unsafe { ret.assume_init_ref() });
+            }
+
+            #[cfg(not(feature = "alloc"))]
+            return None;
+
+            #[cfg(feature = "alloc")]
+            {
+                // SAFETY: the pointer is either null or points to a valid node
+                self.node = unsafe { node.prev.as_ref() };
+                self.offset = self.node.map_or(0, |prev| prev.data.len as usize);
+            }
+        }
+    }
+}
+
+#[allow(single_use_lifetimes)]
+impl<'a: 'b, 'b> core::iter::FusedIterator for LinkedRevIter<'a, 'b> {}
+
 /// An iterator over string chunks.
 #[repr(transparent)]
 #[allow(missing_debug_implementations)]
@@ -80,6 +80,11 @@ impl<'a> LinkedRoot<'a> {
     pub(super) fn iter(&'_ self) -> super::LinkedIter<'a, '_> {
         LinkedIter::new(&self.data)
     }
+
+    #[inline]
+    pub(super) const fn iter_rev(&'_ self) -> super::linked_iter::LinkedRevIter<'a, '_> {
+        super::linked_iter::LinkedRevIter::new(&self.data)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -251,6 +256,18 @@ impl<'a> LinkedRoot<'a> {
         LinkedIter::new(chamber, unsafe { self.first.as_ref() })
     }
 
+    /// Returns an iterator over the chunks from the back to the front.
+    #[inline]
+    pub(super) fn iter_rev(&self) -> super::linked_iter::LinkedRevIter<'a, '_> {
+        let chamber = if self.chamber.is_empty() {
+            None
+        } else {
+            Some(&self.chamber)
+        };
+        // SAFETY: if the pointer is non-null it points to a valid `LinkedNodeLeaf`.
+        super::linked_iter::LinkedRevIter::new(chamber, unsafe { self.last.as_ref() })
+    }
+
     pub(super) fn append(&mut self, mut other: Self) {
         if other.count == 0 {
             return;
@@ -1,128 +1,159 @@
-/// An iterator over chunks of a `ByteQueue` separated by a byte sequence.
+use crate::pattern::Searcher;
+
+/// An iterator over chunks of a `ByteQueue` separated by the matches of a pattern.
 #[allow(missing_debug_implementations)]
-pub struct SplitOn<'a, 'b> {
+pub struct SplitOn<'a, 'b, S = &'b [u8]> {
     queue: &'b super::ByteQueue<'a>,
-    needle: &'b [u8],
+    searcher: S,
     start_chunk: usize,
     start_offset: usize,
     start_byte: usize,
-    max: usize,
-    done: bool,
+    end_byte: usize,
+    /// The number of items that may still be returned.
+    remaining: usize,
+    /// Split from the end of the queue.
+    reverse: bool,
+    /// Keep the match at the end of each item.
+    inclusive: bool,
+    /// The queue is valid UTF-8 and matches must be on char boundaries.
+    utf8: bool,
 }
 
-impl<'a, 'b> SplitOn<'a, 'b> {
-    pub(super) const fn new(queue: &'b super::ByteQueue<'a>, needle: &'b [u8], max: usize) -> Self {
-        let done = queue.is_empty();
+impl<'a, 'b, S> SplitOn<'a, 'b, S> {
+    pub(super) const fn new(queue: &'b super::ByteQueue<'a>, searcher: S, max: usize) -> Self {
+        let remaining = if queue.is_empty() { 0 } else { max };
         Self {
             queue,
-            needle,
+            searcher,
             start_chunk: 0,
             start_offset: 0,
             start_byte: 0,
-            max,
-            done,
+            end_byte: queue.len(),
+            remaining,
+            reverse: false,
+            inclusive: false,
+            utf8: false,
         }
     }
+
+    pub(super) const fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    pub(super) const fn inclusive(mut self) -> Self {
+        self.inclusive = true;
+        self
+    }
+
+    const fn utf8(mut self) -> Self {
+        self.utf8 = true;
+        self
+    }
+
+    fn take_rest(&mut self) -> super::ByteQueue<'a> {
+        self.remaining = 0;
+        self.queue.slice(self.start_byte..self.end_byte)
+    }
 }
 
-impl<'a> Iterator for SplitOn<'a, '_> {
+impl<'a, S: Searcher> Iterator for SplitOn<'a, '_, S> {
     type Item = super::ByteQueue<'a>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        fn inner<'a>(this: &mut SplitOn<'a, '_>) -> super::ByteQueue<'a> {
-            let val = this.queue.find_slice_pos(
-                this.needle,
-                this.start_chunk,
-                this.start_offset,
-                this.start_byte,
-            );
-            if let Some((chunk, offset, byte)) = val {
-                let mut chunks = this.queue.chunks().skip(this.start_chunk);
-                let new_q = if this.start_chunk == chunk {
-                    #[allow(clippy::unwrap_used)]
-                    let chunk_next = chunks.next().unwrap();
-                    super::ByteQueue::with_item(chunk_next.sliced(this.start_offset..offset))
-                } else {
-                    let mut new_q = super::ByteQueue::new();
-                    #[allow(clippy::unwrap_used)]
-                    let chunk_next = chunks.next().unwrap();
-                    new_q.push_back(chunk_next.sliced(this.start_offset..));
-                    for chunkdata in chunks.take(chunk - this.start_chunk - 1) {
-                        new_q.push_back(chunkdata.clone());
-                    }
-                    if offset != 0 {
-                        chunks = this.queue.chunks().skip(chunk);
-                        #[allow(clippy::unwrap_used)]
-                        let chunk_nexter = chunks.next().unwrap();
-                        new_q.push_back(chunk_nexter.sliced(..offset));
-                    }
-                    new_q
-                };
-                this.start_chunk = chunk;
-                this.start_offset = offset;
-                this.start_byte = byte;
-                if this.max != 0 {
-                    this.max -= 1;
-                }
-                new_q
-            } else {
-                this.done = true;
-                this.queue.slice(this.start_byte..)
-            }
-        }
-
-        if self.done {
+        if self.remaining == 0 {
             return None;
         }
-        if self.start_byte == self.queue.len() {
-            self.done = true;
-            return Some(super::ByteQueue::new());
+        if self.remaining == 1 {
+            return Some(self.take_rest());
         }
-        if self.max == 1 {
-            self.done = true;
-            return Some(self.queue.slice(self.start_byte..));
+
+        if self.reverse {
+            let found = self
+                .queue
+                .rfind_pattern_pos(&mut self.searcher, self.end_byte, self.utf8)
+                .filter(|&(_, len)| len != 0);
+            let Some((byte, len)) = found else {
+                return Some(self.take_rest());
+            };
+            self.remaining -= 1;
+            let item = self.queue.slice(byte + len..self.end_byte);
+            self.end_byte = byte;
+            return Some(item);
+        }
+
+        let found = self
+            .queue
+            .find_pattern_pos(
+                &mut self.searcher,
+                self.start_chunk,
+                self.start_offset,
+                self.start_byte,
+                self.utf8,
+            )
+            .filter(|&(.., len)| len != 0);
+        let Some((byte, chunk, offset, len)) = found else {
+            return Some(self.take_rest());
+        };
+        self.remaining -= 1;
+        let item_end = if self.inclusive { byte + len } else { byte };
+        let item = self.queue.slice(self.start_byte..item_end);
+        // continue searching after the match
+        self.start_chunk = chunk;
+        self.start_offset = offset + len;
+        self.start_byte = byte + len;
+        if self.inclusive && self.start_byte == self.end_byte {
+            self.remaining = 0;
         }
-        Some(inner(self))
+        Some(item)
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        if self.done {
+        if self.remaining == 0 {
             (0, Some(0))
         } else {
-            let max = if self.max == 0 { None } else { Some(self.max) };
-            (1, max)
+            let max = self.end_byte - self.start_byte + 1;
+            (1, Some(self.remaining.min(max)))
         }
     }
 }
 
-/// An iterator over chunks of a `StringQueue` separated by a str sequence.
+/// An iterator over chunks of a `StringQueue` separated by the matches of a pattern.
 #[repr(transparent)]
 #[allow(missing_debug_implementations)]
-pub struct SplitOnStr<'a, 'b> {
-    inner: SplitOn<'a, 'b>,
+pub struct SplitOnStr<'a, 'b, S = &'b str> {
+    inner: SplitOn<'a, 'b, S>,
 }
 
-impl<'a, 'b> SplitOnStr<'a, 'b> {
-    pub(super) const fn new(
-        queue: &'b super::StringQueue<'a>,
-        needle: &'b str,
-        max: usize,
-    ) -> Self {
+impl<'a, 'b, S> SplitOnStr<'a, 'b, S> {
+    pub(super) const fn new(queue: &'b super::StringQueue<'a>, searcher: S, max: usize) -> Self {
+        Self {
+            inner: SplitOn::new(queue.as_bytequeue(), searcher, max).utf8(),
+        }
+    }
+
+    pub(super) fn reversed(self) -> Self {
+        Self {
+            inner: self.inner.reversed(),
+        }
+    }
+
+    pub(super) fn inclusive(self) -> Self {
         Self {
-            inner: SplitOn::new(queue.as_bytequeue(), needle.as_bytes(), max),
+            inner: self.inner.inclusive(),
         }
     }
 }
 
-impl<'a> Iterator for SplitOnStr<'a, '_> {
+impl<'a, S: Searcher> Iterator for SplitOnStr<'a, '_, S> {
     type Item = super::StringQueue<'a>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         let n = self.inner.next()?;
-        // SAFETY: as the needle is a valid UTF-8 sequence, the split chunks are also valid UTF-8
+        // SAFETY: the queue is only split on char boundaries, so the split chunks are also valid UTF-8
         Some(unsafe { super::StringQueue::from_bytequeue(n) })
     }
 
@@ -1,5 +1,5 @@
 use super::ByteQueue;
-use crate::StringData;
+use crate::{Pattern, StringData};
 
 /// A queue of strings.
 #[repr(transparent)]
@@ -278,13 +278,14 @@ impl<'a> StringQueue<'a> {
     #[inline]
     #[must_use]
     pub const fn split_on<'b>(&'b self, needle: &'b str) -> super::SplitOnStr<'a, 'b> {
-        super::SplitOnStr::new(self, needle, 0)
+        super::SplitOnStr::new(self, needle, usize::MAX)
     }
 
-    /// Split the queue on a certain str sequence.
+    /// Split the queue on a certain str sequence, returning at most `max` items. A `max` of `0` means no limit.
     #[inline]
     #[must_use]
     pub const fn splitn_on<'b>(&'b self, needle: &'b str, max: usize) -> super::SplitOnStr<'a, 'b> {
+        let max = if max == 0 { usize::MAX } else { max };
         super::SplitOnStr::new(self, needle, max)
     }
 
@@ -292,10 +293,10 @@ impl<'a> StringQueue<'a> {
     #[inline]
     #[must_use]
     pub const fn split_on_bytes<'b>(&'b self, needle: &'b [u8]) -> super::SplitOn<'a, 'b> {
-        super::SplitOn::new(self.as_bytequeue(), needle, 0)
+        self.queue.split_on(needle)
     }
 
-    /// Split the queue on a certain byte sequence.
+    /// Split the queue on a certain byte sequence, returning at most `max` items. A `max` of `0` means no limit.
     #[inline]
     #[must_use]
     pub const fn splitn_on_bytes<'b>(
@@ -303,7 +304,79 @@ impl<'a> StringQueue<'a> {
         needle: &'b [u8],
         max: usize,
     ) -> super::SplitOn<'a, 'b> {
-        super::SplitOn::new(self.as_bytequeue(), needle, max)
+        self.queue.splitn_on(needle, max)
+    }
+
+    /// Returns the byte position of the first match of the pattern.
+    #[inline]
+    pub fn find<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        self.queue
+            .find_pattern_pos(&mut searcher, 0, 0, 0, true)
+            .map(|(pos, ..)| pos)
+    }
+
+    /// Returns the byte position of the last match of the pattern.
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        self.queue
+            .rfind_pattern_pos(&mut searcher, self.len(), true)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Split the queue on each match of the pattern.
+    #[inline]
+    pub fn split<P: Pattern>(&self, pattern: P) -> super::SplitOnStr<'a, '_, P::Searcher> {
+        super::SplitOnStr::new(self, pattern.into_searcher(), usize::MAX)
+    }
+
+    /// Split the queue on each match of the pattern, returning at most `max` items.
+    /// The last item contains the remaining data.
+    #[inline]
+    pub fn splitn<P: Pattern>(
+        &self,
+        max: usize,
+        pattern: P,
+    ) -> super::SplitOnStr<'a, '_, P::Searcher> {
+        super::SplitOnStr::new(self, pattern.into_searcher(), max)
+    }
+
+    /// Split the queue on each match of the pattern, starting from the end.
+    #[inline]
+    pub fn rsplit<P: Pattern>(&self, pattern: P) -> super::SplitOnStr<'a, '_, P::Searcher> {
+        super::SplitOnStr::new(self, pattern.into_searcher(), usize::MAX).reversed()
+    }
+
+    /// Split the queue after each match of the pattern, keeping the match at the end of each item.
+    #[inline]
+    pub fn split_inclusive<P: Pattern>(
+        &self,
+        pattern: P,
+    ) -> super::SplitOnStr<'a, '_, P::Searcher> {
+        super::SplitOnStr::new(self, pattern.into_searcher(), usize::MAX).inclusive()
+    }
+
+    /// Returns the string after the pattern if it matches at the start of the queue.
+    #[inline]
+    #[must_use]
+    pub fn strip_prefix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = self.queue.prefix_pattern_len(&mut searcher, true)?;
+        Some(Self {
+            queue: self.queue.slice(len..),
+        })
+    }
+
+    /// Returns the string before the pattern if it matches at the end of the queue.
+    #[inline]
+    #[must_use]
+    pub fn strip_suffix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = self.queue.suffix_pattern_len(&mut searcher, true)?;
+        Some(Self {
+            queue: self.queue.slice(..self.len() - len),
+        })
     }
 
     /// Append another `StringQueue` to the end of this one.
@@ -0,0 +1,264 @@
+//! Runtime substring search used by the splitting and searching methods.
+//!
+//! The `const` helpers such as [`const_find_bytes`](crate::const_find_bytes) use a naive `O(n*m)` scan.
+//! This module implements the Two-Way algorithm, which runs in `O(n + m)` time and constant space.
+
+/// A precomputed searcher for a non-empty needle using the Two-Way algorithm.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) struct TwoWay<'b> {
+    needle: &'b [u8],
+    /// The critical position, splitting the needle into a left and right part.
+    crit_pos: usize,
+    /// The period of the needle, or a lower bound of it if `long_period` is set.
+    period: usize,
+    /// Bloom filter of the bytes in the needle.
+    byteset: u64,
+    /// Set when the needle is not periodic, in which case no memory of the previous match is kept.
+    long_period: bool,
+    /// Set when searching from the end of the haystack, in which case all indices count from the end.
+    reverse: bool,
+}
+
+/// Returns the byte at `index`, counting from the end of `arr` when `reverse` is set.
+#[inline]
+const fn byte_at(arr: &[u8], index: usize, reverse: bool) -> u8 {
+    if reverse {
+        arr[arr.len() - 1 - index]
+    } else {
+        arr[index]
+    }
+}
+
+impl<'b> TwoWay<'b> {
+    /// Prepares a searcher for the first occurrence of `needle`, which must not be empty.
+    pub(crate) fn new(needle: &'b [u8]) -> Self {
+        Self::with_direction(needle, false)
+    }
+
+    /// Prepares a searcher for the last occurrence of `needle`, which must not be empty.
+    pub(crate) fn new_reverse(needle: &'b [u8]) -> Self {
+        Self::with_direction(needle, true)
+    }
+
+    fn with_direction(needle: &'b [u8], reverse: bool) -> Self {
+        debug_assert!(!needle.is_empty(), "needle is empty");
+        let suffix_less = Self::maximal_suffix(needle, false, reverse);
+        let suffix_greater = Self::maximal_suffix(needle, true, reverse);
+        let (crit_pos, period) = if suffix_less.0 > suffix_greater.0 {
+            suffix_less
+        } else {
+            suffix_greater
+        };
+        let byteset = needle
+            .iter()
+            .fold(0_u64, |acc, &byte| acc | (1_u64 << (byte & 63)));
+
+        if period + crit_pos <= needle.len()
+            && (0..crit_pos)
+                .all(|i| byte_at(needle, i, reverse) == byte_at(needle, period + i, reverse))
+        {
+            Self {
+                needle,
+                crit_pos,
+                period,
+                byteset,
+                long_period: false,
+                reverse,
+            }
+        } else {
+            Self {
+                needle,
+                crit_pos,
+                period: crit_pos.max(needle.len() - crit_pos) + 1,
+                byteset,
+                long_period: true,
+                reverse,
+            }
+        }
+    }
+
+    /// Computes the start and period of the maximal suffix of `arr` for the given byte ordering.
+    const fn maximal_suffix(arr: &[u8], order_greater: bool, reverse: bool) -> (usize, usize) {
+        let mut left = 0;
+        let mut right = 1;
+        let mut offset = 0;
+        let mut period = 1;
+
+        while right + offset < arr.len() {
+            let av = byte_at(arr, right + offset, reverse);
+            let bv = byte_at(arr, left + offset, reverse);
+            if (av < bv && !order_greater) || (av > bv && order_greater) {
+                // the suffix is smaller, the period is the entire prefix so far
+                right += offset + 1;
+                offset = 0;
+                period = right - left;
+            } else if av == bv {
+                // advance through the repetition of the current period
+                if offset + 1 == period {
+                    right += offset + 1;
+                    offset = 0;
+                } else {
+                    offset += 1;
+                }
+            } else {
+                // the suffix is larger, start over from the current location
+                left = right;
+                right += 1;
+                offset = 0;
+                period = 1;
+            }
+        }
+        (left, period)
+    }
+
+    /// Finds the first position of the needle in `haystack`, or the last position for a reverse searcher.
+    pub(crate) fn find(&self, haystack: &[u8]) -> Option<usize> {
+        let needle = self.needle;
+        let needle_last = needle.len() - 1;
+        let reverse = self.reverse;
+        let mut pos = 0;
+        let mut memory = 0;
+
+        'search: while pos + needle_last < haystack.len() {
+            let tail = byte_at(haystack, pos + needle_last, reverse);
+            if (self.byteset >> (tail & 63)) & 1 == 0 {
+                // the last byte of the window is not in the needle, so skip the whole window
+                pos += needle.len();
+                memory = 0;
+                continue 'search;
+            }
+
+            // match the right part of the needle
+            let right_start = if self.long_period {
+                self.crit_pos
+            } else {
+                self.crit_pos.max(memory)
+            };
+            for i in right_start..needle.len() {
+                if byte_at(needle, i, reverse) != byte_at(haystack, pos + i, reverse) {
+                    pos += i - self.crit_pos + 1;
+                    memory = 0;
+                    continue 'search;
+                }
+            }
+
+            // match the left part of the needle
+            let left_start = if self.long_period { 0 } else { memory };
+            for i in (left_start..self.crit_pos).rev() {
+                if byte_at(needle, i, reverse) != byte_at(haystack, pos + i, reverse) {
+                    pos += self.period;
+                    if !self.long_period {
+                        memory = needle.len() - self.period;
+                    }
+                    continue 'search;
+                }
+            }
+            if reverse {
+                return Some(haystack.len() - pos - needle.len());
+            }
+            return Some(pos);
+        }
+        None
+    }
+}
+
+/// Finds the first position of `needle` in `haystack`. An empty needle is found at position `0`.
+///
+/// Time complexity `O(n + m)`.
+#[inline]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    match needle.len() {
+        0 => Some(0),
+        1 => haystack.iter().position(|&byte| byte == needle[0]),
+        len if len > haystack.len() => None,
+        _ => TwoWay::new(needle).find(haystack),
+    }
+}
+
+/// Finds the last position of `needle` in `haystack`. An empty needle is found at the end of the haystack.
+///
+/// Time complexity `O(n + m)`.
+#[inline]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn rfind_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    match needle.len() {
+        0 => Some(haystack.len()),
+        1 => haystack.iter().rposition(|&byte| byte == needle[0]),
+        len if len > haystack.len() => None,
+        _ => TwoWay::new_reverse(needle).find(haystack),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_bytes, rfind_bytes};
+
+    #[test]
+    fn test_find_bytes() {
+        assert_eq!(find_bytes(b"hello world", b""), Some(0));
+        assert_eq!(find_bytes(b"hello world", b"o"), Some(4));
+        assert_eq!(find_bytes(b"hello world", b"world"), Some(6));
+        assert_eq!(find_bytes(b"hello world", b"worlds"), None);
+        assert_eq!(
+            find_bytes(b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody", b"\r\n\r\n"),
+            Some(23)
+        );
+        assert_eq!(find_bytes(b"aaaaaaaaab", b"aaab"), Some(6));
+        assert_eq!(find_bytes(b"abababababc", b"ababc"), Some(6));
+        assert_eq!(
+            find_bytes(b"--boundary--boundar--boundary", b"--boundary"),
+            Some(0)
+        );
+        assert_eq!(
+            find_bytes(b"xx--boundar--boundary", b"--boundary"),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_rfind_bytes() {
+        assert_eq!(rfind_bytes(b"hello world", b""), Some(11));
+        assert_eq!(rfind_bytes(b"hello world", b"o"), Some(7));
+        assert_eq!(rfind_bytes(b"hello world", b"hello"), Some(0));
+        assert_eq!(rfind_bytes(b"hello world", b"hellos"), None);
+        assert_eq!(rfind_bytes(b"abababababc", b"abab"), Some(6));
+        assert_eq!(
+            rfind_bytes(b"--boundary--boundar--boundary", b"--boundary"),
+            Some(19)
+        );
+    }
+
+    #[test]
+    fn test_find_bytes_matches_naive() {
+        const ALPHABET: &[u8] = b"ab\xffc";
+        let mut haystack = [0_u8; 40];
+        let mut state = 0x2545_f491_u32;
+        for _ in 0..2000_u32 {
+            for byte in &mut haystack {
+                state ^= state << 13_u32;
+                state ^= state >> 17_u32;
+                state ^= state << 5_u32;
+                *byte = ALPHABET[(state % 3) as usize];
+            }
+            let start = (state % 30) as usize;
+            let len = ((state >> 8_u32) % 6) as usize + 1;
+            let mut needle = [0_u8; 8];
+            needle[..len].copy_from_slice(&haystack[start..start + len]);
+            needle[(state >> 16_u32) as usize % len] = ALPHABET[((state >> 20_u32) % 4) as usize];
+            let needle = &needle[..len];
+            assert_eq!(
+                find_bytes(&haystack, needle),
+                crate::const_find_bytes(&haystack, needle),
+                "haystack: {haystack:?}, needle: {needle:?}"
+            );
+            assert_eq!(
+                rfind_bytes(&haystack, needle),
+                (0..=haystack.len() - len)
+                    .rev()
+                    .find(|&pos| &haystack[pos..pos + len] == needle),
+                "haystack: {haystack:?}, needle: {needle:?}"
+            );
+        }
+    }
+}
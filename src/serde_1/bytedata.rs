@@ -87,7 +87,7 @@ impl<'de> serde::de::DeserializeSeed<'de> for StaticByteDataVisitor {
         }
         #[cfg(not(feature = "alloc"))]
         {
-            deserializer.deserialize_byte(self)
+            deserializer.deserialize_bytes(self)
         }
     }
 }
@@ -126,7 +126,7 @@ impl serde::de::Visitor<'_> for StaticByteDataVisitor {
     }
 }
 
-struct ByteDataVisitor;
+pub(super) struct ByteDataVisitor;
 
 impl<'de> serde::de::Visitor<'de> for ByteDataVisitor {
     type Value = ByteData<'de>;
@@ -193,7 +193,7 @@ impl<'de: 'a, 'a> serde::de::Deserialize<'de> for ByteData<'a> {
         }
         #[cfg(not(feature = "alloc"))]
         {
-            deserializer.deserialize_byte(ByteDataVisitor)
+            deserializer.deserialize_bytes(ByteDataVisitor)
         }
     }
 }
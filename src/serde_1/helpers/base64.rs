@@ -0,0 +1,32 @@
+//! Represents bytes as standard base64 with padding in human-readable formats, and as a byte array otherwise.
+//!
+//! Use with `#[serde(with = "bytedata::serde::base64")]` on a field of a type listed in [`BytesField`](super::BytesField).
+
+use serde_1 as serde;
+
+use super::{BytesField, Repr};
+
+/// Serializes a value, see the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns the errors of the serializer.
+#[inline]
+pub fn serialize<T: BytesField, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_as(Repr::Base64, serializer)
+}
+
+/// Deserializes a value, see the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns an error if the input is not in the expected representation.
+#[inline]
+pub fn deserialize<'de, T: BytesField, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_as(Repr::Base64, deserializer)
+}
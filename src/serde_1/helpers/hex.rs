@@ -0,0 +1,33 @@
+//! Represents bytes as a lower case hex string in human-readable formats, and as a byte array otherwise.
+//!
+//! Upper case hex digits are accepted when deserializing.
+//! Use with `#[serde(with = "bytedata::serde::hex")]` on a field of a type listed in [`BytesField`](super::BytesField).
+
+use serde_1 as serde;
+
+use super::{BytesField, Repr};
+
+/// Serializes a value, see the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns the errors of the serializer.
+#[inline]
+pub fn serialize<T: BytesField, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_as(Repr::Hex, serializer)
+}
+
+/// Deserializes a value, see the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns an error if the input is not in the expected representation.
+#[inline]
+pub fn deserialize<'de, T: BytesField, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_as(Repr::Hex, deserializer)
+}
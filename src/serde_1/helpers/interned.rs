@@ -0,0 +1,286 @@
+use core::marker::PhantomData;
+
+use serde_1::{self as serde, de::DeserializeSeed};
+
+use crate::interning::ByteInterning;
+use crate::{ByteData, StringData};
+
+/// A [`DeserializeSeed`] that interns the deserialized strings or bytes.
+///
+/// [`StringData`] and [`ByteData`] values are passed through any [`ByteInterning`] implementation,
+/// so repeated values share a single allocation.
+/// When the interning declines a value, for example because it is too long,
+/// it is deserialized into a chunk or a shared buffer like [`StringData::deserialize_static`] does.
+/// Either way the result does not borrow from the input.
+///
+/// The seed also deserializes `Option` and `Vec` of these with every element interned,
+/// as well as `BTreeMap` and `HashMap` with interned keys. The map values are deserialized normally.
+///
+/// ```
+/// use ::bytedata::{interning::StaticInterning, serde::Interned, StringData};
+/// use serde_1::de::{value, DeserializeSeed, IntoDeserializer};
+///
+/// let interning = StaticInterning::new(64);
+/// let input = vec!["content-type: text/plain", "content-type: text/plain"];
+/// let deserializer: value::SeqDeserializer<_, value::Error> = input.into_deserializer();
+/// let values = Interned::<Vec<StringData<'static>>>::new(&interning)
+///     .deserialize(deserializer)
+///     .unwrap();
+/// assert_eq!(values[0], "content-type: text/plain");
+/// assert_eq!(values[0].as_bytes().as_ptr(), values[1].as_bytes().as_ptr());
+/// ```
+pub struct Interned<'i, 'a, T> {
+    interning: &'i dyn ByteInterning<'a>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'i, 'a, T> Interned<'i, 'a, T> {
+    /// Creates a seed that interns values with `interning`.
+    #[inline]
+    #[must_use]
+    pub const fn new(interning: &'i dyn ByteInterning<'a>) -> Self {
+        Self {
+            interning,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a seed for another type that uses the same interning.
+    const fn with<U>(self) -> Interned<'i, 'a, U> {
+        Interned::new(self.interning)
+    }
+}
+
+impl<T> Clone for Interned<'_, '_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Interned<'_, '_, T> {}
+
+impl<T> core::fmt::Debug for Interned<'_, '_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Interned").finish_non_exhaustive()
+    }
+}
+
+impl<'a> Interned<'_, 'a, StringData<'a>> {
+    /// Interns a string, or makes it shared if the interning declines it.
+    fn intern(self, value: StringData<'_>) -> StringData<'a> {
+        self.interning
+            .intern_str(value)
+            .unwrap_or_else(StringData::into_shared)
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for Interned<'_, 'a, StringData<'a>> {
+    type Value = StringData<'a>;
+
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_string(self)
+    }
+}
+
+impl<'a> serde::de::Visitor<'_> for Interned<'_, 'a, StringData<'a>> {
+    type Value = StringData<'a>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.intern(StringData::from_borrowed(v)))
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_string<E: serde::de::Error>(self, v: alloc::string::String) -> Result<Self::Value, E> {
+        Ok(self.intern(StringData::from_owned(v)))
+    }
+}
+
+impl<'a> Interned<'_, 'a, ByteData<'a>> {
+    /// Interns bytes, or makes them shared if the interning declines them.
+    fn intern(self, value: ByteData<'_>) -> ByteData<'a> {
+        self.interning
+            .intern(value)
+            .unwrap_or_else(ByteData::into_shared)
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for Interned<'_, 'a, ByteData<'a>> {
+    type Value = ByteData<'a>;
+
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_byte_buf(self)
+    }
+}
+
+impl<'a> serde::de::Visitor<'_> for Interned<'_, 'a, ByteData<'a>> {
+    type Value = ByteData<'a>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(self.intern(ByteData::from_borrowed(v)))
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_byte_buf<E: serde::de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+        Ok(self.intern(ByteData::from_owned(v)))
+    }
+}
+
+impl<'de, 'i, 'a, T> DeserializeSeed<'de> for Interned<'i, 'a, Option<T>>
+where
+    Interned<'i, 'a, T>: DeserializeSeed<'de, Value = T>,
+{
+    type Value = Option<T>;
+
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, 'i, 'a, T> serde::de::Visitor<'de> for Interned<'i, 'a, Option<T>>
+where
+    Interned<'i, 'a, T>: DeserializeSeed<'de, Value = T>,
+{
+    type Value = Option<T>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("an optional value")
+    }
+
+    #[inline]
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_some<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.with::<T>().deserialize(deserializer).map(Some)
+    }
+}
+
+impl<'de, 'i, 'a, T> DeserializeSeed<'de> for Interned<'i, 'a, alloc::vec::Vec<T>>
+where
+    Interned<'i, 'a, T>: DeserializeSeed<'de, Value = T>,
+{
+    type Value = alloc::vec::Vec<T>;
+
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'i, 'a, T> serde::de::Visitor<'de> for Interned<'i, 'a, alloc::vec::Vec<T>>
+where
+    Interned<'i, 'a, T>: DeserializeSeed<'de, Value = T>,
+{
+    type Value = alloc::vec::Vec<T>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    #[inline]
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut vec = alloc::vec::Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1024));
+        while let Some(value) = seq.next_element_seed(self.with::<T>())? {
+            vec.push(value);
+        }
+        Ok(vec)
+    }
+}
+
+/// Implements the seed for a map type, interning the keys.
+macro_rules! impl_map {
+    ($map:ty, [$($bounds:tt)*]) => {
+        impl<'de, 'i, 'a, K, V, $($bounds)*> DeserializeSeed<'de> for Interned<'i, 'a, $map>
+        where
+            Interned<'i, 'a, K>: DeserializeSeed<'de, Value = K>,
+            V: serde::Deserialize<'de>,
+            $map: Default + Extend<(K, V)>,
+        {
+            type Value = $map;
+
+            #[inline]
+            fn deserialize<D: serde::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_map(self)
+            }
+        }
+
+        impl<'de, 'i, 'a, K, V, $($bounds)*> serde::de::Visitor<'de> for Interned<'i, 'a, $map>
+        where
+            Interned<'i, 'a, K>: DeserializeSeed<'de, Value = K>,
+            V: serde::Deserialize<'de>,
+            $map: Default + Extend<(K, V)>,
+        {
+            type Value = $map;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            #[inline]
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut out = <$map>::default();
+                while let Some(key) = map.next_key_seed(self.with::<K>())? {
+                    let value = map.next_value::<V>()?;
+                    out.extend(core::iter::once((key, value)));
+                }
+                Ok(out)
+            }
+        }
+    };
+}
+
+impl_map!(alloc::collections::BTreeMap<K, V>, []);
+#[cfg(feature = "std")]
+impl_map!(std::collections::HashMap<K, V, S>, [S]);
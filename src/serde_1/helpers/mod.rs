@@ -0,0 +1,232 @@
+//! Helper modules for `serde` that represent byte data as text in human-readable formats.
+//!
+//! By default [`ByteData`](crate::ByteData) and [`SharedBytes`](crate::SharedBytes) are serialized as byte arrays,
+//! which formats like JSON write as arrays of numbers.
+//! The modules in here can be used with `#[serde(with = "...")]` to write them as strings instead:
+//!
+//! - [`base64`] writes standard base64 with padding.
+//! - [`hex`] writes lower case hex and reads either case.
+//! - [`utf8_or_base64`] writes valid UTF-8 as a plain string, and anything else as a map `{"base64": "..."}`.
+//!
+//! The representation only changes when the format is human-readable,
+//! compact formats keep writing plain byte arrays.
+//! The modules work on `ByteData`, `SharedBytes`, and `Option` or `Vec` of these.
+//! Decoded data is written into a chunk or a single shared buffer without any intermediate copies.
+//!
+//! With the `interning` feature, [`Interned`] deserializes strings and bytes through an
+//! [`interning`](crate::interning) implementation, so that repeated values share their storage.
+//!
+//! ```
+//! # use serde_1::{Deserialize, Serialize};
+//! use ::bytedata::{ByteData, SharedBytes};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! # #[serde(crate = "serde_1")]
+//! struct Config {
+//!     #[serde(with = "bytedata::serde::base64")]
+//!     key: ByteData<'static>,
+//!     #[serde(with = "bytedata::serde::hex")]
+//!     digests: Vec<SharedBytes>,
+//!     #[serde(with = "bytedata::serde::utf8_or_base64", default)]
+//!     label: Option<ByteData<'static>>,
+//! }
+//! ```
+
+use serde_1::{self as serde, ser::SerializeSeq};
+
+use crate::{ByteData, SharedBytes};
+
+pub mod base64;
+pub mod hex;
+pub mod utf8_or_base64;
+
+mod repr;
+pub use repr::Repr;
+
+#[cfg(feature = "interning")]
+mod interned;
+#[cfg(feature = "interning")]
+#[cfg_attr(docsrs, doc(cfg(feature = "interning")))]
+pub use interned::Interned;
+
+/// A field type that the helper modules can serialize and deserialize.
+///
+/// This is implemented for [`ByteData`], [`SharedBytes`], and `Option` or `Vec` of these.
+pub trait BytesField: Sized {
+    /// Serializes the value with the given representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the serializer.
+    fn serialize_as<S: serde::Serializer>(
+        &self,
+        repr: Repr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>;
+
+    /// Deserializes a value in the given representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not in the expected representation.
+    fn deserialize_as<'de, D: serde::Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+impl BytesField for ByteData<'_> {
+    #[inline]
+    fn serialize_as<S: serde::Serializer>(
+        &self,
+        repr: Repr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        repr.serialize(self.as_slice(), serializer)
+    }
+
+    #[inline]
+    fn deserialize_as<'de, D: serde::Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        serde::de::DeserializeSeed::deserialize(repr, deserializer)
+    }
+}
+
+impl BytesField for SharedBytes {
+    #[inline]
+    fn serialize_as<S: serde::Serializer>(
+        &self,
+        repr: Repr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        repr.serialize(self.as_slice(), serializer)
+    }
+
+    #[inline]
+    fn deserialize_as<'de, D: serde::Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        serde::de::DeserializeSeed::deserialize(repr, deserializer).map(Self::from)
+    }
+}
+
+/// Serializes a value with the representation of a helper module.
+struct As<'v, T>(Repr, &'v T);
+
+impl<T: BytesField> serde::Serialize for As<'_, T> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.1.serialize_as(self.0, serializer)
+    }
+}
+
+/// Deserializes a value with the representation of a helper module.
+struct Seed<T>(Repr, core::marker::PhantomData<T>);
+
+impl<T> Seed<T> {
+    const fn new(repr: Repr) -> Self {
+        Self(repr, core::marker::PhantomData)
+    }
+}
+
+impl<'de, T: BytesField> serde::de::DeserializeSeed<'de> for Seed<T> {
+    type Value = T;
+
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        T::deserialize_as(self.0, deserializer)
+    }
+}
+
+impl<'de, T: BytesField> serde::de::Visitor<'de> for Seed<Option<T>> {
+    type Value = Option<T>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("an optional value")
+    }
+
+    #[inline]
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_some<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        T::deserialize_as(self.0, deserializer).map(Some)
+    }
+}
+
+impl<'de, T: BytesField> serde::de::Visitor<'de> for Seed<alloc::vec::Vec<T>> {
+    type Value = alloc::vec::Vec<T>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    #[inline]
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut vec = alloc::vec::Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1024));
+        while let Some(value) = seq.next_element_seed(Seed::<T>::new(self.0))? {
+            vec.push(value);
+        }
+        Ok(vec)
+    }
+}
+
+impl<T: BytesField> BytesField for Option<T> {
+    #[inline]
+    fn serialize_as<S: serde::Serializer>(
+        &self,
+        repr: Repr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self.as_ref() {
+            Some(value) => serializer.serialize_some(&As(repr, value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    #[inline]
+    fn deserialize_as<'de, D: serde::Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_option(Seed::<Self>::new(repr))
+    }
+}
+
+impl<T: BytesField> BytesField for alloc::vec::Vec<T> {
+    #[inline]
+    fn serialize_as<S: serde::Serializer>(
+        &self,
+        repr: Repr,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self {
+            seq.serialize_element(&As(repr, value))?;
+        }
+        seq.end()
+    }
+
+    #[inline]
+    fn deserialize_as<'de, D: serde::Deserializer<'de>>(
+        repr: Repr,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(Seed::<Self>::new(repr))
+    }
+}
@@ -0,0 +1,132 @@
+use serde_1::{self as serde, ser::SerializeMap};
+
+use crate::codec::{Base64, Hex};
+use crate::ByteData;
+
+/// The representation of bytes in human-readable formats, as used by the helper modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Repr {
+    /// See [`base64`](super::base64).
+    Base64,
+    /// See [`hex`](super::hex).
+    Hex,
+    /// See [`utf8_or_base64`](super::utf8_or_base64).
+    Utf8OrBase64,
+}
+
+/// The key of the map that holds base64 data for [`utf8_or_base64`](super::utf8_or_base64).
+const BASE64_KEY: &str = "base64";
+
+impl Repr {
+    /// Serializes bytes in this representation, or as a byte array if the format is not human-readable.
+    pub(super) fn serialize<S: serde::Serializer>(
+        self,
+        bytes: &[u8],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(bytes);
+        }
+        match self {
+            Self::Base64 => serializer.serialize_str(Base64::STANDARD.encode(bytes).as_str()),
+            Self::Hex => serializer.serialize_str(Hex::LOWER.encode(bytes).as_str()),
+            Self::Utf8OrBase64 => {
+                if let Ok(text) = core::str::from_utf8(bytes) {
+                    return serializer.serialize_str(text);
+                }
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(BASE64_KEY, Base64::STANDARD.encode(bytes).as_str())?;
+                map.end()
+            }
+        }
+    }
+
+    /// Decodes a string in this representation.
+    fn decode_str<E: serde::de::Error>(self, text: &str) -> Result<ByteData<'static>, E> {
+        match self {
+            Self::Base64 => Base64::STANDARD.decode(text).map_err(E::custom),
+            Self::Hex => Hex::LOWER.decode(text).map_err(E::custom),
+            Self::Utf8OrBase64 => Ok(ByteData::from_borrowed(text.as_bytes()).into_shared()),
+        }
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for Repr {
+    type Value = ByteData<'static>;
+
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_byte_buf(self);
+        }
+        match self {
+            Self::Base64 | Self::Hex => deserializer.deserialize_str(self),
+            Self::Utf8OrBase64 => deserializer.deserialize_any(self),
+        }
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for Repr {
+    type Value = ByteData<'static>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str(match *self {
+            Self::Base64 => "a base64 string or a byte array",
+            Self::Hex => "a hex string or a byte array",
+            Self::Utf8OrBase64 => "a string, a map with a base64 string, or a byte array",
+        })
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.decode_str(v)
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_string<E: serde::de::Error>(self, v: alloc::string::String) -> Result<Self::Value, E> {
+        match self {
+            Self::Utf8OrBase64 => Ok(ByteData::from_owned(v.into_bytes())),
+            Self::Base64 | Self::Hex => self.decode_str(&v),
+        }
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(ByteData::from_borrowed(v).into_shared())
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_byte_buf<E: serde::de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+        Ok(ByteData::from_owned(v))
+    }
+
+    #[inline]
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        if !matches!(self, Self::Utf8OrBase64) {
+            return Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::Map,
+                &self,
+            ));
+        }
+        let Some(key) = map.next_key::<crate::StringData<'de>>()? else {
+            return Err(serde::de::Error::missing_field(BASE64_KEY));
+        };
+        if key != BASE64_KEY {
+            return Err(serde::de::Error::unknown_field(key.as_str(), &[BASE64_KEY]));
+        }
+        let data = map.next_value_seed(Self::Base64)?;
+        if let Some(extra) = map.next_key::<crate::StringData<'de>>()? {
+            return Err(serde::de::Error::unknown_field(extra.as_str(), &[]));
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,34 @@
+//! Represents bytes as a string in human-readable formats if they are valid UTF-8, and as a byte array otherwise.
+//!
+//! Bytes that are not valid UTF-8 are written as a map with a single `base64` entry holding standard base64 with padding.
+//! This keeps text readable while still allowing arbitrary bytes, for example `"hello"` or `{"base64": "/w=="}`.
+//! Use with `#[serde(with = "bytedata::serde::utf8_or_base64")]` on a field of a type listed in [`BytesField`](super::BytesField).
+
+use serde_1 as serde;
+
+use super::{BytesField, Repr};
+
+/// Serializes a value, see the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns the errors of the serializer.
+#[inline]
+pub fn serialize<T: BytesField, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize_as(Repr::Utf8OrBase64, serializer)
+}
+
+/// Deserializes a value, see the [module documentation](self).
+///
+/// # Errors
+///
+/// Returns an error if the input is not in the expected representation.
+#[inline]
+pub fn deserialize<'de, T: BytesField, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize_as(Repr::Utf8OrBase64, deserializer)
+}
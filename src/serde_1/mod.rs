@@ -6,6 +6,12 @@ mod stringdata;
 #[cfg(feature = "alloc")]
 mod shared_bytes;
 
+#[cfg(feature = "queue")]
+mod queue;
+
+#[cfg(feature = "alloc")]
+pub mod helpers;
+
 struct OptVisit<V>(V);
 
 impl<
@@ -0,0 +1,212 @@
+use serde_1 as serde;
+
+use crate::{ByteData, ByteQueue, StringData, StringQueue};
+
+/// Serializes a byte queue as a single byte array.
+///
+/// A queue with a single chunk is serialized without copying,
+/// otherwise the chunks are copied into one buffer as `serde` has no way to stream bytes.
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_1")))]
+impl serde::ser::Serialize for ByteQueue<'_> {
+    #[inline]
+    fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.chunk_len() <= 1 {
+            return serializer.serialize_bytes(self.front().map_or(&[], ByteData::as_slice));
+        }
+        #[cfg(feature = "alloc")]
+        {
+            let mut buf = alloc::vec::Vec::with_capacity(self.len());
+            for chunk in self.chunks() {
+                buf.extend_from_slice(chunk.as_slice());
+            }
+            serializer.serialize_bytes(&buf)
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            Err(serde::ser::Error::custom(
+                "the `alloc` or `std` feature is required in `bytedata` for fragmented byte data",
+            ))
+        }
+    }
+}
+
+/// Serializes a string queue as a single string.
+///
+/// A queue with a single chunk is serialized without copying,
+/// otherwise the chunks are passed to [`Serializer::collect_str`](serde::ser::Serializer::collect_str),
+/// which lets formats write the chunks one by one.
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_1")))]
+impl serde::ser::Serialize for StringQueue<'_> {
+    #[inline]
+    fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.chunk_len() <= 1 {
+            return serializer.serialize_str(self.front().map_or("", StringData::as_str));
+        }
+        serializer.collect_str(self)
+    }
+}
+
+/// The error for a sequence with more fragments than a queue can hold without `alloc`.
+fn queue_full<E: serde::de::Error>() -> E {
+    E::custom("the `alloc` or `std` feature is required in `bytedata` for more than 8 fragments")
+}
+
+struct ByteQueueVisitor<'a>(core::marker::PhantomData<ByteQueue<'a>>);
+
+impl<'de: 'a, 'a> serde::de::Visitor<'de> for ByteQueueVisitor<'a> {
+    type Value = ByteQueue<'a>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a byte array or a sequence of byte arrays")
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_borrowed_bytes<E: serde::de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        super::bytedata::ByteDataVisitor
+            .visit_borrowed_bytes(v)
+            .map(ByteQueue::with_item)
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        super::bytedata::ByteDataVisitor
+            .visit_bytes(v)
+            .map(ByteQueue::with_item)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_byte_buf<E: serde::de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+        super::bytedata::ByteDataVisitor
+            .visit_byte_buf(v)
+            .map(ByteQueue::with_item)
+    }
+
+    #[inline]
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut queue = ByteQueue::new();
+        while let Some(fragment) = seq.next_element::<ByteData<'de>>()? {
+            if queue.is_full() {
+                return Err(queue_full());
+            }
+            queue.push_back(fragment);
+        }
+        Ok(queue)
+    }
+}
+
+/// Deserialize a byte array, or a sequence of byte arrays, to a `ByteQueue` using `serde`.
+///
+/// Byte arrays borrowed from the input are pushed to the queue without copying.
+///
+/// ```rust
+/// # use serde_1::Deserialize;
+/// # use bytedata::ByteQueue;
+/// #[derive(Deserialize)]
+/// # #[serde(crate = "serde_1")]
+/// struct Borrowed<'a> {
+///     #[serde(borrow)]
+///     a: ByteQueue<'a>,
+///     b: u8,
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_1")))]
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for ByteQueue<'a> {
+    #[inline]
+    fn deserialize<D: serde::de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[cfg(feature = "alloc")]
+        {
+            deserializer.deserialize_byte_buf(ByteQueueVisitor(core::marker::PhantomData))
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            deserializer.deserialize_bytes(ByteQueueVisitor(core::marker::PhantomData))
+        }
+    }
+}
+
+struct StringQueueVisitor<'a>(core::marker::PhantomData<StringQueue<'a>>);
+
+impl<'de: 'a, 'a> serde::de::Visitor<'de> for StringQueueVisitor<'a> {
+    type Value = StringQueue<'a>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a string or a sequence of strings")
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        super::stringdata::StringDataVisitor
+            .visit_borrowed_str(v)
+            .map(StringQueue::from)
+    }
+
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        super::stringdata::StringDataVisitor
+            .visit_str(v)
+            .map(StringQueue::from)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::min_ident_chars)]
+    #[inline]
+    fn visit_string<E: serde::de::Error>(self, v: alloc::string::String) -> Result<Self::Value, E> {
+        super::stringdata::StringDataVisitor
+            .visit_string(v)
+            .map(StringQueue::from)
+    }
+
+    #[inline]
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut queue = StringQueue::new();
+        while let Some(fragment) = seq.next_element::<StringData<'de>>()? {
+            if queue.is_full() {
+                return Err(queue_full());
+            }
+            queue.push_back(fragment);
+        }
+        Ok(queue)
+    }
+}
+
+/// Deserialize a string, or a sequence of strings, to a `StringQueue` using `serde`.
+///
+/// Strings borrowed from the input are pushed to the queue without copying.
+///
+/// ```rust
+/// # use serde_1::Deserialize;
+/// # use bytedata::StringQueue;
+/// #[derive(Deserialize)]
+/// # #[serde(crate = "serde_1")]
+/// struct Borrowed<'a> {
+///     #[serde(borrow)]
+///     a: StringQueue<'a>,
+///     b: u8,
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_1")))]
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for StringQueue<'a> {
+    #[inline]
+    fn deserialize<D: serde::de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[cfg(feature = "alloc")]
+        {
+            deserializer.deserialize_string(StringQueueVisitor(core::marker::PhantomData))
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            deserializer.deserialize_str(StringQueueVisitor(core::marker::PhantomData))
+        }
+    }
+}
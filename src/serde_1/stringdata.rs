@@ -127,7 +127,7 @@ impl<'de> serde::de::DeserializeSeed<'de> for StaticStringDataVisitor {
     }
 }
 
-struct StringDataVisitor;
+pub(super) struct StringDataVisitor;
 
 impl<'de> serde::de::Visitor<'de> for StringDataVisitor {
     type Value = StringData<'de>;
@@ -0,0 +1,165 @@
+use core::alloc::Layout;
+
+/// An allocator for the buffers of [`SharedBytes`](crate::SharedBytes) and [`SharedBytesBuilder`](crate::SharedBytesBuilder).
+///
+/// A reference to the allocator is stored in front of each buffer it allocates, so the buffer is always released through the allocator that created it.
+///
+/// # Safety
+///
+/// Implementations must follow the same rules as [`GlobalAlloc`](core::alloc::GlobalAlloc).
+/// Memory returned by [`allocate`](SharedAllocator::allocate) must stay valid until it is passed to [`deallocate`](SharedAllocator::deallocate),
+/// which may happen on any thread.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub unsafe trait SharedAllocator: Sync {
+    /// Allocates memory as described by `layout`, returning a null pointer on failure.
+    ///
+    /// # Safety
+    ///
+    /// `layout` must have a non-zero size.
+    unsafe fn allocate(&self, layout: Layout) -> *mut u8;
+
+    /// Deallocates the memory at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator using `layout`.
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
+
+    /// Resizes the memory at `ptr` to `new_size` bytes, returning a null pointer on failure in which case the old memory is left untouched.
+    ///
+    /// The default implementation allocates a new block, copies the data, and deallocates the old block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator using `layout` and `new_size` must be non-zero.
+    #[inline]
+    unsafe fn reallocate(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let Ok(new_layout) = Layout::from_size_align(new_size, layout.align()) else {
+            return core::ptr::null_mut();
+        };
+        // SAFETY: the caller guarantees that `new_size` is non-zero.
+        let new_ptr = unsafe { self.allocate(new_layout) };
+        if new_ptr.is_null() {
+            return new_ptr;
+        }
+        // SAFETY: both blocks are valid for the smaller of the two sizes and do not overlap.
+        unsafe {
+            new_ptr.copy_from_nonoverlapping(ptr, layout.size().min(new_size));
+        };
+        // SAFETY: the caller guarantees that `ptr` was allocated by this allocator using `layout`.
+        unsafe { self.deallocate(ptr, layout) };
+        new_ptr
+    }
+}
+
+/// A reference to the allocator of a buffer.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) type AllocRef = &'static dyn SharedAllocator;
+
+/// The number of bytes in front of the [`SharedBytesMeta`](crate::SharedBytesMeta) used to store the [`AllocRef`] of a buffer from a custom allocator.
+#[inline]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const fn alloc_prefix(align: usize) -> usize {
+    let size = core::mem::size_of::<AllocRef>();
+    if align > size {
+        align
+    } else {
+        size
+    }
+}
+
+#[inline]
+fn layout_for(alloc: Option<AllocRef>, len: usize, align: usize) -> Layout {
+    let size = if alloc.is_some() {
+        len + alloc_prefix(align)
+    } else {
+        len
+    };
+    #[allow(clippy::unwrap_used)]
+    Layout::from_size_align(size, align).unwrap()
+}
+
+/// Allocates a buffer of `len` bytes, returning a pointer to where the [`SharedBytesMeta`](crate::SharedBytesMeta) should be placed.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn allocate(alloc: Option<AllocRef>, len: usize, align: usize) -> *mut u8 {
+    let layout = layout_for(alloc, len, align);
+    let Some(alloc) = alloc else {
+        // SAFETY: the layout must be valid
+        let ptr = unsafe { alloc::alloc::alloc(layout) };
+        if ptr.is_null() {
+            alloc::alloc::handle_alloc_error(layout);
+        }
+        return ptr;
+    };
+    // SAFETY: the layout must be valid
+    let ptr = unsafe { alloc.allocate(layout) };
+    if ptr.is_null() {
+        alloc::alloc::handle_alloc_error(layout);
+    }
+    // SAFETY: the prefix is large enough to hold the allocator reference.
+    unsafe { ptr.cast::<AllocRef>().write_unaligned(alloc) };
+    // SAFETY: the prefix is part of the allocation.
+    unsafe { ptr.add(alloc_prefix(align)) }
+}
+
+/// Resizes a buffer from `len` to `new_len` bytes, returning a null pointer on failure.
+///
+/// # Safety
+///
+/// `dat` must have been returned by [`allocate`] or [`reallocate`] using the same `alloc`, `len`, and `align`.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) unsafe fn reallocate(
+    alloc: Option<AllocRef>,
+    dat: *mut u8,
+    len: usize,
+    align: usize,
+    new_len: usize,
+) -> *mut u8 {
+    let layout = layout_for(alloc, len, align);
+    let Some(alloc) = alloc else {
+        // SAFETY: `layout` is the layout of the old allocation.
+        return unsafe { alloc::alloc::realloc(dat, layout, new_len) };
+    };
+    let prefix = alloc_prefix(align);
+    // SAFETY: the prefix is part of the allocation.
+    let base = unsafe { dat.sub(prefix) };
+    // SAFETY: `layout` is the layout of the old allocation.
+    let ptr = unsafe { alloc.reallocate(base, layout, new_len + prefix) };
+    if ptr.is_null() {
+        return ptr;
+    }
+    // SAFETY: the prefix is part of the allocation.
+    unsafe { ptr.add(prefix) }
+}
+
+/// Deallocates a buffer of `len` bytes.
+///
+/// # Safety
+///
+/// `dat` must have been returned by [`allocate`] or [`reallocate`] using the same `alloc`, `len`, and `align`.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) unsafe fn deallocate(alloc: Option<AllocRef>, dat: *mut u8, len: usize, align: usize) {
+    let layout = layout_for(alloc, len, align);
+    let Some(alloc) = alloc else {
+        // SAFETY: `layout` is the layout of the allocation.
+        unsafe { alloc::alloc::dealloc(dat, layout) };
+        return;
+    };
+    // SAFETY: the prefix is part of the allocation.
+    let base = unsafe { dat.sub(alloc_prefix(align)) };
+    // SAFETY: `layout` is the layout of the allocation.
+    unsafe { alloc.deallocate(base, layout) };
+}
+
+/// Reads the allocator reference stored in front of a buffer from a custom allocator.
+///
+/// # Safety
+///
+/// `dat` must have been returned by [`allocate`] or [`reallocate`] using a custom allocator and `align`.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) unsafe fn read_allocator(dat: *const u8, align: usize) -> AllocRef {
+    // SAFETY: the prefix is part of the allocation.
+    let base = unsafe { dat.sub(alloc_prefix(align)) };
+    // SAFETY: the prefix holds the allocator reference.
+    unsafe { base.cast::<AllocRef>().read_unaligned() }
+}
@@ -1,19 +1,49 @@
 use core::ops::{Bound, Deref, Index, RangeBounds};
+use core::sync::atomic::Ordering;
 
 type RefCounter = core::sync::atomic::AtomicU32;
 
 use alloc::vec::Vec;
 
-use crate::SharedBytesBuilder;
+use crate::shared_allocator::{self, AllocRef};
+use crate::{SharedAllocator, SharedBytesBuilder};
+
+/// Flag in [`SharedBytesMeta::info`] that marks a buffer whose allocated length does not fit in 32 bits.
+/// The upper bits of the length are then stored in [`SharedBytesMeta::len_high`].
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const INFO_LARGE: u8 = 0b1000_0000;
+
+/// Flag in [`SharedBytesMeta::info`] that marks a buffer allocated by a [`SharedAllocator`](crate::SharedAllocator).
+/// A reference to the allocator is stored in front of the [`SharedBytesMeta`].
+const INFO_CUSTOM_ALLOC: u8 = 0b0100_0000;
+
+/// Value of [`SharedBytesMeta::weakcnt`] while a unique buffer is being converted into a builder.
+const WEAK_LOCKED: u32 = u32::MAX;
+
+/// Mask of the bits in [`SharedBytesMeta::info`] that store the alignment of the buffer.
+const INFO_ALIGN_MASK: u8 = 0b0000_0111;
+
+/// The maximum number of bytes that can be allocated for a single buffer, including the metadata prefix.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const MAX_ALLOC_LEN: usize = if usize::BITS > 56 {
+    0x00FF_FFFF_FFFF_FFFF
+} else {
+    isize::MAX as usize
+};
 
 #[allow(clippy::redundant_pub_crate)]
 #[repr(C)]
 pub(crate) struct SharedBytesMeta {
     /// The reference count of the data.
     pub(crate) refcnt: RefCounter,
+    /// The number of [`WeakSharedBytes`] referencing the allocation, plus one shared by all strong references.
+    pub(crate) weakcnt: RefCounter,
     /// The last 3 bits are used to store the alignment of the data.
+    /// The first bit is set if the buffer is larger than 4 GiB, see [`INFO_LARGE`].
+    /// The second bit is set if the buffer was allocated by a custom allocator, see [`INFO_CUSTOM_ALLOC`].
     pub(crate) info: u8,
-    pub(crate) _reserved: [u8; 3],
+    /// The upper 24 bits of the allocated length if [`INFO_LARGE`] is set.
+    pub(crate) len_high: [u8; 3],
     /// The allocated length of the data, some of which may be uninitialized.
     pub(crate) len: u32,
 }
@@ -22,12 +52,27 @@ impl SharedBytesMeta {
     #[inline]
     pub(crate) const fn new() -> Self {
         // SAFETY: `SharedBytesMeta` is a valid empty struct.
-        unsafe { core::mem::zeroed::<Self>() }.with_align(4)
+        let mut meta = unsafe { core::mem::zeroed::<Self>() }.with_align(4);
+        meta.weakcnt = RefCounter::new(1);
+        meta
     }
 
     #[inline]
-    pub(crate) const fn with_len(mut self, len: u32) -> Self {
-        self.len = len;
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const fn with_len(mut self, len: usize) -> Self {
+        let len = len as u64;
+        self.len = len as u32;
+        if len > u32::MAX as u64 {
+            self.info |= INFO_LARGE;
+            self.len_high = [
+                (len >> 32_u32) as u8,
+                (len >> 40_u32) as u8,
+                (len >> 48_u32) as u8,
+            ];
+        } else {
+            self.info &= !INFO_LARGE;
+            self.len_high = [0; 3];
+        }
         self
     }
 
@@ -36,7 +81,17 @@ impl SharedBytesMeta {
     pub(crate) const fn with_align(mut self, align: usize) -> Self {
         const ALIGN: usize = core::mem::align_of::<SharedBytesMeta>();
         let align = if align < ALIGN { ALIGN } else { align };
-        self.info = (align >> 2).ilog2() as u8;
+        self.info = (self.info & !INFO_ALIGN_MASK) | (align >> 2).ilog2() as u8;
+        self
+    }
+
+    #[inline]
+    pub(crate) const fn with_allocator(mut self, alloc: Option<AllocRef>) -> Self {
+        if alloc.is_some() {
+            self.info |= INFO_CUSTOM_ALLOC;
+        } else {
+            self.info &= !INFO_CUSTOM_ALLOC;
+        }
         self
     }
 
@@ -48,27 +103,113 @@ impl SharedBytesMeta {
 
     #[inline]
     pub(crate) const fn align(&self) -> usize {
-        4_usize << self.info
+        4_usize << (self.info & INFO_ALIGN_MASK)
+    }
+
+    /// Returns `true` if the allocated length is stored using more than 32 bits.
+    #[inline]
+    pub(crate) const fn is_large(&self) -> bool {
+        self.info & INFO_LARGE != 0
+    }
+
+    /// Returns the custom allocator of the buffer, or `None` if it was allocated by the global allocator.
+    #[inline]
+    pub(crate) fn allocator(&self) -> Option<AllocRef> {
+        if self.info & INFO_CUSTOM_ALLOC == 0 {
+            return None;
+        }
+        let dat: *const Self = self;
+        // SAFETY: the flag is only set for buffers allocated by `shared_allocator::allocate` with a custom allocator.
+        Some(unsafe { shared_allocator::read_allocator(dat.cast::<u8>(), self.align()) })
     }
 
-    pub(crate) const fn compute_start_offset(align: usize) -> u32 {
-        #[allow(
-            clippy::cast_possible_truncation,
-            clippy::integer_division_remainder_used
-        )]
+    /// The allocated length of the buffer, including the metadata prefix.
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const fn alloc_len(&self) -> usize {
+        if !self.is_large() {
+            return self.len as usize;
+        }
+        let high = self.len_high;
+        let len = self.len as u64
+            | (high[0] as u64) << 32_u32
+            | (high[1] as u64) << 40_u32
+            | (high[2] as u64) << 48_u32;
+        len as usize
+    }
+
+    /// Releases one strong reference to the allocation at `dat`, deallocating it if it was the last reference.
+    ///
+    /// # Safety
+    ///
+    /// `dat` must point to a live `SharedBytesMeta` and the caller must own one strong reference to it.
+    pub(crate) unsafe fn release(dat: *const u8) {
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` should point to a `SharedBytesMeta`.
+        let meta = unsafe { &*dat.cast::<Self>() };
+        if meta.refcnt.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        core::sync::atomic::fence(Ordering::Acquire);
+        // SAFETY: the strong references collectively own one weak reference, which is released by the last of them.
+        unsafe { Self::release_weak(dat) };
+    }
+
+    /// Releases one weak reference to the allocation at `dat`, deallocating it if it was the last reference.
+    ///
+    /// # Safety
+    ///
+    /// `dat` must point to a live `SharedBytesMeta` and the caller must own one weak reference to it.
+    pub(crate) unsafe fn release_weak(dat: *const u8) {
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` should point to a `SharedBytesMeta`.
+        let meta = unsafe { &*dat.cast::<Self>() };
+        if meta.weakcnt.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        core::sync::atomic::fence(Ordering::Acquire);
+        // SAFETY: no references remain and the allocator, length, and alignment are the ones used when allocating.
+        unsafe {
+            shared_allocator::deallocate(
+                meta.allocator(),
+                dat.cast_mut(),
+                meta.alloc_len(),
+                meta.align(),
+            );
+        };
+    }
+
+    pub(crate) const fn compute_start_offset(align: usize) -> usize {
         if align >= core::mem::size_of::<Self>() {
-            align as u32
+            align
         } else {
+            #[allow(clippy::integer_division_remainder_used)]
             let diff = core::mem::size_of::<Self>() % align;
             if diff == 0 {
-                core::mem::size_of::<Self>() as u32
+                core::mem::size_of::<Self>()
             } else {
-                (core::mem::size_of::<Self>() + (align - diff)) as u32
+                core::mem::size_of::<Self>() + (align - diff)
             }
         }
     }
 }
 
+/// The offset and length of a [`SharedBytes`] that can not be represented using 32 bits.
+///
+/// Each handle owns its own boxed `SharedBytesLarge`, which is marked by [`SharedBytes::len`] being [`LEN_LARGE`].
+#[repr(C)]
+struct SharedBytesLarge {
+    /// Pointer to the start of the allocation, where the [`SharedBytesMeta`] is located.
+    dat: *const u8,
+    /// The offset of the first byte from the start of the allocation.
+    off: usize,
+    /// The number of bytes.
+    len: usize,
+}
+
+/// Marker in [`SharedBytes::len`] for a handle that stores its offset and length in a [`SharedBytesLarge`].
+const LEN_LARGE: u32 = u32::MAX;
+
 /// A slice of a reference-counted byte buffer.
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 #[repr(C)]
@@ -93,13 +234,88 @@ impl SharedBytes {
         Self::EMPTY
     }
 
+    /// Creates a `SharedBytes` from a pointer to the allocation, taking over one reference.
+    ///
+    /// Uses the compact representation if both `off` and `len` fit in 32 bits.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_parts(dat: *const u8, off: usize, len: usize) -> Self {
+        if len < LEN_LARGE as usize && u32::try_from(off).is_ok() {
+            let dat_addr = dat as usize as u64;
+            return Self {
+                len: len as u32,
+                off: off as u32,
+                dat_addr: dat_addr.to_le(),
+            };
+        }
+        let large = alloc::boxed::Box::new(SharedBytesLarge { dat, off, len });
+        let dat_addr = alloc::boxed::Box::into_raw(large) as usize as u64;
+        Self {
+            len: LEN_LARGE,
+            off: 0,
+            dat_addr: dat_addr.to_le(),
+        }
+    }
+
+    /// Splits the `SharedBytes` into the pointer to the allocation, the offset, and the length, without releasing the reference.
+    pub(crate) fn into_parts(self) -> (*const u8, usize, usize) {
+        let this = core::mem::ManuallyDrop::new(self);
+        let dat = this.dat();
+        let (off, len) = this.range();
+        if this.len == LEN_LARGE {
+            // SAFETY: the handle owns the boxed `SharedBytesLarge`.
+            core::mem::drop(unsafe { alloc::boxed::Box::from_raw(this.large_ptr()) });
+        }
+        (dat, off, len)
+    }
+
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    const fn large_ptr(&self) -> *mut SharedBytesLarge {
+        self.dat_addr.to_le() as usize as *mut SharedBytesLarge
+    }
+
+    /// Returns a pointer to the start of the allocation, where the [`SharedBytesMeta`] is located.
     #[inline]
     #[must_use]
     #[allow(clippy::cast_possible_truncation)]
     pub(crate) const fn dat(&self) -> *const u8 {
+        if self.len == LEN_LARGE {
+            // SAFETY: the handle owns a valid `SharedBytesLarge`.
+            return unsafe { (*self.large_ptr()).dat };
+        }
         self.dat_addr.to_le() as usize as *const u8
     }
 
+    /// Returns the offset from the start of the allocation and the length of the data.
+    #[inline]
+    pub(crate) const fn range(&self) -> (usize, usize) {
+        if self.len == LEN_LARGE {
+            // SAFETY: the handle owns a valid `SharedBytesLarge`.
+            let large = unsafe { &*self.large_ptr() };
+            return (large.off, large.len);
+        }
+        (self.off as usize, self.len as usize)
+    }
+
+    /// Moves the start of the data forward by `offset` bytes and sets the length to `len`.
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    fn restrict(&mut self, offset: usize, len: usize) {
+        if self.len == LEN_LARGE {
+            // SAFETY: the handle owns a valid `SharedBytesLarge`, which is not shared with any other handle.
+            let large = unsafe { &mut *self.large_ptr() };
+            large.off += offset;
+            large.len = len;
+        } else if let Ok(off) = u32::try_from(self.off as usize + offset) {
+            self.len = len as u32;
+            self.off = off;
+        } else {
+            // the new offset does not fit in 32 bits, so the handle moves to the large representation
+            let large = Self::from_parts(self.dat(), self.off as usize + offset, len);
+            core::mem::forget(core::mem::replace(self, large));
+        }
+    }
+
     /// Creates a `SharedBytes` from a slice of bytes.
     #[inline]
     #[must_use]
@@ -107,20 +323,44 @@ impl SharedBytes {
         Self::from_slice_aligned(dat, core::mem::align_of::<SharedBytesMeta>())
     }
 
+    /// Creates a `SharedBytes` from a slice of bytes, allocated by the given allocator.
+    #[inline]
+    #[must_use]
+    pub fn from_slice_in(dat: &[u8], alloc: &'static dyn SharedAllocator) -> Self {
+        if dat.is_empty() {
+            return Self::EMPTY;
+        }
+        let mut builder = SharedBytesBuilder::with_capacity_in(dat.len(), alloc);
+        builder.extend_from_slice(dat);
+        builder.build()
+    }
+
+    /// Returns the custom allocator of the buffer, or `None` if it was allocated by the global allocator.
+    #[inline]
+    #[must_use]
+    pub fn allocator(&self) -> Option<&'static dyn SharedAllocator> {
+        let dat = self.dat();
+        if dat.is_null() {
+            return None;
+        }
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` should point to a `SharedBytesMeta`.
+        let meta = unsafe { &*dat.cast::<SharedBytesMeta>() };
+        meta.allocator()
+    }
+
     /// Creates a `SharedBytes` from a slice of bytes and a target alignment.
     #[inline]
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn from_slice_aligned(dat: &[u8], align: usize) -> Self {
         fn from_slice_aligned_inner(dat: &[u8], align: usize) -> SharedBytes {
             let align = core::mem::align_of::<SharedBytesMeta>().max(align);
-            let off = SharedBytesMeta::compute_start_offset(align) as usize;
-            let max_size: usize = 0xFFFF_FFFF - off;
+            let off = SharedBytesMeta::compute_start_offset(align);
+            let max_size: usize = MAX_ALLOC_LEN - off;
             assert!(
                 dat.len() <= max_size,
                 "SharedBytes::from_slice: slice too large"
             );
-            let len = dat.len() as u32;
             let alloc_size = dat.len() + off;
             #[allow(clippy::unwrap_used)]
             let layout = core::alloc::Layout::from_size_align(alloc_size, align).unwrap();
@@ -133,7 +373,7 @@ impl SharedBytes {
                 #[allow(clippy::cast_ptr_alignment)]
                 let meta = ptr.cast::<SharedBytesMeta>();
                 let meta_item = SharedBytesMeta::new()
-                    .with_len(alloc_size as u32)
+                    .with_len(alloc_size)
                     .with_align(align)
                     .with_refcount(1);
                 // SAFETY: `meta` is a valid pointer to the prefix `SharedBytesMeta`.
@@ -148,13 +388,7 @@ impl SharedBytes {
                 };
                 ptr
             };
-            let dat_addr = ptr as usize as u64;
-            let dat_addr = dat_addr.to_le();
-            SharedBytes {
-                len,
-                off: off as u32,
-                dat_addr,
-            }
+            SharedBytes::from_parts(ptr, off, dat.len())
         }
         if dat.is_empty() {
             return Self::EMPTY;
@@ -173,14 +407,14 @@ impl SharedBytes {
     #[inline]
     #[must_use]
     pub const fn len(&self) -> usize {
-        self.len as usize
+        self.range().1
     }
 
     /// Returns `true` if the buffer is empty.
     #[inline]
     #[must_use]
     pub const fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
     }
 
     /// Returns `true` if there is only a single owner of the data.
@@ -198,6 +432,47 @@ impl SharedBytes {
         meta.refcnt.load(core::sync::atomic::Ordering::Relaxed) == 1
     }
 
+    /// Creates a [`WeakSharedBytes`] referencing the same data, which does not keep the data alive.
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn downgrade(&self) -> WeakSharedBytes {
+        let dat = self.dat();
+        let (off, len) = self.range();
+        if dat.is_null() {
+            return WeakSharedBytes {
+                dat_addr: 0,
+                off,
+                len,
+            };
+        }
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` should point to a `SharedBytesMeta`.
+        let meta = unsafe { &*dat.cast::<SharedBytesMeta>() };
+        let mut cur = meta.weakcnt.load(Ordering::Relaxed);
+        loop {
+            if cur == WEAK_LOCKED {
+                // the buffer is being checked for uniqueness by `try_into_builder`
+                core::hint::spin_loop();
+                cur = meta.weakcnt.load(Ordering::Relaxed);
+                continue;
+            }
+            match meta.weakcnt.compare_exchange_weak(
+                cur,
+                cur + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => cur = actual,
+            }
+        }
+        WeakSharedBytes {
+            dat_addr: dat as usize,
+            off,
+            len,
+        }
+    }
+
     /// Returns the bytes as a slice.
     #[inline]
     #[must_use]
@@ -205,8 +480,7 @@ impl SharedBytes {
         if self.len == 0 {
             return &[];
         }
-        let len = self.len as usize;
-        let off = self.off as usize;
+        let (off, len) = self.range();
         let dat = self.dat();
         // SAFETY: `len` and `off` are within bounds.
         let dat = unsafe { dat.add(off) };
@@ -250,32 +524,26 @@ impl SharedBytes {
     #[must_use]
     #[allow(clippy::missing_inline_in_public_items)]
     pub fn sliced(&self, offset: usize, len: usize) -> Self {
+        let (cur_off, cur_len) = self.range();
         assert!(
-            offset <= self.len as usize,
+            offset <= cur_len,
             "SharedBytes::sliced: offset out of bounds"
         );
         assert!(
-            offset + len <= self.len as usize,
+            offset + len <= cur_len,
             "SharedBytes::sliced: offset + len out of bounds"
         );
         if len == 0 {
             return Self::EMPTY;
         }
-        #[allow(clippy::cast_possible_truncation)]
-        let len = len as u32;
-        #[allow(clippy::cast_possible_truncation)]
-        let off = self.off + offset as u32;
+        let dat = self.dat();
         #[allow(clippy::cast_ptr_alignment)]
-        let meta = self.dat().cast::<SharedBytesMeta>();
+        let meta = dat.cast::<SharedBytesMeta>();
         // SAFETY: `meta` is a valid pointer.
         unsafe { &*meta }
             .refcnt
             .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
-        Self {
-            len,
-            off,
-            dat_addr: self.dat_addr,
-        }
+        Self::from_parts(dat, cur_off + offset, len)
     }
 
     /// Returns a new subslice of the bytes.
@@ -294,7 +562,7 @@ impl SharedBytes {
         let end = match range.end_bound() {
             Bound::Included(end) => end + 1,
             Bound::Excluded(end) => *end,
-            Bound::Unbounded => self.len as usize,
+            Bound::Unbounded => self.len(),
         };
         assert!(end >= start, "SharedBytes::sliced_range: end < start");
         self.sliced(start, end - start)
@@ -305,20 +573,20 @@ impl SharedBytes {
     /// # Panics
     ///
     /// Panics if `range` are out of bounds.
+    ///
+    /// This is not a `const fn` since 0.2.0, as a handle whose offset no longer fits in 32 bits is moved to an allocated representation.
     #[inline]
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
-    pub const fn into_sliced(mut self, offset: usize, len: usize) -> Self {
+    pub fn into_sliced(mut self, offset: usize, len: usize) -> Self {
         assert!(
-            offset <= self.len as usize,
+            offset <= self.len(),
             "SharedBytes::into_sliced: offset out of bounds"
         );
         assert!(
-            offset + len <= self.len as usize,
+            offset + len <= self.len(),
             "SharedBytes::into_sliced: offset + len out of bounds"
         );
-        self.len = len as u32;
-        self.off += offset as u32;
+        self.restrict(offset, len);
         self
     }
 
@@ -328,18 +596,16 @@ impl SharedBytes {
     ///
     /// Panics if `range` are out of bounds.
     #[inline]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn make_sliced(&mut self, offset: usize, len: usize) -> &mut Self {
         assert!(
-            offset <= self.len as usize,
+            offset <= self.len(),
             "SharedBytes::make_sliced: offset out of bounds"
         );
         assert!(
-            offset + len <= self.len as usize,
+            offset + len <= self.len(),
             "SharedBytes::make_sliced: offset + len out of bounds"
         );
-        self.len = len as u32;
-        self.off += offset as u32;
+        self.restrict(offset, len);
         self
     }
 
@@ -350,7 +616,6 @@ impl SharedBytes {
     /// Panics if `range` are out of bounds.
     #[inline]
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn into_sliced_range<R: RangeBounds<usize>>(self, range: R) -> Self {
         let start = match range.start_bound() {
             Bound::Included(start) => *start,
@@ -360,7 +625,7 @@ impl SharedBytes {
         let end = match range.end_bound() {
             Bound::Included(end) => end + 1,
             Bound::Excluded(end) => *end,
-            Bound::Unbounded => self.len as usize,
+            Bound::Unbounded => self.len(),
         };
         self.into_sliced(start, end - start)
     }
@@ -371,7 +636,6 @@ impl SharedBytes {
     ///
     /// Panics if `range` are out of bounds.
     #[inline]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn make_sliced_range<R: RangeBounds<usize>>(&mut self, range: R) -> &mut Self {
         let start = match range.start_bound() {
             Bound::Included(start) => *start,
@@ -381,11 +645,96 @@ impl SharedBytes {
         let end = match range.end_bound() {
             Bound::Included(end) => end + 1,
             Bound::Excluded(end) => *end,
-            Bound::Unbounded => self.len as usize,
+            Bound::Unbounded => self.len(),
         };
         self.make_sliced(start, end - start)
     }
 
+    /// Returns the alignment of the underlying buffer.
+    #[inline]
+    pub(crate) fn align(&self) -> usize {
+        let dat = self.dat();
+        if dat.is_null() {
+            return core::mem::align_of::<SharedBytesMeta>();
+        }
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` should point to a `SharedBytesMeta`.
+        let meta = unsafe { &*dat.cast::<SharedBytesMeta>() };
+        meta.align()
+    }
+
+    /// Converts this `SharedBytes` into a [`SharedBytesBuilder`] without copying.
+    ///
+    /// The allocation is reused in place, including any unused capacity after the data.
+    /// If the data has been sliced from the start of the buffer it is moved to the beginning of the allocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns the input if there are other references to the buffer, including [`WeakSharedBytes`].
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn try_into_builder(self) -> Result<SharedBytesBuilder, Self> {
+        let dat_dat = self.dat();
+        if dat_dat.is_null() {
+            return Ok(SharedBytesBuilder::new());
+        }
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat_dat` should be a valid pointer.
+        let meta = unsafe { &*dat_dat.cast::<SharedBytesMeta>() };
+        // lock out new weak references while checking that this is the only strong reference
+        if meta
+            .weakcnt
+            .compare_exchange(1, WEAK_LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(self);
+        }
+        if meta.refcnt.load(Ordering::Acquire) != 1 {
+            meta.weakcnt.store(1, Ordering::Release);
+            return Err(self);
+        }
+        let align = meta.align();
+        let len = meta.alloc_len();
+        let alloc = meta.allocator();
+        let (dat, dat_off, dat_len) = self.into_parts();
+        let dataptr = dat.cast_mut();
+        let start_off = SharedBytesMeta::compute_start_offset(align);
+        if dat_len != 0 && dat_off != start_off {
+            // there is a prefix of unwanted data, so we move it to the beginning
+
+            // SAFETY: `start_off` is the correct offset for the data, as determined by the alignment.
+            let dest = unsafe { dataptr.add(start_off) };
+            // SAFETY: `dat_off` is the offset of the data in the buffer which is greater than `start_off` but regions may overlap.
+            let sorc = unsafe { dataptr.add(dat_off) };
+            // SAFETY: `dat_len` is the length of the data in the buffer, `src` and `dst` are valid pointers but may overlap within `dat_len` bytes.
+            unsafe {
+                dest.copy_from(sorc, dat_len);
+            };
+        }
+        Ok(SharedBytesBuilder {
+            align,
+            off: start_off + dat_len,
+            len,
+            dat: dataptr,
+            alloc,
+        })
+    }
+
+    /// Forces the handle to use the boxed representation for large buffers, regardless of size.
+    #[cfg(test)]
+    pub(crate) fn into_large_repr(self) -> Self {
+        if self.len == LEN_LARGE || self.is_empty() {
+            return self;
+        }
+        let (dat, off, len) = self.into_parts();
+        let large = alloc::boxed::Box::new(SharedBytesLarge { dat, off, len });
+        let dat_addr = alloc::boxed::Box::into_raw(large) as usize as u64;
+        Self {
+            len: LEN_LARGE,
+            off: 0,
+            dat_addr: dat_addr.to_le(),
+        }
+    }
+
     #[cfg(test)]
     #[inline]
     pub(crate) fn ref_count(&self) -> u32 {
@@ -413,11 +762,15 @@ impl Clone for SharedBytes {
             return Self::EMPTY;
         }
         #[allow(clippy::cast_ptr_alignment)]
-        let dat = dat.cast::<SharedBytesMeta>().cast_mut();
-        // SAFETY: `dat` is a valid pointer.
-        unsafe { &mut *dat }
+        let meta = dat.cast::<SharedBytesMeta>();
+        // SAFETY: `meta` is a valid pointer.
+        unsafe { &*meta }
             .refcnt
             .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        if self.len == LEN_LARGE {
+            let (off, len) = self.range();
+            return Self::from_parts(dat, off, len);
+        }
         Self {
             len: self.len,
             off: self.off,
@@ -429,33 +782,135 @@ impl Clone for SharedBytes {
 impl Drop for SharedBytes {
     #[inline]
     fn drop(&mut self) {
-        fn drop_dat(dat: &u8) {
-            let dat = dat as *const u8;
+        let dat = self.dat();
+        if self.len == LEN_LARGE {
+            // SAFETY: the handle owns the boxed `SharedBytesLarge`.
+            core::mem::drop(unsafe { alloc::boxed::Box::from_raw(self.large_ptr()) });
+            self.len = 0;
+        }
+        if dat.is_null() {
+            return;
+        }
+        // SAFETY: `dat` is a valid pointer and this handle owns one strong reference.
+        unsafe { SharedBytesMeta::release(dat) };
+        self.dat_addr = 0;
+    }
+}
+
+/// A weak reference to the data of a [`SharedBytes`], created by [`SharedBytes::downgrade`].
+///
+/// The allocation is kept until all weak references are dropped, but the data can only be accessed
+/// through [`WeakSharedBytes::upgrade`] while there is at least one [`SharedBytes`] referencing it.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct WeakSharedBytes {
+    dat_addr: usize,
+    off: usize,
+    len: usize,
+}
+
+impl WeakSharedBytes {
+    /// Attempts to get a [`SharedBytes`] for the referenced data.
+    ///
+    /// Returns `None` if all [`SharedBytes`] referencing the data have been dropped.
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn upgrade(&self) -> Option<SharedBytes> {
+        let dat = self.dat_addr as *const u8;
+        if dat.is_null() {
+            return Some(SharedBytes::EMPTY);
+        }
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` points to a `SharedBytesMeta` which is kept allocated by this weak reference.
+        let meta = unsafe { &*dat.cast::<SharedBytesMeta>() };
+        let mut cur = meta.refcnt.load(Ordering::Relaxed);
+        loop {
+            if cur == 0 {
+                return None;
+            }
+            match meta.refcnt.compare_exchange_weak(
+                cur,
+                cur + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => cur = actual,
+            }
+        }
+        Some(SharedBytes::from_parts(dat, self.off, self.len))
+    }
+
+    /// Returns the number of [`SharedBytes`] referencing the data.
+    #[must_use]
+    #[allow(clippy::missing_inline_in_public_items)]
+    pub fn strong_count(&self) -> usize {
+        let dat = self.dat_addr as *const u8;
+        if dat.is_null() {
+            return 0;
+        }
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `dat` points to a `SharedBytesMeta` which is kept allocated by this weak reference.
+        let meta = unsafe { &*dat.cast::<SharedBytesMeta>() };
+        meta.refcnt.load(Ordering::Relaxed) as usize
+    }
+
+    /// Returns the number of bytes in the referenced data.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the referenced data is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Clone for WeakSharedBytes {
+    #[inline]
+    fn clone(&self) -> Self {
+        let dat = self.dat_addr as *const u8;
+        if !dat.is_null() {
             #[allow(clippy::cast_ptr_alignment)]
-            // SAFETY: `dat` should point to a `SharedBytesMeta`.
+            // SAFETY: `dat` points to a `SharedBytesMeta` which is kept allocated by this weak reference.
             let meta = unsafe { &*dat.cast::<SharedBytesMeta>() };
-            let refcnt = &meta.refcnt;
-            if refcnt.fetch_sub(1, core::sync::atomic::Ordering::Relaxed) == 1 {
-                #[allow(clippy::unwrap_used)]
-                let layout =
-                    core::alloc::Layout::from_size_align(meta.len as usize, meta.align()).unwrap();
-                let dat = dat.cast_mut();
-                // SAFETY: `dat` is a valid pointer and the layout should be correct.
-                unsafe {
-                    alloc::alloc::dealloc(dat, layout);
-                };
-            }
+            meta.weakcnt.fetch_add(1, Ordering::Relaxed);
         }
-        let dat = self.dat();
-        // SAFETY: `dat` is a valid pointer.
-        let Some(dat) = (unsafe { dat.as_ref() }) else {
+        Self {
+            dat_addr: self.dat_addr,
+            off: self.off,
+            len: self.len,
+        }
+    }
+}
+
+impl Drop for WeakSharedBytes {
+    #[inline]
+    fn drop(&mut self) {
+        let dat = self.dat_addr as *const u8;
+        if dat.is_null() {
             return;
-        };
-        drop_dat(dat);
+        }
+        // SAFETY: `dat` is a valid pointer and this handle owns one weak reference.
+        unsafe { SharedBytesMeta::release_weak(dat) };
         self.dat_addr = 0;
     }
 }
 
+impl core::fmt::Debug for WeakSharedBytes {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WeakSharedBytes")
+            .field("len", &self.len)
+            .field("off", &self.off)
+            .field("ptr", &(self.dat_addr as *const u8))
+            .finish()
+    }
+}
+
 impl core::str::FromStr for SharedBytes {
     type Err = core::convert::Infallible;
 
@@ -473,6 +928,13 @@ impl AsRef<[u8]> for SharedBytes {
     }
 }
 
+impl core::borrow::Borrow<[u8]> for SharedBytes {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 impl Deref for SharedBytes {
     type Target = [u8];
     #[inline]
@@ -532,44 +994,14 @@ impl From<SharedBytesBuilder> for SharedBytes {
 
 impl From<SharedBytes> for SharedBytesBuilder {
     #[inline]
-    fn from(mut dat: SharedBytes) -> Self {
-        let dat_dat = dat.dat();
-        if dat_dat.is_null() {
-            return Self::new();
-        }
-        // SAFETY: `dat_dat` should be a valid pointer.
-        #[allow(clippy::cast_ptr_alignment)]
-        let meta = unsafe { &*dat_dat.cast::<SharedBytesMeta>() };
-        let align = meta.align();
-        if meta.refcnt.load(core::sync::atomic::Ordering::Relaxed) == 1 {
-            let len = meta.len;
-            let dat_len = dat.len;
-            let dataptr = dat_dat.cast_mut();
-            dat.dat_addr = 0;
-            let start_off = SharedBytesMeta::compute_start_offset(align);
-            if dat_len != 0 && dat.off != start_off {
-                // there is a prefix of unwanted data, so we move it to the beginning
-
-                // SAFETY: `start_off` is the correct offset for the data, as determined by the alignment.
-                let dest = unsafe { dataptr.add(start_off as usize) };
-                // SAFETY: `dat.off` is the offset of the data in the buffer which is greater than `start_off` but regions may overlap.
-                let sorc = unsafe { dataptr.add(dat.off as usize) };
-                // SAFETY: `dat_len` is the length of the data in the buffer, `src` and `dst` are valid pointers but may overlap within `dat_len` bytes.
-                unsafe {
-                    dest.copy_from(sorc, dat_len as usize);
-                };
+    fn from(dat: SharedBytes) -> Self {
+        match dat.try_into_builder() {
+            Ok(builder) => builder,
+            Err(dat) => {
+                let mut ret = Self::with_alignment(dat.align());
+                ret.extend_from_slice(dat.as_slice());
+                ret
             }
-            core::mem::forget(dat);
-            Self {
-                align,
-                off: start_off + dat_len,
-                len,
-                dat: dataptr,
-            }
-        } else {
-            let mut ret = Self::with_alignment(align);
-            ret.extend_from_slice(dat.as_slice());
-            ret
         }
     }
 }
@@ -579,12 +1011,10 @@ impl Index<usize> for SharedBytes {
 
     #[inline]
     fn index(&self, index: usize) -> &Self::Output {
-        assert!(
-            index < self.len as usize,
-            "SharedBytes::index: index out of bounds"
-        );
+        let (off, len) = self.range();
+        assert!(index < len, "SharedBytes::index: index out of bounds");
         // SAFETY: `index` is checked to be in bounds.
-        let ptr = unsafe { self.dat().add(self.off as usize + index) };
+        let ptr = unsafe { self.dat().add(off + index) };
         // SAFETY: `ptr` is a valid pointer.
         unsafe { &*ptr }
     }
@@ -734,9 +1164,10 @@ impl Ord for SharedBytes {
 )]
 impl core::fmt::Debug for SharedBytes {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (off, len) = self.range();
         f.debug_struct("SharedBytes")
-            .field("len", &self.len)
-            .field("off", &self.off)
+            .field("len", &len)
+            .field("off", &off)
             .field("ptr", &self.dat())
             .field("dat", &crate::ByteStringRender::from_slice(self.as_slice()))
             .finish()
@@ -1,16 +1,22 @@
-use crate::{SharedBytes, SharedBytesMeta};
+use crate::{
+    shared_allocator::{self, AllocRef},
+    shared_bytes::MAX_ALLOC_LEN,
+    SharedAllocator, SharedBytes, SharedBytesMeta,
+};
 
 /// A builder for `SharedBytes`.
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub struct SharedBytesBuilder {
     /// The total capacity of the buffer.
-    pub(crate) len: u32,
+    pub(crate) len: usize,
     /// The offset of the first unused byte.
-    pub(crate) off: u32,
+    pub(crate) off: usize,
     /// Pointer to the heap buffer.
     pub(crate) dat: *mut u8,
     /// The alignment of the buffer.
     pub(crate) align: usize,
+    /// The allocator of the buffer, or `None` for the global allocator.
+    pub(crate) alloc: Option<AllocRef>,
 }
 
 #[allow(clippy::missing_fields_in_debug)]
@@ -25,6 +31,7 @@ impl core::fmt::Debug for SharedBytesBuilder {
             .field("len", &self.len())
             .field("capacity", &self.capacity())
             .field("align", &self.align)
+            .field("custom_alloc", &self.alloc.is_some())
             .finish()
     }
 }
@@ -38,16 +45,25 @@ impl SharedBytesBuilder {
     /// Creates a new `SharedBytesBuilder`.
     #[inline]
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub const fn new() -> Self {
         Self {
             len: 0,
-            off: core::mem::size_of::<SharedBytesMeta>() as u32,
+            off: core::mem::size_of::<SharedBytesMeta>(),
             dat: core::ptr::null_mut(),
             align: core::mem::align_of::<SharedBytesMeta>(),
+            alloc: None,
         }
     }
 
+    /// Creates a new `SharedBytesBuilder` which allocates its buffer using the given allocator.
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: &'static dyn SharedAllocator) -> Self {
+        let mut builder = Self::new();
+        builder.alloc = Some(alloc);
+        builder
+    }
+
     /// Creates a new `SharedBytesBuilder`.
     ///
     /// # Panics
@@ -76,21 +92,17 @@ impl SharedBytesBuilder {
             off,
             dat: core::ptr::null_mut(),
             align,
+            alloc: None,
         }
     }
 
     const fn cap_check(cap: usize, alignment: usize) -> usize {
-        const MAX_CAP: usize = if isize::BITS <= 32 {
-            isize::MAX as usize
-        } else {
-            0xFFFF_FFFF
-        };
         let alignment = if alignment < core::mem::align_of::<SharedBytesMeta>() {
             core::mem::align_of::<SharedBytesMeta>()
         } else {
             alignment
         };
-        let max_cap = MAX_CAP - SharedBytesMeta::compute_start_offset(alignment) as usize;
+        let max_cap = MAX_ALLOC_LEN - SharedBytesMeta::compute_start_offset(alignment);
         assert!(
             cap <= max_cap,
             "SharedBytesBuilder::with_aligned_capacity: capacity too large"
@@ -98,19 +110,35 @@ impl SharedBytesBuilder {
         alignment
     }
 
-    /// Creates a new `SharedBytesBuilder` with at least the specified capacity. The maximum capacity is `0xFFFF_FFF0` or `isize::MAX - 15`, whichever is lower.
+    /// Creates a new `SharedBytesBuilder` with at least the specified capacity. The maximum capacity is `0x00FF_FFFF_FFFF_FFF0` or `isize::MAX - 15`, whichever is lower.
+    ///
+    /// Buffers larger than 4 GiB are supported on 64-bit targets.
     #[inline]
     #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
     pub fn with_capacity(cap: usize) -> Self {
         if cap == 0 {
             return Self::new();
         }
         let align = Self::cap_check(cap, core::mem::align_of::<SharedBytesMeta>());
-        Self::with_capacity_u32(cap as u32, align)
+        Self::with_capacity_inner(cap, align, None)
     }
 
-    /// Creates a new `SharedBytesBuilder` with at least the specified capacity. The maximum capacity is `0xFFFF_FFF0 - align` or `isize::MAX - 15 - align`, whichever is lower.
+    /// Creates a new `SharedBytesBuilder` with at least the specified capacity, allocated by the given allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is too large.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_in(cap: usize, alloc: &'static dyn SharedAllocator) -> Self {
+        if cap == 0 {
+            return Self::new_in(alloc);
+        }
+        let align = Self::cap_check(cap, core::mem::align_of::<SharedBytesMeta>());
+        Self::with_capacity_inner(cap, align, Some(alloc))
+    }
+
+    /// Creates a new `SharedBytesBuilder` with at least the specified capacity. The maximum capacity is `0x00FF_FFFF_FFFF_FFF0 - align` or `isize::MAX - 15 - align`, whichever is lower.
     ///
     /// # Panics
     ///
@@ -128,40 +156,61 @@ impl SharedBytesBuilder {
             align2 == alignment,
             "SharedBytesBuilder::with_aligned_capacity: alignment must be a power of two"
         );
-        #[allow(clippy::cast_possible_truncation)]
-        let cap = cap as u32;
-        Self::with_capacity_u32(cap, align)
+        Self::with_capacity_inner(cap, align, None)
     }
 
-    fn with_capacity_u32(cap: u32, align: usize) -> Self {
+    /// Creates a new `SharedBytesBuilder` with at least the specified capacity and alignment, allocated by the given allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is too large or if the alignment is not a power of two or is greater than 512.
+    #[inline]
+    #[must_use]
+    pub fn with_aligned_capacity_in(
+        cap: usize,
+        alignment: usize,
+        alloc: &'static dyn SharedAllocator,
+    ) -> Self {
+        let mut builder = Self::with_alignment(alignment);
+        builder.alloc = Some(alloc);
+        if cap == 0 {
+            return builder;
+        }
+        let align = Self::cap_check(cap, builder.align);
+        Self::with_capacity_inner(cap, align, builder.alloc)
+    }
+
+    fn with_capacity_inner(cap: usize, align: usize, alloc: Option<AllocRef>) -> Self {
         let off = SharedBytesMeta::compute_start_offset(align);
         let len = cap + off;
-        #[allow(clippy::unwrap_used)]
-        let layout = core::alloc::Layout::from_size_align(len as usize, align).unwrap();
-        // SAFETY: the layout must be valid
-        let ptr = unsafe { alloc::alloc::alloc(layout) };
-        if ptr.is_null() {
-            alloc::alloc::handle_alloc_error(layout);
-        }
+        let ptr = shared_allocator::allocate(alloc, len, align);
         Self {
             len,
             off,
             dat: ptr,
             align,
+            alloc,
         }
     }
 
+    /// Returns the custom allocator used by this builder, or `None` if it uses the global allocator.
+    #[inline]
+    #[must_use]
+    pub const fn allocator(&self) -> Option<&'static dyn SharedAllocator> {
+        self.alloc
+    }
+
     #[cfg_attr(not(feature = "bytes_1"), allow(dead_code))]
     #[inline]
     pub(crate) fn reserve_extra(&mut self) {
-        let off = self.off as usize;
-        if off == 0xFFFF_FFFF {
+        let off = self.off;
+        if off >= MAX_ALLOC_LEN {
             return;
         }
         let new_len = if off >= 0x0000_8000 {
-            (off & 0xFFFF_8000)
+            (off & !0x0000_7FFF)
                 .saturating_add(0x0000_8000)
-                .min(0xFFFF_FFFF)
+                .min(MAX_ALLOC_LEN)
         } else {
             (off + 7).next_power_of_two()
         };
@@ -171,15 +220,15 @@ impl SharedBytesBuilder {
     /// Reserves capacity for at least `additional` more bytes to be written to the buffer.
     #[inline]
     pub fn reserve(&mut self, additional: usize) {
-        let off = self.off as usize;
-        if off == 0xFFFF_FFFF || additional == 0 {
+        let off = self.off;
+        if off >= MAX_ALLOC_LEN || additional == 0 {
             return;
         }
         let new_off = off.saturating_add(additional);
         let new_len = if new_off >= 0x0000_8000 {
-            (new_off & 0xFFFF_8000)
+            (new_off & !0x0000_7FFF)
                 .saturating_add(0x0000_8000)
-                .min(0xFFFF_FFFF)
+                .min(MAX_ALLOC_LEN)
         } else {
             new_off.next_power_of_two()
         };
@@ -187,50 +236,34 @@ impl SharedBytesBuilder {
     }
 
     pub(crate) fn reserve_exact(&mut self, new_len: usize) {
-        if new_len <= self.len as usize {
+        if new_len <= self.len {
             return;
         }
         let ptr = if self.len == 0 {
-            #[allow(clippy::unwrap_used)]
-            let layout = core::alloc::Layout::from_size_align(new_len, self.align).unwrap();
-            // SAFETY: the layout must be valid
-            let ptr = unsafe { alloc::alloc::alloc(layout) };
-            if ptr.is_null() {
-                alloc::alloc::handle_alloc_error(layout);
-            }
-            ptr
+            shared_allocator::allocate(self.alloc, new_len, self.align)
         } else {
-            let start_off = SharedBytesMeta::compute_start_offset(self.align) as usize;
-            #[allow(clippy::unwrap_used)]
-            let old_layout =
-                core::alloc::Layout::from_size_align(self.len as usize, self.align).unwrap();
-            // SAFETY: `old_layout` is the layout of the old allocation.
-            let mut ptr = unsafe { alloc::alloc::realloc(self.dat, old_layout, new_len) };
+            let start_off = SharedBytesMeta::compute_start_offset(self.align);
+            // SAFETY: `dat` is the old allocation of `len` bytes made with the same allocator and alignment.
+            let mut ptr = unsafe {
+                shared_allocator::reallocate(self.alloc, self.dat, self.len, self.align, new_len)
+            };
             if ptr.is_null() {
-                #[allow(clippy::unwrap_used)]
-                let layout = core::alloc::Layout::from_size_align(new_len, self.align).unwrap();
-                // SAFETY: the layout must be valid
-                ptr = unsafe { alloc::alloc::alloc(layout) };
-                if ptr.is_null() {
-                    alloc::alloc::handle_alloc_error(layout);
-                }
+                ptr = shared_allocator::allocate(self.alloc, new_len, self.align);
                 // SAFETY: `start_off` is always less than or equal to `len`.
                 let src = unsafe { self.dat.add(start_off) };
                 // SAFETY: `start_off` is always less than or equal to `len`.
                 let dst = unsafe { ptr.add(start_off) };
                 // SAFETY: `start_off` is always less than or equal to `len`.
                 unsafe {
-                    dst.copy_from_nonoverlapping(src, self.off as usize - start_off);
+                    dst.copy_from_nonoverlapping(src, self.off - start_off);
                 };
-                // SAFETY: `old_layout` is the layout of the old allocation.
+                // SAFETY: `dat` is the old allocation of `len` bytes made with the same allocator and alignment.
                 unsafe {
-                    alloc::alloc::dealloc(self.dat, old_layout);
+                    shared_allocator::deallocate(self.alloc, self.dat, self.len, self.align);
                 };
             }
             ptr
         };
-        #[allow(clippy::cast_possible_truncation)]
-        let new_len = new_len as u32;
         self.len = new_len;
         self.dat = ptr;
     }
@@ -243,22 +276,22 @@ impl SharedBytesBuilder {
     #[inline]
     pub fn extend_from_slice(&mut self, dat: &[u8]) {
         fn extend_from_slice_inner(builder: &mut SharedBytesBuilder, dat: &[u8]) {
-            let off = builder.off as usize;
+            let off = builder.off;
 
             #[allow(clippy::panic)]
             let new_off = match off.checked_add(dat.len()) {
-                Some(new_off) if new_off <= 0xFFFF_FFFF => new_off,
+                Some(new_off) if new_off <= MAX_ALLOC_LEN => new_off,
                 _ => {
                     panic!("SharedBytesBuilder::extend_from_slice: slice too large to append to existing data");
                 }
             };
 
             // reallocate if necessary
-            if new_off > builder.len as usize {
+            if new_off > builder.len {
                 let new_len = if new_off > 0x0000_8000 {
-                    (new_off & 0xFFFF_8000)
+                    (new_off & !0x0000_7FFF)
                         .saturating_add(0x0000_8000)
-                        .min(0xFFFF_FFFF)
+                        .min(MAX_ALLOC_LEN)
                 } else {
                     new_off.next_power_of_two()
                 };
@@ -273,8 +306,6 @@ impl SharedBytesBuilder {
             unsafe {
                 core::ptr::copy_nonoverlapping(dat.as_ptr(), dest, len);
             };
-            #[allow(clippy::cast_possible_truncation)]
-            let new_off = new_off as u32;
             builder.off = new_off;
         }
 
@@ -300,8 +331,7 @@ impl SharedBytesBuilder {
         if self.len == 0 {
             return;
         }
-        #[allow(clippy::cast_possible_truncation)]
-        let data_off = SharedBytesMeta::compute_start_offset(self.align).saturating_add(len as u32);
+        let data_off = SharedBytesMeta::compute_start_offset(self.align).saturating_add(len);
         if self.off > data_off {
             self.off = data_off;
         }
@@ -318,16 +348,15 @@ impl SharedBytesBuilder {
 
             let len = slf.len;
             let align = slf.align;
+            let alloc = slf.alloc;
             let data_off = SharedBytesMeta::compute_start_offset(align);
             let off = slf.off;
             let dat = slf.dat;
             if data_off == off {
                 if !dat.is_null() {
-                    #[allow(clippy::unwrap_used)]
-                    let layout = core::alloc::Layout::from_size_align(len as usize, align).unwrap();
-                    // SAFETY: the layout must have been allocated
+                    // SAFETY: the buffer must have been allocated with this allocator, length, and alignment.
                     unsafe {
-                        alloc::alloc::dealloc(dat, layout);
+                        shared_allocator::deallocate(alloc, dat, len, align);
                     };
                 }
                 return SharedBytes {
@@ -339,14 +368,8 @@ impl SharedBytesBuilder {
             #[allow(clippy::cast_ptr_alignment)]
             let meta = dat.cast::<SharedBytesMeta>();
             // SAFETY: `meta` is a valid pointer to a `SharedBytesMeta`.
-            unsafe { meta.write(INIT.with_align(align).with_len(len)) };
-            let dat_addr = dat as usize as u64;
-            let dat_addr = dat_addr.to_le();
-            SharedBytes {
-                len: off - data_off,
-                off: data_off,
-                dat_addr,
-            }
+            unsafe { meta.write(INIT.with_align(align).with_len(len).with_allocator(alloc)) };
+            SharedBytes::from_parts(dat, data_off, off - data_off)
         }
 
         let this = core::mem::ManuallyDrop::new(self);
@@ -369,7 +392,7 @@ impl SharedBytesBuilder {
             return 0;
         }
         let data_off = SharedBytesMeta::compute_start_offset(self.align);
-        (self.len - data_off) as usize
+        self.len - data_off
     }
 
     /// Returns the number of bytes currently written to in the buffer.
@@ -377,7 +400,7 @@ impl SharedBytesBuilder {
     #[must_use]
     pub const fn len(&self) -> usize {
         let data_off = SharedBytesMeta::compute_start_offset(self.align);
-        (self.off - data_off) as usize
+        self.off - data_off
     }
 
     /// Returns `true` if the buffer is empty.
@@ -403,8 +426,8 @@ impl SharedBytesBuilder {
             return &[];
         }
         // SAFETY: `data_off` is the start offset of the data.
-        let dat = unsafe { self.dat.add(data_off as usize) };
-        let len = (self.off - data_off) as usize;
+        let dat = unsafe { self.dat.add(data_off) };
+        let len = self.off - data_off;
         // SAFETY: `len` is the length of the data.
         unsafe { core::slice::from_raw_parts(dat, len) }
     }
@@ -417,8 +440,8 @@ impl SharedBytesBuilder {
         }
         let data_off = SharedBytesMeta::compute_start_offset(self.align);
         // SAFETY: `data_off` is the start offset of the data.
-        let dat = unsafe { self.dat.add(data_off as usize) };
-        let len = (self.off - data_off) as usize;
+        let dat = unsafe { self.dat.add(data_off) };
+        let len = self.off - data_off;
         // SAFETY: `len` is the length of the data.
         unsafe { core::slice::from_raw_parts_mut(dat, len) }
     }
@@ -438,11 +461,11 @@ impl SharedBytesBuilder {
         let data = if self.len == 0 || self.off == self.len {
             &mut [] as &mut [core::mem::MaybeUninit<u8>]
         } else {
-            let off = self.off as usize;
+            let off = self.off;
             // SAFETY: `off` is always less than or equal to `len`.
             let dat = unsafe { self.dat.add(off) };
             let dat = dat.cast::<core::mem::MaybeUninit<u8>>();
-            let len = self.len as usize - off;
+            let len = self.len - off;
             // SAFETY: `len` is the allocated length minus the start offset
             unsafe { core::slice::from_raw_parts_mut(dat, len) }
         };
@@ -451,10 +474,7 @@ impl SharedBytesBuilder {
             len <= data.len(),
             "SharedBytesBuilder::apply_unfilled: returned length exceeds reserved capacity"
         );
-        #[allow(clippy::cast_possible_truncation)]
-        {
-            self.off += len as u32;
-        };
+        self.off += len;
         ret
     }
 
@@ -474,11 +494,11 @@ impl SharedBytesBuilder {
         let data = if self.len == 0 || self.off == self.len {
             &mut [] as &mut [core::mem::MaybeUninit<u8>]
         } else {
-            let off = self.off as usize;
+            let off = self.off;
             // SAFETY: `off` is always less than or equal to `len`.
             let dat = unsafe { self.dat.add(off) };
             let dat = dat.cast::<core::mem::MaybeUninit<u8>>();
-            let len = self.len as usize - off;
+            let len = self.len - off;
             // SAFETY: `len` is the allocated length minus the start offset
             unsafe { core::slice::from_raw_parts_mut(dat, len) }
         };
@@ -489,10 +509,7 @@ impl SharedBytesBuilder {
             len <= maxlen,
             "SharedBytesBuilder::apply_unfilled_async: returned length exceeds reserved capacity"
         );
-        #[allow(clippy::cast_possible_truncation)]
-        {
-            self.off += len as u32;
-        };
+        self.off += len;
         ret
     }
 }
@@ -506,15 +523,13 @@ impl Default for SharedBytesBuilder {
 
 impl Drop for SharedBytesBuilder {
     #[inline]
-    #[allow(clippy::unwrap_used)]
     fn drop(&mut self) {
         if self.len == 0 {
             return;
         }
-        let layout = core::alloc::Layout::from_size_align(self.len as usize, self.align).unwrap();
         // SAFETY: `len` is the allocated length.
         unsafe {
-            alloc::alloc::dealloc(self.dat, layout);
+            shared_allocator::deallocate(self.alloc, self.dat, self.len, self.align);
         };
     }
 }
@@ -651,22 +666,20 @@ impl SharedBytesBuilder {
     where
         F: FnOnce(&mut core::io::BorrowedBuf<'this>) -> R,
     {
-        let off = self.off as usize;
+        let off = self.off;
         let mut bb = if self.len == 0 {
             core::io::BorrowedBuf::from(&mut [] as &mut [u8])
         } else {
             // SAFETY: `off` is always less than or equal to `len`.
             let data = unsafe { self.dat.add(off) };
             let data = data.cast::<core::mem::MaybeUninit<u8>>();
-            let len = self.len as usize - off;
+            let len = self.len - off;
             // SAFETY: `len` is the allocated length minus the start offset
             let data = unsafe { core::slice::from_raw_parts_mut(data, len) };
             core::io::BorrowedBuf::from(data)
         };
         let ret = fun(&mut bb);
-        #[allow(clippy::cast_possible_truncation)]
-        let len = bb.len() as u32;
-        self.len += len;
+        self.len += bb.len();
         ret
     }
 }
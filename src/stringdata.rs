@@ -6,7 +6,7 @@ use core::{
 #[cfg(feature = "alloc")]
 use alloc::{borrow::Cow, string::String, vec::Vec};
 
-use crate::ByteData;
+use crate::{ByteData, Pattern, Utf8CharError};
 
 #[cfg(feature = "alloc")]
 use crate::SharedBytes;
@@ -113,6 +113,53 @@ impl<'a> StringData<'a> {
         Ok(StringData { data: dat })
     }
 
+    /// Creates a `StringData` from `ByteData`, reporting where the data stops being valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns the input together with the position and length of the first invalid UTF-8 sequence.
+    #[inline]
+    pub const fn from_utf8(dat: ByteData<'a>) -> Result<Self, FromUtf8Error<'a>> {
+        match Utf8CharError::check(dat.as_slice()) {
+            Ok(()) => Ok(StringData { data: dat }),
+            Err(error) => Err(FromUtf8Error { data: dat, error }),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Creates a `StringData` from `ByteData`, replacing invalid UTF-8 sequences with `U+FFFD`.
+    ///
+    /// Valid input is returned as it is without copying, otherwise the data is copied into a new buffer.
+    #[inline]
+    #[must_use]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn from_utf8_lossy(dat: ByteData<'a>) -> Self {
+        match Utf8CharError::check(dat.as_slice()) {
+            Ok(()) => StringData { data: dat },
+            Err(error) => Self::replace_invalid(dat.as_slice(), error),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Copies `bytes` into a new buffer, replacing invalid UTF-8 sequences starting with `error` with `U+FFFD`.
+    fn replace_invalid(mut bytes: &[u8], mut error: Utf8CharError) -> Self {
+        let mut out = crate::SharedStrBuilder::with_capacity(bytes.len() + 2);
+        loop {
+            let (valid, rest) = bytes.split_at(error.valid_up_to);
+            // SAFETY: the bytes up to `valid_up_to` have been checked to be valid UTF-8.
+            out.push_str(unsafe { core::str::from_utf8_unchecked(valid) });
+            out.push(char::REPLACEMENT_CHARACTER);
+            bytes = &rest[usize::from(error.error_len)..];
+            match Utf8CharError::check(bytes) {
+                Ok(()) => break,
+                Err(next) => error = next,
+            }
+        }
+        // SAFETY: the remaining bytes have been checked to be valid UTF-8.
+        out.push_str(unsafe { core::str::from_utf8_unchecked(bytes) });
+        out.build_str()
+    }
+
     /// Creates a `StringData` from `ByteData`.
     ///
     /// # Safety
@@ -312,6 +359,17 @@ impl<'a> StringData<'a> {
         start..end
     }
 
+    /// Decodes the percent-encoded escape sequences of a URL component, see [`Percent::COMPONENT`](crate::codec::Percent::COMPONENT).
+    ///
+    /// Returns the same data without copying if nothing has to be decoded.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn percent_decode(&self) -> Self {
+        crate::codec::Percent::COMPONENT.decode(self.data.clone())
+    }
+
     /// Returns a `ByteData` with the given range of bytes.
     #[inline]
     #[must_use]
@@ -388,14 +446,13 @@ impl<'a> StringData<'a> {
     /// Returns the input if the `needle` is not found.
     #[inline]
     pub fn split_once_on(self, needle: &str) -> Result<(Self, Self), Self> {
-        let aa = match crate::const_split_once_bytes(self.as_bytes(), needle.as_bytes()) {
-            Some((aa, _)) => aa.len(),
-            None => return Err(self),
+        let Some(aa) = crate::search::find_bytes(self.as_bytes(), needle.as_bytes()) else {
+            return Err(self);
         };
         Ok(self.split_at(aa))
     }
 
-    /// Split the `StringData` at the first occurrence of the given str sequence.
+    /// Split the `StringData` on each occurrence of the given str sequence.
     #[inline]
     pub fn split_on<'b>(self, needle: &'b str) -> impl Iterator<Item = Self> + Send + 'b
     where
@@ -407,6 +464,88 @@ impl<'a> StringData<'a> {
             .map(|x| unsafe { Self::from_bytedata_unchecked(x) })
     }
 
+    /// Returns the byte position of the first match of the pattern.
+    #[inline]
+    pub fn find<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        crate::pattern::find_match(&mut searcher, self.as_bytes(), true).map(|(start, _)| start)
+    }
+
+    /// Returns the byte position of the last match of the pattern.
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        let mut searcher = pattern.into_searcher();
+        crate::pattern::rfind_match(&mut searcher, self.as_bytes(), true).map(|(start, _)| start)
+    }
+
+    /// Wraps a split of the inner `ByteData`, which only splits on char boundaries.
+    #[inline]
+    fn wrap_split<S: crate::pattern::Searcher>(
+        split: crate::pattern::SplitData<'a, S>,
+    ) -> impl Iterator<Item = Self> {
+        // SAFETY: the split only happens on char boundaries, so each item is valid UTF-8.
+        split.map(|x| unsafe { Self::from_bytedata_unchecked(x) })
+    }
+
+    /// Split the `StringData` on each match of the pattern.
+    #[inline]
+    pub fn split<P: Pattern>(self, pattern: P) -> impl Iterator<Item = Self> {
+        Self::wrap_split(crate::pattern::SplitData::new(
+            self.data,
+            pattern.into_searcher(),
+            usize::MAX,
+            true,
+        ))
+    }
+
+    /// Split the `StringData` on each match of the pattern, returning at most `max` items.
+    /// The last item contains the remaining data.
+    #[inline]
+    pub fn splitn<P: Pattern>(self, max: usize, pattern: P) -> impl Iterator<Item = Self> {
+        Self::wrap_split(crate::pattern::SplitData::new(
+            self.data,
+            pattern.into_searcher(),
+            max,
+            true,
+        ))
+    }
+
+    /// Split the `StringData` on each match of the pattern, starting from the end.
+    #[inline]
+    pub fn rsplit<P: Pattern>(self, pattern: P) -> impl Iterator<Item = Self> {
+        Self::wrap_split(
+            crate::pattern::SplitData::new(self.data, pattern.into_searcher(), usize::MAX, true)
+                .reversed(),
+        )
+    }
+
+    /// Split the `StringData` after each match of the pattern, keeping the match at the end of each item.
+    #[inline]
+    pub fn split_inclusive<P: Pattern>(self, pattern: P) -> impl Iterator<Item = Self> {
+        Self::wrap_split(
+            crate::pattern::SplitData::new(self.data, pattern.into_searcher(), usize::MAX, true)
+                .inclusive(),
+        )
+    }
+
+    /// Returns the string after the pattern if it matches at the start.
+    #[inline]
+    #[must_use]
+    pub fn strip_prefix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = crate::pattern::prefix_match(&mut searcher, self.as_bytes(), true)?;
+        Some(self.sliced(len..))
+    }
+
+    /// Returns the string before the pattern if it matches at the end.
+    #[inline]
+    #[must_use]
+    pub fn strip_suffix<P: Pattern>(&self, pattern: P) -> Option<Self> {
+        let mut searcher = pattern.into_searcher();
+        let len = crate::pattern::suffix_match(&mut searcher, self.as_bytes(), true)?;
+        Some(self.sliced(..self.len() - len))
+    }
+
     #[cfg(feature = "alloc")]
     /// Transform any borrowed data into shared data. This is useful when you wish to change the lifetime of the data.
     #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
@@ -476,6 +615,70 @@ impl Deref for StringData<'_> {
     }
 }
 
+/// The error returned by [`StringData::from_utf8`] when the data is not valid UTF-8.
+///
+/// The error holds on to the input, which can be recovered with [`FromUtf8Error::into_bytedata`].
+#[derive(Debug, Clone)]
+pub struct FromUtf8Error<'a> {
+    data: ByteData<'a>,
+    error: Utf8CharError,
+}
+
+impl<'a> FromUtf8Error<'a> {
+    /// Returns the position and length of the first invalid UTF-8 sequence.
+    #[inline]
+    #[must_use]
+    pub const fn utf8_error(&self) -> Utf8CharError {
+        self.error
+    }
+
+    /// Returns the index in the input up to which valid UTF-8 was verified.
+    #[inline]
+    #[must_use]
+    pub const fn valid_up_to(&self) -> usize {
+        self.error.valid_up_to
+    }
+
+    /// Returns the input that was not valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// Returns the input that was not valid UTF-8.
+    #[inline]
+    #[must_use]
+    pub fn into_bytedata(self) -> ByteData<'a> {
+        self.data
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Converts the input into a `StringData`, replacing invalid UTF-8 sequences with `U+FFFD`.
+    #[inline]
+    #[must_use]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn into_lossy(self) -> StringData<'static> {
+        StringData::replace_invalid(self.data.as_slice(), self.error)
+    }
+}
+
+impl core::fmt::Display for FromUtf8Error<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for FromUtf8Error<'_> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 impl<'a> From<&'a str> for StringData<'a> {
     #[inline]
     fn from(dat: &'a str) -> Self {
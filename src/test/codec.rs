@@ -0,0 +1,321 @@
+use crate::codec::{Base64, Hex};
+
+#[test]
+fn test_hex_chunk() {
+    assert_eq!(
+        Hex::LOWER.encode_chunk(&[0x01, 0xAB, 0xFF]).as_deref(),
+        Some(b"01abff".as_slice())
+    );
+    assert_eq!(
+        Hex::UPPER.encode_chunk(&[0x01, 0xAB, 0xFF]).as_deref(),
+        Some(b"01ABFF".as_slice())
+    );
+    assert!(Hex::LOWER.encode_chunk(&[0; 7]).is_some());
+    assert!(Hex::LOWER.encode_chunk(&[0; 8]).is_none());
+}
+
+#[test]
+fn test_base64_chunk() {
+    static CASES: &[(&[u8], &[u8], &[u8])] = &[
+        (b"", b"", b""),
+        (b"f", b"Zg==", b"Zg"),
+        (b"fo", b"Zm8=", b"Zm8"),
+        (b"foo", b"Zm9v", b"Zm9v"),
+        (b"foob", b"Zm9vYg==", b"Zm9vYg"),
+        (b"fooba", b"Zm9vYmE=", b"Zm9vYmE"),
+        (b"foobar", b"Zm9vYmFy", b"Zm9vYmFy"),
+    ];
+    for &(input, padded, unpadded) in CASES {
+        assert_eq!(
+            Base64::STANDARD.encode_chunk(input).as_deref(),
+            Some(padded),
+            "input: {input:?}"
+        );
+        assert_eq!(
+            Base64::STANDARD_NO_PAD.encode_chunk(input).as_deref(),
+            Some(unpadded),
+            "input: {input:?}"
+        );
+        assert_eq!(Base64::STANDARD.encoded_len(input.len()), padded.len());
+        assert_eq!(
+            Base64::URL_SAFE_NO_PAD.encoded_len(input.len()),
+            unpadded.len()
+        );
+    }
+    assert_eq!(
+        Base64::STANDARD.encode_chunk(b"\xfb\xff\xbf").as_deref(),
+        Some(b"+/+/".as_slice())
+    );
+    assert_eq!(
+        Base64::URL_SAFE.encode_chunk(b"\xfb\xff\xbf").as_deref(),
+        Some(b"-_-_".as_slice())
+    );
+    assert!(Base64::STANDARD.encode_chunk(&[0; 10]).is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_codec_roundtrip() {
+    let mut input = [0_u8; 300];
+    let mut value = 0_u8;
+    for byte in &mut input {
+        *byte = value;
+        value = value.wrapping_add(7);
+    }
+    for len in [0, 1, 2, 3, 4, 5, 6, 7, 10, 11, 47, 48, 49, 100, 300] {
+        let input = &input[..len];
+        for codec in [
+            Base64::STANDARD,
+            Base64::STANDARD_NO_PAD,
+            Base64::URL_SAFE,
+            Base64::URL_SAFE_NO_PAD,
+        ] {
+            let encoded = codec.encode(input);
+            assert_eq!(encoded.len(), codec.encoded_len(len), "len: {len}");
+            assert_eq!(codec.decode(&encoded).as_deref(), Ok(input), "len: {len}");
+        }
+        let encoded = Hex::UPPER.encode(input);
+        assert_eq!(encoded.len(), len * 2);
+        assert_eq!(Hex::LOWER.decode(encoded).as_deref(), Ok(input));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_codec_into_builder() {
+    let mut out = crate::SharedStrBuilder::new();
+    out.push_str("sha256:");
+    Hex::LOWER.encode_into(b"\x12\x34", &mut out);
+    out.push(';');
+    Base64::URL_SAFE_NO_PAD.encode_into(b"\xff\xfe", &mut out);
+    assert_eq!(out.as_str(), "sha256:1234;__4");
+
+    let mut bytes = crate::SharedBytesBuilder::new();
+    Base64::STANDARD.encode_into_bytes(b"hi", &mut bytes);
+    assert_eq!(bytes.as_slice(), b"aGk=");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_codec_decode_errors() {
+    use crate::codec::DecodeError;
+
+    assert_eq!(
+        Hex::LOWER.decode("abc"),
+        Err(DecodeError::InvalidLength { length: 3 })
+    );
+    assert_eq!(
+        Hex::LOWER.decode("0g"),
+        Err(DecodeError::InvalidByte {
+            position: 1,
+            byte: b'g'
+        })
+    );
+    assert_eq!(
+        Hex::LOWER.decode("AbCd").as_deref(),
+        Ok(b"\xab\xcd".as_slice())
+    );
+
+    assert_eq!(
+        Base64::STANDARD.decode("Zg"),
+        Err(DecodeError::InvalidPadding { position: 2 })
+    );
+    assert_eq!(
+        Base64::STANDARD_NO_PAD.decode("Zg=="),
+        Err(DecodeError::InvalidPadding { position: 2 })
+    );
+    assert_eq!(
+        Base64::STANDARD.decode("Zg==Zg=="),
+        Err(DecodeError::InvalidPadding { position: 4 })
+    );
+    assert_eq!(
+        Base64::STANDARD.decode("Z==="),
+        Err(DecodeError::InvalidPadding { position: 1 })
+    );
+    assert_eq!(
+        Base64::STANDARD_NO_PAD.decode("Zm9vY"),
+        Err(DecodeError::InvalidLength { length: 5 })
+    );
+    assert_eq!(
+        Base64::STANDARD.decode("Zh=="),
+        Err(DecodeError::InvalidLastSymbol { position: 1 })
+    );
+    assert_eq!(
+        Base64::STANDARD.decode("-_8="),
+        Err(DecodeError::InvalidByte {
+            position: 0,
+            byte: b'-'
+        })
+    );
+    assert_eq!(
+        Base64::URL_SAFE
+            .decode(crate::StringData::from_static("-_8="))
+            .as_deref(),
+        Ok(b"\xfb\xff".as_slice())
+    );
+}
+
+#[cfg(all(feature = "alloc", feature = "queue"))]
+#[test]
+fn test_codec_decode_queue() {
+    let encoded = Base64::STANDARD.encode(b"a queue split at every possible position");
+    let hex = Hex::LOWER.encode(b"queue");
+    for chunk_len in 1..8 {
+        let mut queue = crate::ByteQueue::new();
+        for chunk in encoded.as_bytes().chunks(chunk_len) {
+            queue.push_back(chunk);
+        }
+        assert_eq!(
+            Base64::STANDARD.decode_queue(&queue).as_deref(),
+            Ok(b"a queue split at every possible position".as_slice())
+        );
+
+        let mut hex_queue = crate::ByteQueue::new();
+        for chunk in hex.as_bytes().chunks(chunk_len) {
+            hex_queue.push_back(chunk);
+        }
+        assert_eq!(
+            Hex::LOWER.decode_queue(&hex_queue).as_deref(),
+            Ok(b"queue".as_slice())
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_percent_decode() {
+    use crate::codec::Percent;
+    use crate::StringData;
+
+    let input = "/path/without-escapes+plus";
+    let decoded = StringData::from_borrowed(input).percent_decode();
+    assert_eq!(decoded.as_borrowed().map(str::as_ptr), Some(input.as_ptr()));
+
+    assert_eq!(
+        StringData::from_static("a%20b+c%2Fd")
+            .percent_decode()
+            .as_str(),
+        "a b+c/d"
+    );
+    assert_eq!(
+        Percent::FORM.decode(b"a%20b+c%2fd".as_slice()).as_str(),
+        "a b c/d"
+    );
+    assert_eq!(
+        Percent::COMPONENT
+            .decode(b"100%, %zz, %4".as_slice())
+            .as_str(),
+        "100%, %zz, %4"
+    );
+    assert_eq!(
+        Percent::COMPONENT
+            .decode(b"%E2%9C%93 caf%C3%A9 and some more text".as_slice())
+            .as_str(),
+        "\u{2713} caf\u{e9} and some more text"
+    );
+    assert_eq!(
+        Percent::COMPONENT.decode(b"%FFok%C3".as_slice()).as_str(),
+        "\u{FFFD}ok\u{FFFD}"
+    );
+    assert_eq!(
+        Percent::COMPONENT
+            .decode(b"raw\xffbytes".as_slice())
+            .as_str(),
+        "raw\u{FFFD}bytes"
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_percent_encode() {
+    use crate::codec::Percent;
+
+    assert_eq!(
+        Percent::COMPONENT.encode(b"a b+c/d~*").as_str(),
+        "a%20b%2Bc%2Fd~%2A"
+    );
+    assert_eq!(
+        Percent::FORM.encode(b"a b+c/d~*").as_str(),
+        "a+b%2Bc%2Fd%7E*"
+    );
+    let encoded = Percent::FORM.encode("caf\u{e9} \u{2713}".as_bytes());
+    assert_eq!(encoded.as_str(), "caf%C3%A9+%E2%9C%93");
+    assert_eq!(Percent::FORM.decode(encoded).as_str(), "caf\u{e9} \u{2713}");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_form_pairs() {
+    use crate::codec::FormPairs;
+
+    let input = b"a=1&&b=two+words&c&=empty&d=x%3Dy=z&";
+    let pairs = FormPairs::new(input.as_slice()).collect::<alloc::vec::Vec<_>>();
+    assert_eq!(
+        pairs
+            .iter()
+            .map(|pair| (pair.0.as_str(), pair.1.as_str()))
+            .collect::<alloc::vec::Vec<_>>(),
+        [
+            ("a", "1"),
+            ("b", "two words"),
+            ("c", ""),
+            ("", "empty"),
+            ("d", "x=y=z"),
+        ]
+    );
+
+    // names and values longer than a chunk borrow from the input
+    let long = b"a_long_field_name=and_a_long_field_value&next=1";
+    let Some((name, value)) = FormPairs::new(long.as_slice()).next() else {
+        unreachable!("the input has pairs");
+    };
+    assert_eq!(name.as_str(), "a_long_field_name");
+    assert_eq!(name.as_borrowed().map(str::as_ptr), Some(long.as_ptr()));
+    assert_eq!(
+        value.as_borrowed().map(str::as_ptr),
+        Some(long[18..].as_ptr())
+    );
+    assert_eq!(FormPairs::new(b"".as_slice()).count(), 0);
+}
+
+#[cfg(all(feature = "alloc", feature = "queue"))]
+#[test]
+fn test_form_queue_pairs() {
+    use crate::codec::{FormQueuePairs, FormSerializer};
+    use crate::{ByteQueue, StringData, StringQueue};
+
+    let mut queue = ByteQueue::new();
+    queue.push_back(b"first_field_name=one&sec".as_slice());
+    queue.push_back(b"ond=t%77o&third=".as_slice());
+    queue.push_back(b"3+3".as_slice());
+    let pairs = FormQueuePairs::new(&queue).collect::<alloc::vec::Vec<_>>();
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(
+        (pairs[0].0.as_str(), pairs[0].1.as_str()),
+        ("first_field_name", "one")
+    );
+    assert_eq!(
+        pairs[0].0.as_borrowed().map(str::as_ptr),
+        queue.front().map(|chunk| chunk.as_slice().as_ptr())
+    );
+    assert_eq!(
+        (pairs[1].0.as_str(), pairs[1].1.as_str()),
+        ("second", "two")
+    );
+    assert_eq!((pairs[2].0.as_str(), pairs[2].1.as_str()), ("third", "3 3"));
+
+    let mut out = StringQueue::new();
+    out.push_back("?");
+    FormSerializer::new(&mut out)
+        .append_pair("first", "one")
+        .append_pair("second", StringData::from_static("two words & more"));
+    assert_eq!(out.chunk_len(), 9);
+    assert_eq!(
+        StringData::from(out).as_str(),
+        "?&first=one&second=two+words+%26+more"
+    );
+
+    let mut empty = StringQueue::new();
+    FormSerializer::new(&mut empty).append_pair("k", "v");
+    assert_eq!(StringData::from(empty).as_str(), "k=v");
+}
@@ -0,0 +1,149 @@
+use crate::{ByteCursor, ByteData, UnexpectedEnd};
+
+#[test]
+fn test_cursor_reads() {
+    let mut cursor = ByteCursor::new(ByteData::from_static(
+        b"\x01\xFF\x00\x02\x03\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x3F\x80\x00\x00",
+    ));
+    assert_eq!(cursor.len(), 20);
+    assert_eq!(cursor.peek_u8(), Ok(1));
+    assert_eq!(cursor.read_u8(), Ok(1));
+    assert_eq!(cursor.read_i8(), Ok(-1));
+    assert_eq!(cursor.peek_u16(), Ok(2));
+    assert_eq!(cursor.read_u16_le(), Ok(0x0200));
+    assert_eq!(cursor.read_u32_le(), Ok(3));
+    assert_eq!(cursor.position(), 8);
+    assert_eq!(cursor.read_i64(), Ok(4));
+    assert_eq!(cursor.peek_f32(), Ok(1.0));
+    assert_eq!(cursor.remaining(), 4);
+    assert_eq!(
+        cursor.read_u64(),
+        Err(UnexpectedEnd {
+            needed: 8,
+            remaining: 4
+        })
+    );
+    assert_eq!(cursor.position(), 16);
+    assert_eq!(cursor.read_f32(), Ok(1.0));
+    assert_eq!(
+        cursor.read_u8(),
+        Err(UnexpectedEnd {
+            needed: 1,
+            remaining: 0
+        })
+    );
+
+    cursor.rewind();
+    assert_eq!(cursor.read_array::<3>(), Ok([1, 0xFF, 0]));
+    cursor.set_position(100);
+    assert_eq!(cursor.remaining(), 0);
+    assert_eq!(
+        cursor.read_u8(),
+        Err(UnexpectedEnd {
+            needed: 1,
+            remaining: 0
+        })
+    );
+    assert_eq!(cursor.read_bytes(0).map(|data| data.len()), Ok(0));
+}
+
+#[test]
+fn test_cursor_read_bytes() {
+    let input: &[u8] = b"a header, then a body long enough to share";
+    let mut cursor = ByteCursor::new(ByteData::from_borrowed(input));
+    assert_eq!(cursor.advance(2), Ok(()));
+    let Ok(bytes) = cursor.read_bytes(30) else {
+        unreachable!("enough bytes are left");
+    };
+    assert_eq!(bytes.as_slice(), &input[2..32]);
+    assert_eq!(bytes.as_slice().as_ptr(), input[2..].as_ptr());
+    assert_eq!(cursor.remaining_data().as_slice(), &input[32..]);
+    assert_eq!(
+        cursor.read_bytes(11).map(|data| data.len()),
+        Err(UnexpectedEnd {
+            needed: 11,
+            remaining: 10
+        })
+    );
+    assert_eq!(cursor.position(), 32);
+    assert_eq!(
+        cursor.advance(11),
+        Err(UnexpectedEnd {
+            needed: 11,
+            remaining: 10
+        })
+    );
+}
+
+#[cfg(all(feature = "queue", feature = "alloc"))]
+#[test]
+fn test_cursor_queue() {
+    let first: &[u8] = b"\x00\x00\x01the first chunk holds a frame\x00";
+    let second: &[u8] = b"\x02\x7F and the second chunk holds the rest";
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(first);
+    queue.push_back(second);
+    let mut cursor = ByteCursor::from_queue(queue);
+    assert_eq!(cursor.len(), first.len() + second.len());
+
+    assert_eq!(cursor.read_u16(), Ok(0));
+    assert_eq!(cursor.advance(1), Ok(()));
+    let Ok(bytes) = cursor.read_bytes(27) else {
+        unreachable!("enough bytes are left");
+    };
+    assert_eq!(bytes.as_slice(), b"the first chunk holds a fra");
+    assert_eq!(bytes.as_slice().as_ptr(), first[3..].as_ptr());
+
+    // reads across the chunk boundary
+    assert_eq!(cursor.peek_u32(), Ok(0x6D65_0002));
+    assert_eq!(cursor.advance(3), Ok(()));
+    assert_eq!(cursor.read_u16(), Ok(0x027F));
+    cursor.set_position(30);
+    let Ok(across) = cursor.read_bytes(20) else {
+        unreachable!("enough bytes are left");
+    };
+    assert_eq!(across.as_slice(), b"me\x00\x02\x7F and the second");
+    let Ok(queued) = cursor.read_queue(4) else {
+        unreachable!("enough bytes are left");
+    };
+    assert_eq!(queued, b" chu".as_slice());
+
+    cursor.rewind();
+    let Ok(all) = cursor.read_queue(cursor.remaining()) else {
+        unreachable!("enough bytes are left");
+    };
+    assert_eq!(all.chunk_len(), 2);
+    assert_eq!(
+        cursor.read_u8(),
+        Err(UnexpectedEnd {
+            needed: 1,
+            remaining: 0
+        })
+    );
+    assert_eq!(cursor.read_queue(0).map(|empty| empty.len()), Ok(0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[allow(clippy::std_instead_of_core)]
+fn test_cursor_seek() {
+    use std::io::{BufRead, Read, Seek, SeekFrom};
+
+    let mut cursor = ByteCursor::new(ByteData::from_static(b"0123456789"));
+    assert_eq!(cursor.seek(SeekFrom::End(-3)).ok(), Some(7));
+    assert_eq!(cursor.read_u8(), Ok(b'7'));
+    assert_eq!(cursor.seek(SeekFrom::Current(-5)).ok(), Some(3));
+    assert_eq!(cursor.fill_buf().ok(), Some(b"3456789".as_slice()));
+    cursor.consume(2);
+    let mut buf = [0_u8; 8];
+    assert_eq!(cursor.read(&mut buf).ok(), Some(5));
+    assert_eq!(&buf[..5], b"56789");
+    assert_eq!(
+        cursor
+            .seek(SeekFrom::Current(-20))
+            .map_err(|err| err.kind()),
+        Err(std::io::ErrorKind::InvalidInput)
+    );
+    assert_eq!(cursor.seek(SeekFrom::Start(20)).ok(), Some(20));
+    assert_eq!(cursor.read(&mut buf).ok(), Some(0));
+}
@@ -0,0 +1,247 @@
+use crate::framing::{
+    Decoder, DelimiterCodec, Encoder, FrameError, LengthDelimitedCodec, LinesCodec,
+};
+use crate::{ByteData, ByteQueue, StringData};
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_length_delimited() {
+    let mut codec = LengthDelimitedCodec::new();
+    let payload = b"a payload long enough to be shared";
+    let mut queue = ByteQueue::new();
+    codec
+        .encode(ByteData::from_static(payload), &mut queue)
+        .unwrap();
+    assert_eq!(queue.len(), payload.len() + 4);
+    assert!(queue.starts_with(&[0, 0, 0, 34]));
+
+    // the frame arrives in pieces and is only decoded once complete
+    let mut input = ByteQueue::new();
+    let mut encoded = queue.into_iter();
+    input.push_back(encoded.next().unwrap().sliced(..2));
+    assert!(codec.decode(&mut input).unwrap().is_none());
+    input.push_back(&[0_u8, 34][..]);
+    assert!(codec.decode(&mut input).unwrap().is_none());
+    input.push_back(encoded.next().unwrap());
+    input.push_back(&[0_u8][..]);
+    let frame = codec.decode(&mut input).unwrap().unwrap();
+    assert_eq!(frame, payload.as_slice());
+    assert_eq!(
+        frame.front().map(|chunk| chunk.as_slice().as_ptr()),
+        Some(payload.as_ptr())
+    );
+    assert_eq!(input.len(), 1);
+    assert!(matches!(
+        codec.decode_eof(&mut input),
+        Err(FrameError::Incomplete { remaining: 1 })
+    ));
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_length_delimited_options() {
+    let mut codec = LengthDelimitedCodec::new()
+        .with_length_size(2)
+        .little_endian()
+        .with_max_frame_length(4);
+    let mut queue = ByteQueue::new();
+    codec
+        .encode(ByteData::from_static(b"abc"), &mut queue)
+        .unwrap();
+    assert_eq!(queue, b"\x03\x00abc".as_slice());
+    assert!(matches!(
+        codec.encode(ByteData::from_static(b"abcde"), &mut queue),
+        Err(FrameError::TooLong { length: 5, max: 4 })
+    ));
+    assert_eq!(
+        codec.decode(&mut queue).unwrap().unwrap(),
+        b"abc".as_slice()
+    );
+    assert!(codec.decode_eof(&mut queue).unwrap().is_none());
+
+    queue.push_back(b"\x05\x00abcde".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::TooLong { length: 5, max: 4 })
+    ));
+
+    let mut short = LengthDelimitedCodec::new().with_length_size(1);
+    let mut out = ByteQueue::new();
+    assert!(matches!(
+        short.encode(ByteData::from_static(&[0; 256]), &mut out),
+        Err(FrameError::TooLong {
+            length: 256,
+            max: 255
+        })
+    ));
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_lines() {
+    let mut codec = LinesCodec::new();
+    let mut queue = ByteQueue::new();
+    queue.push_back(b"first line\r\nsecond ".as_slice());
+    queue.push_back(b"line\n\ncaf\xC3".as_slice());
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "first line");
+    assert!(queue.is_empty());
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "second line");
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "");
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    queue.push_back(b"\xA9".as_slice());
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    assert_eq!(codec.buffered().len(), 5);
+    assert_eq!(codec.decode_eof(&mut queue).unwrap().unwrap(), "caf\u{e9}");
+    assert!(codec.decode_eof(&mut queue).unwrap().is_none());
+
+    queue.push_back(b"bad \xFF\n".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::InvalidUtf8(_))
+    ));
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_lines_max_length() {
+    let mut codec = LinesCodec::new().with_max_length(4);
+    let mut queue = ByteQueue::new();
+    queue.push_back(b"12345".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::TooLong { length: 5, max: 4 })
+    ));
+    queue.push_back(b"678\nok\n".as_slice());
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "ok");
+    queue.push_back(b"toolong\n".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::TooLong { length: 7, max: 4 })
+    ));
+    assert!(codec.decode_eof(&mut queue).unwrap().is_none());
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_lines_max_length_split_crlf() {
+    let mut codec = LinesCodec::new().with_max_length(5);
+    let mut queue = ByteQueue::new();
+    queue.push_back(b"abcde\r".as_slice());
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    queue.push_back(b"\n".as_slice());
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "abcde");
+
+    queue.push_back(b"abcdef\r".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::TooLong { length: 6, max: 5 })
+    ));
+    queue.push_back(b"\nok\n".as_slice());
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), "ok");
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_lines_encode() {
+    let mut codec = LinesCodec::new();
+    let mut queue = ByteQueue::new();
+    codec.encode("hello", &mut queue).unwrap();
+    codec
+        .encode(StringData::from_static("world"), &mut queue)
+        .unwrap();
+    assert_eq!(queue, b"hello\nworld\n".as_slice());
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_delimiter() {
+    let mut codec = DelimiterCodec::new(b"\r\n\r\n".as_slice());
+    let mut queue = ByteQueue::new();
+    queue.push_back(b"head one\r\n".as_slice());
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    queue.push_back(b"\r".as_slice());
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    queue.push_back(b"\nhead two\r\n\r\ntail".as_slice());
+    assert_eq!(
+        codec.decode(&mut queue).unwrap().unwrap(),
+        b"head one".as_slice()
+    );
+    assert_eq!(
+        codec.decode(&mut queue).unwrap().unwrap(),
+        b"head two".as_slice()
+    );
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    assert_eq!(
+        codec.decode_eof(&mut queue).unwrap().unwrap(),
+        b"tail".as_slice()
+    );
+    assert!(codec.decode_eof(&mut queue).unwrap().is_none());
+
+    let mut nul = DelimiterCodec::new(b"\0".as_slice()).with_max_length(3);
+    let mut out = ByteQueue::new();
+    nul.encode(ByteData::from_static(b"abc"), &mut out).unwrap();
+    assert_eq!(out, b"abc\0".as_slice());
+    out.push_back(b"abcd".as_slice());
+    assert_eq!(nul.decode(&mut out).unwrap().unwrap(), b"abc".as_slice());
+    assert!(matches!(
+        nul.decode(&mut out),
+        Err(FrameError::TooLong { length: 4, max: 3 })
+    ));
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_delimiter_skips_too_long_frame() {
+    let mut codec = DelimiterCodec::new(b"\r\n".as_slice()).with_max_length(4);
+    let mut queue = ByteQueue::new();
+    queue.push_back(b"too long".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::TooLong { length: 7, max: 4 })
+    ));
+    queue.push_back(b" still\r".as_slice());
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+    queue.push_back(b"\nok\r\nlonger one\r\nend\r\n".as_slice());
+    assert_eq!(codec.decode(&mut queue).unwrap().unwrap(), b"ok".as_slice());
+    assert!(matches!(
+        codec.decode(&mut queue),
+        Err(FrameError::TooLong { length: 10, max: 4 })
+    ));
+    assert_eq!(
+        codec.decode(&mut queue).unwrap().unwrap(),
+        b"end".as_slice()
+    );
+    assert!(codec.decode(&mut queue).unwrap().is_none());
+
+    queue.push_back(b"discarded at the end".as_slice());
+    assert!(codec.decode(&mut queue).ok().is_none());
+    assert!(codec.decode_eof(&mut queue).unwrap().is_none());
+    assert!(queue.is_empty());
+}
+
+#[cfg(feature = "tokio-util_07")]
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_tokio_codec() {
+    use ::tokio_util_07::codec::{Decoder as _, Encoder as _};
+
+    let mut codec = crate::framing::TokioCodec::new(LinesCodec::new());
+    let mut buf = ::bytes_1::BytesMut::new();
+    codec.encode("hello", &mut buf).unwrap();
+    codec.encode("world", &mut buf).unwrap();
+    buf.extend_from_slice(b"partial");
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), "hello");
+    assert!(buf.is_empty());
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), "world");
+    assert!(codec.decode(&mut buf).unwrap().is_none());
+    assert_eq!(codec.decode_eof(&mut buf).unwrap().unwrap(), "partial");
+    assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+
+    let mut length = crate::framing::TokioCodec::new(LengthDelimitedCodec::new());
+    buf.extend_from_slice(&[0, 0, 0, 3, b'a']);
+    assert!(length.decode(&mut buf).unwrap().is_none());
+    assert!(matches!(
+        length.decode_eof(&mut buf),
+        Err(FrameError::Incomplete { remaining: 5 })
+    ));
+}
@@ -5,6 +5,17 @@ mod shared_bytes_builder;
 
 mod stringdata;
 
+mod codec;
+
+mod cursor;
+
+mod wire;
+
+mod utf8_stream;
+
+#[cfg(all(feature = "queue", feature = "alloc"))]
+mod framing;
+
 #[cfg(feature = "macros")]
 mod macros;
 
@@ -14,6 +25,15 @@ mod queue;
 #[cfg(feature = "bytes_1")]
 mod bytes_1;
 
+#[cfg(all(feature = "serde_1", feature = "queue", feature = "alloc"))]
+mod serde_1;
+
+#[cfg(all(feature = "serde_1", feature = "alloc"))]
+mod serde_helpers;
+
+#[cfg(all(feature = "serde_1", feature = "interning"))]
+mod serde_interned;
+
 #[test]
 fn next_char_test() {
     use crate::const_utf8_char_next;
@@ -23,3 +43,25 @@ fn next_char_test() {
     assert_eq!(const_utf8_char_next("€".as_bytes()), ('€' as u32, 3));
     assert_eq!(const_utf8_char_next("𐍈".as_bytes()), ('𐍈' as u32, 4));
 }
+
+#[cfg(any(feature = "tokio_1", feature = "futures-io_03"))]
+struct NoopWaker;
+
+#[cfg(any(feature = "tokio_1", feature = "futures-io_03"))]
+impl alloc::task::Wake for NoopWaker {
+    fn wake(self: alloc::sync::Arc<Self>) {}
+}
+
+/// Polls a future that is expected to complete without waiting.
+#[cfg(any(feature = "tokio_1", feature = "futures-io_03"))]
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) fn block_on<F: core::future::Future>(fut: F) -> F::Output {
+    let waker = core::task::Waker::from(alloc::sync::Arc::new(NoopWaker));
+    let mut cx = core::task::Context::from_waker(&waker);
+    let mut fut = core::pin::pin!(fut);
+    loop {
+        if let core::task::Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+    }
+}
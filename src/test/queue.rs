@@ -99,3 +99,220 @@ fn byte_queue_append_test() {
     }
     assert_eq!(i, A_DATA.len() + B_DATA.len());
 }
+
+#[test]
+fn byte_queue_find_slice_across_chunks() {
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(b"GET / HTTP/1.1\r".as_slice());
+    queue.push_back(b"\nHost: a\r\n".as_slice());
+    queue.push_back(b"\r".as_slice());
+    queue.push_back(b"\nbody\r\n\r\n".as_slice());
+
+    assert_eq!(queue.find_slice(b"\r\n\r\n"), Some(23));
+    assert_eq!(queue.find_slice_after(b"\r\n\r\n", 24), Some(31));
+    assert_eq!(queue.find_slice(b"1.1\r\nHost"), Some(11));
+    assert_eq!(queue.find_slice(b"\r\n\r\nbody"), Some(23));
+    assert_eq!(queue.find_slice(b"\r\n\r\n\r\n"), None);
+    assert_eq!(queue.find_slice(b"body\r\n\r\n!"), None);
+    assert_eq!(queue.find_slice(b"H"), Some(6));
+    assert_eq!(queue.find_slice_after(b"H", 7), Some(16));
+}
+
+#[test]
+fn byte_queue_find_slice_matches_contiguous() {
+    static DATA: &[u8] = b"abaabababbaab--aab--ab--b-a--bab";
+    static NEEDLES: &[&[u8]] = &[
+        b"a", b"ab", b"aab", b"--", b"--b", b"abab", b"ba--b", b"bab", b"-a-",
+    ];
+    for chunk_len in 1..6 {
+        let mut queue = crate::ByteQueue::new();
+        for chunk in DATA.chunks(chunk_len) {
+            queue.push_back(chunk);
+        }
+        for needle in NEEDLES {
+            for start in 0..DATA.len() {
+                let expected =
+                    crate::search::find_bytes(&DATA[start..], needle).map(|pos| pos + start);
+                assert_eq!(
+                    queue.find_slice_after(needle, start),
+                    expected,
+                    "chunk_len: {chunk_len}, needle: {needle:?}, start: {start}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::panic)]
+fn byte_queue_split_on() {
+    static DATA: &[u8] = b"ab--cd----e--f-g--";
+    static EXPECTED: &[&[u8]] = &[b"ab", b"cd", b"", b"e", b"f-g", b""];
+    for chunk_len in 1..8 {
+        let mut queue = crate::ByteQueue::new();
+        for chunk in DATA.chunks(chunk_len) {
+            queue.push_back(chunk);
+        }
+        let mut parts = queue.split_on(b"--");
+        for expected in EXPECTED {
+            let Some(part) = parts.next() else {
+                panic!("missing part {expected:?}");
+            };
+            assert!(is_same_as(expected, &part), "chunk_len: {chunk_len}");
+        }
+        assert!(parts.next().is_none());
+
+        let mut limited = queue.splitn_on(b"--", 2);
+        assert!(limited.next().is_some_and(|part| is_same_as(b"ab", &part)));
+        assert!(limited
+            .next()
+            .is_some_and(|part| is_same_as(b"cd----e--f-g--", &part)));
+        assert!(limited.next().is_none());
+    }
+}
+
+#[test]
+fn string_queue_split_on() {
+    let mut queue = crate::StringQueue::new();
+    queue.push_back("key=val");
+    queue.push_back("ue&a=");
+    queue.push_back("b&&c");
+    let parts: alloc::vec::Vec<_> = queue
+        .split_on("&")
+        .map(|part| alloc::string::ToString::to_string(&part))
+        .collect();
+    assert_eq!(parts, ["key=value", "a=b", "", "c"]);
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn byte_queue_rsplit_across_nodes() {
+    let mut queue = crate::ByteQueue::new();
+    let mut data = alloc::vec::Vec::new();
+    for index in 0..200_u32 {
+        let chunk = alloc::format!("{index}:--");
+        data.extend_from_slice(chunk.as_bytes());
+        queue.push_back(crate::ByteData::from(chunk.into_bytes()));
+    }
+    // move the start of the first node so the chunks do not begin at the start of its ring buffer
+    for _ in 0..3_u32 {
+        let chunk = queue.pop_front().unwrap();
+        data.drain(..chunk.len());
+    }
+    queue.push_front(b"-:-".as_slice());
+    data.splice(..0, b"-:-".iter().copied());
+    let data = crate::ByteData::from(data);
+    for needle in [b"-:".as_slice(), b"--1", b":--", b"9:--19", b"-"] {
+        assert_eq!(
+            queue.rfind(needle),
+            data.rfind(needle),
+            "needle: {needle:?}"
+        );
+        let parts: alloc::vec::Vec<_> = queue.rsplit(needle).collect();
+        let expected_parts: alloc::vec::Vec<_> = data.clone().rsplit(needle).collect();
+        assert_eq!(parts.len(), expected_parts.len(), "needle: {needle:?}");
+        for (part, expected) in parts.iter().zip(&expected_parts) {
+            assert!(is_same_as(expected, part), "needle: {needle:?}");
+        }
+    }
+    assert_eq!(queue.rfind(b"x:".as_slice()), None);
+}
+
+#[test]
+fn byte_queue_pattern_matches_contiguous() {
+    static DATA: &[u8] = b"abaabababbaab--aab--ab--b-a--bab";
+    static NEEDLES: &[&[u8]] = &[
+        b"", b"a", b"ab", b"aab", b"--", b"--b", b"abab", b"ba--b", b"bab", b"-a-",
+    ];
+    let data = crate::ByteData::from_static(DATA);
+    for chunk_len in 1..6 {
+        let mut queue = crate::ByteQueue::new();
+        for chunk in DATA.chunks(chunk_len) {
+            queue.push_back(chunk);
+        }
+        for needle in NEEDLES {
+            assert_eq!(
+                queue.find(*needle),
+                data.find(*needle),
+                "needle: {needle:?}"
+            );
+            assert_eq!(
+                queue.rfind(*needle),
+                data.rfind(*needle),
+                "chunk_len: {chunk_len}, needle: {needle:?}"
+            );
+            let parts: alloc::vec::Vec<_> = queue.rsplit(*needle).collect();
+            let expected_parts: alloc::vec::Vec<_> = data.clone().rsplit(*needle).collect();
+            assert_eq!(parts.len(), expected_parts.len(), "needle: {needle:?}");
+            for (part, expected) in parts.iter().zip(&expected_parts) {
+                assert!(is_same_as(expected, part), "needle: {needle:?}");
+            }
+        }
+        assert_eq!(queue.find(b"-b".as_slice()), Some(23));
+        assert_eq!(queue.rfind(b"-b".as_slice()), Some(28));
+        assert_eq!(queue.find(b"-b"), Some(23));
+        assert_eq!(queue.find(crate::pattern::AnyOf(*b"-b")), Some(1));
+        assert_eq!(queue.rfind('-'), Some(28));
+    }
+}
+
+#[test]
+#[allow(clippy::panic)]
+fn byte_queue_split_pattern() {
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(b"GET / HT".as_slice());
+    queue.push_back(b"TP/1.1\r".as_slice());
+    queue.push_back(b"\nHost: a\r\n".as_slice());
+
+    let lines: alloc::vec::Vec<_> = queue.split_inclusive(b"\r\n".as_slice()).collect();
+    assert_eq!(lines.len(), 2);
+    assert!(is_same_as(b"GET / HTTP/1.1\r\n", &lines[0]));
+    assert!(is_same_as(b"Host: a\r\n", &lines[1]));
+
+    let words: alloc::vec::Vec<_> = queue.splitn(3, b' ').collect();
+    assert_eq!(words.len(), 3);
+    assert!(is_same_as(b"HTTP/1.1\r\nHost: a\r\n", &words[2]));
+
+    let Some(after) = queue.strip_prefix(b"GET /".as_slice()) else {
+        panic!("prefix not found");
+    };
+    assert!(is_same_as(b" HTTP/1.1\r\nHost: a\r\n", &after));
+    assert!(queue.strip_prefix(b"GET /H".as_slice()).is_none());
+    let Some(before) = queue.strip_suffix(b"\r\n".as_slice()) else {
+        panic!("suffix not found");
+    };
+    assert!(is_same_as(b"GET / HTTP/1.1\r\nHost: a", &before));
+}
+
+#[test]
+fn string_queue_split_pattern() {
+    fn collect(
+        parts: impl Iterator<Item = crate::StringQueue<'static>>,
+    ) -> alloc::vec::Vec<alloc::string::String> {
+        parts
+            .map(|part| alloc::string::ToString::to_string(&part))
+            .collect()
+    }
+
+    let mut queue = crate::StringQueue::new();
+    queue.push_back("smörg");
+    queue.push_back("åsbord, sm");
+    queue.push_back("örgåsbord");
+
+    assert_eq!(queue.find("gås"), Some(5));
+    assert_eq!(queue.rfind('å'), Some(21));
+    assert_eq!(queue.find(b'\xA5'), None);
+    assert_eq!(queue.find(b"\xC3".as_slice()), None);
+    assert_eq!(
+        collect(queue.split(|ch: char| ch == ',' || ch == 'å')),
+        ["smörg", "sbord", " smörg", "sbord"]
+    );
+    assert_eq!(
+        collect(queue.rsplit("ör")),
+        ["gåsbord", "gåsbord, sm", "sm"]
+    );
+    assert!(queue
+        .strip_prefix("smö")
+        .is_some_and(|rest| rest.chars().eq("rgåsbord, smörgåsbord".chars())));
+    assert!(queue.strip_prefix(b"sm\xC3".as_slice()).is_none());
+}
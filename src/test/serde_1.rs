@@ -0,0 +1,102 @@
+use serde_1::de::{
+    value::{BorrowedBytesDeserializer, BorrowedStrDeserializer, Error, SeqDeserializer},
+    Deserialize,
+};
+use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
+
+use crate::{ByteData, ByteQueue, StringData, StringQueue};
+
+#[test]
+fn test_serialize_byte_queue() {
+    let mut queue = ByteQueue::new();
+    assert_ser_tokens(&queue, &[Token::Bytes(b"")]);
+    queue.push_back(ByteData::from_static(b"hello"));
+    assert_ser_tokens(&queue, &[Token::Bytes(b"hello")]);
+    queue.push_back(ByteData::from_static(b" world"));
+    assert_ser_tokens(&queue, &[Token::Bytes(b"hello world")]);
+}
+
+#[test]
+fn test_serialize_string_queue() {
+    let mut queue = StringQueue::new();
+    assert_ser_tokens(&queue, &[Token::Str("")]);
+    queue.push_back(StringData::from_static("hello"));
+    assert_ser_tokens(&queue, &[Token::Str("hello")]);
+    queue.push_back(StringData::from_static(" world"));
+    assert_ser_tokens(&queue, &[Token::Str("hello world")]);
+}
+
+#[test]
+fn test_deserialize_byte_queue() {
+    let queue = ByteQueue::from_iter([
+        ByteData::from_static(b"hello"),
+        ByteData::from_static(b" world"),
+    ]);
+    assert_de_tokens(&queue, &[Token::BorrowedBytes(b"hello world")]);
+    assert_de_tokens(&queue, &[Token::ByteBuf(b"hello world")]);
+    assert_de_tokens(
+        &queue,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::BorrowedBytes(b"hello"),
+            Token::Bytes(b" world"),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_deserialize_string_queue() {
+    let queue = StringQueue::from_iter([
+        StringData::from_static("hello"),
+        StringData::from_static(" world"),
+    ]);
+    assert_de_tokens(&queue, &[Token::BorrowedStr("hello world")]);
+    assert_de_tokens(&queue, &[Token::String("hello world")]);
+    assert_de_tokens(
+        &queue,
+        &[
+            Token::Seq { len: None },
+            Token::BorrowedStr("hello"),
+            Token::Str(" world"),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_deserialize_queue_borrowed() {
+    let input = b"a byte array longer than a chunk";
+    let deserializer = BorrowedBytesDeserializer::<Error>::new(input);
+    let queue = ByteQueue::deserialize(deserializer).unwrap();
+    assert_eq!(queue.chunk_len(), 1);
+    assert_eq!(
+        queue.front().unwrap().as_slice().as_ptr(),
+        input.as_slice().as_ptr()
+    );
+
+    let fragments = ["a string longer than a chunk", ", and another one"];
+    let deserializer = SeqDeserializer::<_, Error>::new(
+        fragments
+            .iter()
+            .map(|&fragment| BorrowedStrDeserializer::new(fragment)),
+    );
+    let queue = StringQueue::deserialize(deserializer).unwrap();
+    assert_eq!(queue.chunk_len(), 2);
+    for (chunk, fragment) in queue.chunks().zip(fragments) {
+        assert_eq!(chunk.as_str().as_ptr(), fragment.as_ptr());
+    }
+}
+
+#[test]
+fn test_deserialize_queue_invalid() {
+    assert_de_tokens_error::<ByteQueue<'_>>(
+        &[Token::Str("text")],
+        "invalid type: string \"text\", expected a byte array or a sequence of byte arrays",
+    );
+    assert_de_tokens_error::<StringQueue<'_>>(
+        &[Token::Seq { len: None }, Token::U8(1)],
+        "invalid type: integer `1`, expected a string",
+    );
+}
@@ -0,0 +1,126 @@
+use serde_1::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Readable, Token};
+
+use crate::{ByteData, SharedBytes};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "serde_1")]
+struct Record {
+    #[serde(with = "crate::serde::base64")]
+    key: ByteData<'static>,
+    #[serde(with = "crate::serde::hex")]
+    digests: alloc::vec::Vec<SharedBytes>,
+    #[serde(with = "crate::serde::utf8_or_base64")]
+    label: Option<ByteData<'static>>,
+}
+
+#[test]
+fn test_serde_helpers_readable() {
+    let record = Record {
+        key: ByteData::from_static(b"a key longer than a chunk"),
+        digests: alloc::vec![
+            SharedBytes::from(&[0xDE_u8, 0xAD][..]),
+            SharedBytes::from(&[0xBE_u8, 0xEF][..]),
+        ],
+        label: Some(ByteData::from_static(b"text")),
+    };
+    assert_tokens(
+        &record.readable(),
+        &[
+            Token::Struct {
+                name: "Record",
+                len: 3,
+            },
+            Token::Str("key"),
+            Token::Str("YSBrZXkgbG9uZ2VyIHRoYW4gYSBjaHVuaw=="),
+            Token::Str("digests"),
+            Token::Seq { len: Some(2) },
+            Token::Str("dead"),
+            Token::Str("beef"),
+            Token::SeqEnd,
+            Token::Str("label"),
+            Token::Some,
+            Token::Str("text"),
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[test]
+fn test_serde_helpers_compact() {
+    let record = Record {
+        key: ByteData::from_static(b"key"),
+        digests: alloc::vec![],
+        label: None,
+    };
+    assert_tokens(
+        &record.compact(),
+        &[
+            Token::Struct {
+                name: "Record",
+                len: 3,
+            },
+            Token::Str("key"),
+            Token::Bytes(b"key"),
+            Token::Str("digests"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("label"),
+            Token::None,
+            Token::StructEnd,
+        ],
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "serde_1")]
+struct Label(#[serde(with = "crate::serde::utf8_or_base64")] SharedBytes);
+
+#[test]
+fn test_serde_utf8_or_base64() {
+    assert_tokens(
+        &Label(SharedBytes::from(&b"\xFFbinary"[..])).readable(),
+        &[
+            Token::NewtypeStruct { name: "Label" },
+            Token::Map { len: Some(1) },
+            Token::Str("base64"),
+            Token::Str("/2JpbmFyeQ=="),
+            Token::MapEnd,
+        ],
+    );
+    assert_tokens(
+        &Label(SharedBytes::from(&b"plain text"[..])).readable(),
+        &[
+            Token::NewtypeStruct { name: "Label" },
+            Token::Str("plain text"),
+        ],
+    );
+    assert_de_tokens_error::<Readable<Label>>(
+        &[
+            Token::NewtypeStruct { name: "Label" },
+            Token::Map { len: Some(1) },
+            Token::Str("hex"),
+        ],
+        "unknown field `hex`, expected `base64`",
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "serde_1")]
+struct Digest(#[serde(with = "crate::serde::hex")] ByteData<'static>);
+
+#[test]
+fn test_serde_hex() {
+    assert_de_tokens_error::<Readable<Digest>>(
+        &[Token::NewtypeStruct { name: "Digest" }, Token::Str("abc")],
+        "invalid input length 3",
+    );
+    assert_de_tokens_error::<Readable<Digest>>(
+        &[Token::NewtypeStruct { name: "Digest" }, Token::Str("zz")],
+        "invalid byte 0x7a at position 0",
+    );
+    serde_test::assert_de_tokens(
+        &Digest(ByteData::from_static(&[0xAB, 0xCD])).readable(),
+        &[Token::NewtypeStruct { name: "Digest" }, Token::Str("ABcd")],
+    );
+}
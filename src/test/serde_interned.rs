@@ -0,0 +1,126 @@
+use serde_1::de::{value, DeserializeSeed, IntoDeserializer};
+
+use crate::interning::{ByteInterning, StaticInterning};
+use crate::serde::Interned;
+use crate::{ByteData, StringData};
+
+type Error = value::Error;
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_interned_string() {
+    let interning = StaticInterning::new(32);
+    let seed = Interned::<StringData<'static>>::new(&interning);
+    let first = seed
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(
+            "a string longer than a chunk",
+        ))
+        .unwrap();
+    let second = seed
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(
+            alloc::string::String::from("a string longer than a chunk"),
+        ))
+        .unwrap();
+    assert_eq!(first, "a string longer than a chunk");
+    assert_eq!(first.as_bytes().as_ptr(), second.as_bytes().as_ptr());
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_interned_fallback() {
+    let interning = StaticInterning::new(16);
+    let seed = Interned::<StringData<'static>>::new(&interning);
+    let input = alloc::string::String::from("a string that is too long to be interned");
+    let value = seed
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(input.as_str()))
+        .unwrap();
+    assert_eq!(value, input.as_str());
+    assert_ne!(value.as_bytes().as_ptr(), input.as_ptr());
+    assert!(interning
+        .get_str(StringData::from_borrowed(input.as_str()))
+        .ok()
+        .is_none());
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_interned_bytes() {
+    let interning = StaticInterning::new(32);
+    let seed = Interned::<ByteData<'static>>::new(&interning);
+    let first = seed
+        .deserialize(value::BytesDeserializer::<Error>::new(
+            b"some bytes longer than a chunk",
+        ))
+        .unwrap();
+    let second = seed
+        .deserialize(value::BytesDeserializer::<Error>::new(
+            b"some bytes longer than a chunk",
+        ))
+        .unwrap();
+    assert_eq!(first, b"some bytes longer than a chunk".as_slice());
+    assert_eq!(first.as_slice().as_ptr(), second.as_slice().as_ptr());
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_interned_seq_and_option() {
+    let interning = StaticInterning::new(32);
+    let input = alloc::vec!["a repeated value in the list"; 3];
+    let values = Interned::<alloc::vec::Vec<StringData<'static>>>::new(&interning)
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(input))
+        .unwrap();
+    assert_eq!(values.len(), 3);
+    assert!(values
+        .iter()
+        .all(|value| value.as_bytes().as_ptr() == values[0].as_bytes().as_ptr()));
+
+    let seed = Interned::<Option<StringData<'static>>>::new(&interning);
+    let none = seed
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(()))
+        .unwrap();
+    assert_eq!(none, None);
+}
+
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_interned_map_keys() {
+    let interning = StaticInterning::new(32);
+    let input = alloc::collections::BTreeMap::from([
+        ("x-forwarded-for-header", 1_u32),
+        ("x-request-identifier", 2),
+    ]);
+    let seed = Interned::<alloc::collections::BTreeMap<StringData<'static>, u32>>::new(&interning);
+    let first = seed
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(input.clone()))
+        .unwrap();
+    let second = seed
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(input))
+        .unwrap();
+    assert_eq!(first.get("x-request-identifier"), Some(&2));
+    for (left, right) in first.keys().zip(second.keys()) {
+        assert_eq!(left.as_bytes().as_ptr(), right.as_bytes().as_ptr());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[allow(clippy::unwrap_used)]
+fn test_interned_hash_map() {
+    let interning = StaticInterning::new(32);
+    let input = std::collections::HashMap::from([("a key longer than a chunk", "value")]);
+    let map =
+        Interned::<std::collections::HashMap<StringData<'static>, alloc::string::String>>::new(
+            &interning,
+        )
+        .deserialize(IntoDeserializer::<Error>::into_deserializer(input))
+        .unwrap();
+    assert_eq!(
+        map.get("a key longer than a chunk")
+            .map(alloc::string::String::as_str),
+        Some("value")
+    );
+    assert!(interning
+        .get_str(StringData::from_borrowed("a key longer than a chunk"))
+        .ok()
+        .is_some());
+}
@@ -31,3 +31,414 @@ fn test_shared_bytes_from_builder() {
     assert_eq!(s0.as_slice(), b"hello world2".as_slice());
     assert_eq!(s0.ref_count(), 1);
 }
+
+#[test]
+#[allow(clippy::panic)]
+fn test_shared_bytes_try_into_builder() {
+    let mut builder = crate::SharedBytesBuilder::with_capacity(64);
+    builder.extend_from_slice(b"hello world, this is some data");
+    let s0 = builder.build();
+    let ptr = s0.as_slice().as_ptr();
+    let s1 = s0.clone();
+    let Err(s0) = s0.try_into_builder() else {
+        panic!("a shared buffer should not be converted into a builder");
+    };
+    core::mem::drop(s1);
+
+    let s0 = s0.into_sliced(6, 5);
+    let Ok(mut reused) = s0.try_into_builder() else {
+        panic!("a unique buffer should be converted into a builder");
+    };
+    assert_eq!(reused.as_slice(), b"world");
+    assert_eq!(reused.as_slice().as_ptr(), ptr);
+    assert!(reused.capacity() >= 64, "tail capacity should be kept");
+    reused.extend_from_slice(b"!");
+    assert_eq!(reused.build().as_slice(), b"world!");
+}
+
+#[test]
+#[allow(clippy::panic)]
+fn test_bytedata_try_make_mut() {
+    let data = crate::ByteData::from_shared(crate::SharedBytes::from_slice(
+        b"a slice that is longer than a chunk",
+    ));
+    let copy = data.clone();
+    let Err(data) = data.try_make_mut() else {
+        panic!("shared data should not be made mutable");
+    };
+    core::mem::drop(copy);
+    let Ok(mut builder) = data.try_make_mut() else {
+        panic!("unique data should be made mutable");
+    };
+    builder.as_slice_mut()[0] = b'A';
+    assert_eq!(builder.as_slice(), b"A slice that is longer than a chunk");
+
+    let borrowed = crate::ByteData::from_static(b"a static slice that is long");
+    let Err(borrowed) = borrowed.try_make_mut() else {
+        panic!("borrowed data should not be made mutable");
+    };
+    assert_eq!(borrowed.as_slice(), b"a static slice that is long");
+}
+
+#[test]
+fn test_shared_bytes_meta_large_len() {
+    let meta = crate::SharedBytesMeta::new()
+        .with_align(64)
+        .with_len(0x0012_3456_789A);
+    assert!(meta.is_large());
+    assert_eq!(meta.align(), 64);
+    assert_eq!(meta.alloc_len(), 0x0012_3456_789A);
+    let meta = meta.with_len(0x1000);
+    assert!(!meta.is_large());
+    assert_eq!(meta.align(), 64);
+    assert_eq!(meta.alloc_len(), 0x1000);
+}
+
+/// Slicing a compact handle past a 32 bit offset moves it to the large representation.
+///
+/// The handle describes a range far outside the real allocation, so only its offsets are inspected.
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_shared_bytes_offset_past_u32() {
+    const GIB: usize = 1 << 30;
+    let (dat, _, _) = crate::SharedBytes::from_slice(b"offset").into_parts();
+    let s0 = crate::SharedBytes::from_parts(dat, 3 * GIB, 3 * GIB);
+    assert_eq!(s0.range(), (3 * GIB, 3 * GIB));
+    let s1 = s0.clone().into_sliced(2 * GIB, 10);
+    assert_eq!(s1.range(), (5 * GIB, 10));
+    assert_eq!(s1.len(), 10);
+    let mut s2 = s0.clone();
+    s2.make_sliced(GIB, 20);
+    assert_eq!(s2.range(), (4 * GIB, 20));
+    let mut s3 = s0.clone();
+    s3.make_sliced(GIB - 1, 30);
+    assert_eq!(s3.range(), (4 * GIB - 1, 30));
+    assert_eq!(s0.ref_count(), 4);
+    core::mem::drop((s1, s2, s3));
+    assert_eq!(s0.ref_count(), 1);
+}
+
+#[test]
+#[allow(clippy::panic)]
+fn test_shared_bytes_large_repr() {
+    let s0 =
+        crate::SharedBytes::from_slice(b"hello world, this is a large buffer").into_large_repr();
+    assert_eq!(s0.as_slice(), b"hello world, this is a large buffer");
+    assert_eq!(s0.len(), 35);
+    assert_eq!(s0.ref_count(), 1);
+    let s1 = s0.clone();
+    assert_eq!(s1.as_slice(), s0.as_slice());
+    assert_eq!(s0.ref_count(), 2);
+    let s2 = s1.sliced(6, 5);
+    assert_eq!(s2.as_slice(), b"world");
+    assert_eq!(s0.ref_count(), 3);
+    let s1 = s1.into_sliced(13, 22);
+    assert_eq!(s1.as_slice(), b"this is a large buffer");
+    assert_eq!(s1[0], b't');
+    let mut s3 = s1.clone();
+    s3.make_sliced_range(..4);
+    assert_eq!(s3.as_slice(), b"this");
+    s3.make_sliced(4, 0);
+    assert!(s3.is_empty());
+    core::mem::drop(s3);
+    core::mem::drop(s1);
+    core::mem::drop(s2);
+    assert_eq!(s0.ref_count(), 1);
+
+    let s0 = s0.into_sliced(6, 5);
+    let Ok(builder) = s0.try_into_builder() else {
+        panic!("a unique buffer should be converted into a builder");
+    };
+    assert_eq!(builder.as_slice(), b"world");
+}
+
+#[test]
+#[ignore = "allocates more than 4 GiB of memory"]
+fn test_shared_bytes_over_4gib() {
+    const LEN: usize = 0x1_0000_0100;
+    let mut builder = crate::SharedBytesBuilder::with_capacity(LEN);
+    assert!(builder.capacity() >= LEN);
+    builder.apply_unfilled(|buf| {
+        for (i, byte) in buf[..LEN].iter_mut().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            byte.write(i as u8);
+        }
+        ((), LEN)
+    });
+    builder.reserve(0x100);
+    builder.extend_from_slice(b"tail");
+    assert_eq!(builder.len(), LEN + 4);
+    let s0 = builder.build();
+    assert_eq!(s0.len(), LEN + 4);
+    assert!(s0.ends_with(b"tail"));
+    let s1 = s0.sliced(0x1_0000_0000, 0x104);
+    assert_eq!(s1.len(), 0x104);
+    assert_eq!(s1[0], 0);
+    assert_eq!(s1[0xFF], 0xFF);
+    assert_eq!(&s1[0x100..], b"tail");
+    let s2 = s1.clone().into_sliced(0x100, 4);
+    assert_eq!(s2.as_slice(), b"tail");
+    core::mem::drop(s0);
+    assert_eq!(s1.ref_count(), 2);
+}
+
+#[test]
+fn test_shared_bytes_weak() {
+    let s0 = crate::SharedBytes::from_slice(b"hello world");
+    let w0 = s0.sliced(6, 5).downgrade();
+    assert_eq!(w0.len(), 5);
+    assert_eq!(w0.strong_count(), 1);
+    let w1 = w0.clone();
+    let s1 = w1.upgrade();
+    assert_eq!(
+        s1.as_ref().map(crate::SharedBytes::as_slice),
+        Some(b"world".as_slice())
+    );
+    assert_eq!(s0.ref_count(), 2);
+    core::mem::drop(s1);
+    assert!(
+        s0.clone().try_into_builder().is_err(),
+        "the buffer is shared"
+    );
+    core::mem::drop(s0);
+    assert_eq!(w0.strong_count(), 0);
+    assert!(w0.upgrade().is_none());
+    assert!(w1.upgrade().is_none());
+    core::mem::drop(w0);
+    core::mem::drop(w1);
+
+    let empty = crate::SharedBytes::empty().downgrade();
+    assert!(empty.is_empty());
+    assert_eq!(empty.upgrade(), Some(crate::SharedBytes::empty()));
+}
+
+#[test]
+#[allow(clippy::panic)]
+fn test_shared_bytes_weak_blocks_builder() {
+    let s0 = crate::SharedBytes::from_slice(b"hello world");
+    let w0 = s0.downgrade();
+    let Err(s0) = s0.try_into_builder() else {
+        panic!("a buffer with weak references should not be converted into a builder");
+    };
+    core::mem::drop(w0);
+    let Ok(builder) = s0.try_into_builder() else {
+        panic!("a unique buffer should be converted into a builder");
+    };
+    assert_eq!(builder.as_slice(), b"hello world");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_shared_bytes_weak_race() {
+    use std::sync::Barrier;
+
+    const THREADS: usize = 4;
+    for _ in 0..200_u32 {
+        let s0 = crate::SharedBytes::from_slice(b"racing data");
+        let weak = s0.downgrade();
+        let barrier = Barrier::new(THREADS + 1);
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    let local = weak.clone();
+                    barrier.wait();
+                    for _ in 0..50_u32 {
+                        if let Some(upgraded) = local.upgrade() {
+                            assert_eq!(upgraded.as_slice(), b"racing data");
+                        }
+                    }
+                });
+            }
+            barrier.wait();
+            core::mem::drop(s0);
+        });
+        assert!(weak.upgrade().is_none());
+        assert_eq!(weak.strong_count(), 0);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_shared_bytes_weak_race_into_builder() {
+    use std::sync::Barrier;
+
+    for _ in 0..200_u32 {
+        let s0 = crate::SharedBytes::from_slice(b"racing data");
+        let s1 = s0.clone();
+        let weak = s0.downgrade();
+        let barrier = Barrier::new(3);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                barrier.wait();
+                let upgraded = weak.upgrade();
+                core::mem::drop(weak);
+                if let Some(upgraded) = upgraded {
+                    assert_eq!(upgraded.as_slice(), b"racing data");
+                }
+            });
+            scope.spawn(|| {
+                barrier.wait();
+                let downgraded = s1.downgrade();
+                core::mem::drop(s1);
+                core::mem::drop(downgraded);
+            });
+            barrier.wait();
+            let mut current = s0;
+            // wait for the other references to be released before taking ownership
+            let builder = loop {
+                match current.try_into_builder() {
+                    Ok(builder) => break builder,
+                    Err(rest) => {
+                        current = rest;
+                        std::thread::yield_now();
+                    }
+                }
+            };
+            assert_eq!(builder.as_slice(), b"racing data");
+        });
+    }
+}
+
+struct CountingAllocator {
+    allocated: core::sync::atomic::AtomicUsize,
+    released: core::sync::atomic::AtomicUsize,
+}
+
+// SAFETY: all allocations are forwarded to the global allocator.
+unsafe impl crate::SharedAllocator for CountingAllocator {
+    unsafe fn allocate(&self, layout: core::alloc::Layout) -> *mut u8 {
+        self.allocated
+            .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        // SAFETY: the caller guarantees that the layout has a non-zero size.
+        unsafe { alloc::alloc::alloc(layout) }
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: core::alloc::Layout) {
+        self.released
+            .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        // SAFETY: the caller guarantees that `ptr` was allocated using `layout`.
+        unsafe { alloc::alloc::dealloc(ptr, layout) };
+    }
+}
+
+impl CountingAllocator {
+    const fn new() -> Self {
+        Self {
+            allocated: core::sync::atomic::AtomicUsize::new(0),
+            released: core::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    fn counts(&self) -> (usize, usize) {
+        (
+            self.allocated.load(core::sync::atomic::Ordering::Relaxed),
+            self.released.load(core::sync::atomic::Ordering::Relaxed),
+        )
+    }
+}
+
+#[test]
+#[allow(clippy::panic)]
+fn test_shared_bytes_custom_allocator() {
+    static ALLOC: CountingAllocator = CountingAllocator::new();
+
+    let mut builder = crate::SharedBytesBuilder::with_capacity_in(8, &ALLOC);
+    assert!(builder.allocator().is_some());
+    builder.extend_from_slice(b"hello world, this forces a reallocation of the buffer");
+    assert_eq!(ALLOC.counts(), (2, 1));
+    let s0 = builder.build();
+    assert!(s0.allocator().is_some());
+    assert_eq!(
+        s0.as_slice(),
+        b"hello world, this forces a reallocation of the buffer"
+    );
+    let s1 = s0.sliced(6, 5);
+    let weak = s1.downgrade();
+    core::mem::drop(s0);
+    assert_eq!(s1.as_slice(), b"world");
+    core::mem::drop(s1);
+    assert_eq!(ALLOC.counts(), (2, 1));
+    core::mem::drop(weak);
+    assert_eq!(ALLOC.counts(), (2, 2));
+
+    let s2 = crate::SharedBytes::from_slice_in(b"reused", &ALLOC);
+    let Ok(mut reused) = s2.try_into_builder() else {
+        panic!("a unique buffer should be converted into a builder");
+    };
+    assert!(reused.allocator().is_some());
+    reused.extend_from_slice(b" buffer");
+    assert_eq!(reused.build().as_slice(), b"reused buffer");
+    let (allocated, released) = ALLOC.counts();
+    assert_eq!(allocated, released);
+
+    assert!(crate::SharedBytes::from_slice(b"global")
+        .allocator()
+        .is_none());
+}
+
+#[test]
+fn test_shared_bytes_custom_allocator_aligned() {
+    static ALLOC: CountingAllocator = CountingAllocator::new();
+
+    let mut builder = crate::SharedBytesBuilder::with_aligned_capacity_in(16, 64, &ALLOC);
+    builder.extend_from_slice(b"aligned data");
+    let s0 = builder.build();
+    assert_eq!(s0.as_slice(), b"aligned data");
+    assert_eq!(s0.as_slice().as_ptr().align_offset(64), 0);
+    core::mem::drop(s0);
+    assert_eq!(ALLOC.counts(), (1, 1));
+
+    let empty = crate::SharedBytesBuilder::new_in(&ALLOC).build();
+    assert!(empty.is_empty());
+    assert_eq!(ALLOC.counts(), (1, 1));
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[allow(clippy::std_instead_of_core)]
+fn test_shared_bytes_read_into_larger_buffer() {
+    use std::io::Read;
+
+    let mut buf = [0_u8; 16];
+    let mut shared = crate::SharedBytes::from_slice(b"hello");
+    assert_eq!(shared.read(&mut buf).ok(), Some(5));
+    assert_eq!(&buf[..5], b"hello");
+    assert_eq!(shared.read(&mut buf).ok(), Some(0));
+
+    buf.fill(0);
+    let mut data = crate::ByteData::from_shared(crate::SharedBytes::from_slice(b"world"));
+    assert_eq!(data.read(&mut buf).ok(), Some(5));
+    assert_eq!(&buf[..5], b"world");
+    assert_eq!(data.read(&mut buf).ok(), Some(0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[allow(clippy::std_instead_of_core)]
+fn test_shared_bytes_read_vectored_past_end() {
+    use std::io::{IoSliceMut, Read};
+
+    let mut first = [0_u8; 4];
+    let mut second = [0_u8; 4];
+    let mut third = [0_u8; 4];
+    let mut shared = crate::SharedBytes::from_slice(b"0123456");
+    let mut bufs = [
+        IoSliceMut::new(&mut first),
+        IoSliceMut::new(&mut second),
+        IoSliceMut::new(&mut third),
+    ];
+    assert_eq!(shared.read_vectored(&mut bufs).ok(), Some(7));
+    assert_eq!(&first, b"0123");
+    assert_eq!(&second[..3], b"456");
+    assert_eq!(third, [0; 4]);
+    assert!(shared.is_empty());
+
+    let mut head = [0_u8; 3];
+    let mut tail = [0_u8; 8];
+    let mut data = crate::ByteData::from_static(b"abcdefg");
+    let mut data_bufs = [IoSliceMut::new(&mut head), IoSliceMut::new(&mut tail)];
+    assert_eq!(data.read_vectored(&mut data_bufs).ok(), Some(7));
+    assert_eq!(&head, b"abc");
+    assert_eq!(&tail[..4], b"defg");
+    assert!(data.is_empty());
+}
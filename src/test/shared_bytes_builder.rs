@@ -8,7 +8,7 @@ fn test_shared_bytes_builder_from_slice() {
 
     assert_eq!(s0.align, core::mem::align_of::<crate::SharedBytesMeta>());
     assert_eq!(
-        s0.off as usize,
+        s0.off,
         core::mem::size_of::<crate::SharedBytesMeta>() + data.len() + data2.len()
     );
     assert_eq!(
@@ -33,12 +33,12 @@ fn test_shared_bytes_builder_from_aligned_slice() {
 
     assert_eq!(s0.align, 8);
     assert_eq!(
-        s0.off as usize,
-        core::mem::size_of::<crate::SharedBytesMeta>() + 4 + data.len() + data2.len()
+        s0.off,
+        crate::SharedBytesMeta::compute_start_offset(8) + data.len() + data2.len()
     );
     assert_eq!(
         s0.capacity(),
-        32 - core::mem::size_of::<crate::SharedBytesMeta>() - 4
+        32 - crate::SharedBytesMeta::compute_start_offset(8)
     );
 
     let s0 = s0.build();
@@ -34,3 +34,245 @@ fn test_stringdata_alloc() {
     assert!(!s1.is_empty());
     assert_eq!(&s1[1..3], "el");
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+#[allow(clippy::panic)]
+fn test_split_on() {
+    let data = crate::ByteData::from_static(b"ab,cde,,fg,h with some trailing bytes,");
+    let parts: alloc::vec::Vec<_> = data.split_on(b",").collect();
+    assert_eq!(
+        parts,
+        [
+            b"ab".as_slice(),
+            b"cde",
+            b"",
+            b"fg",
+            b"h with some trailing bytes",
+            b""
+        ]
+    );
+    assert_eq!(crate::ByteData::empty().split_on(b",").count(), 0);
+    assert_eq!(
+        crate::ByteData::from_static(b"abc").split_on(b"").count(),
+        1
+    );
+
+    let text = crate::StringData::from_static("GET / HTTP/1.1\r\nHost: a\r\n\r\nbody");
+    let Ok((head, body)) = text.split_once_on("\r\n\r\n") else {
+        panic!("separator not found");
+    };
+    assert_eq!(head.as_str(), "GET / HTTP/1.1\r\nHost: a");
+    assert_eq!(body.as_str(), "\r\n\r\nbody");
+    let lines: alloc::vec::Vec<_> = head.split_on("\r\n").collect();
+    assert_eq!(lines, ["GET / HTTP/1.1", "Host: a"]);
+}
+
+#[test]
+fn test_pattern_find() {
+    let data = crate::ByteData::from_static(b"key=value; other=thing");
+    assert_eq!(data.find(b'='), Some(3));
+    assert_eq!(data.rfind(b'='), Some(16));
+    assert_eq!(data.find("other"), Some(11));
+    assert_eq!(data.find(b"; ".as_slice()), Some(9));
+    assert_eq!(data.find(b"; "), Some(9));
+    assert_eq!(data.rfind(b"; "), Some(9));
+    assert_eq!(data.find(crate::pattern::AnyOf(*b"; ")), Some(9));
+    assert_eq!(data.rfind(crate::pattern::AnyOf(*b"; ")), Some(10));
+    assert_eq!(data.find('t'), Some(12));
+    assert_eq!(data.find(|ch: char| ch.is_whitespace()), Some(10));
+    assert_eq!(
+        data.rfind(crate::pattern::BytePredicate(|byte: u8| byte == b'e')),
+        Some(14)
+    );
+    assert_eq!(data.find(b"".as_slice()), Some(0));
+    assert_eq!(data.rfind(b"".as_slice()), Some(data.len()));
+    assert_eq!(data.find('x'), None);
+
+    let text = crate::StringData::from_static("smörgåsbord");
+    assert_eq!(text.find('å'), Some(6));
+    assert_eq!(text.rfind(|ch: char| !ch.is_ascii()), Some(6));
+    // bytes of a multi-byte char never match on their own
+    assert_eq!(text.find(0xB6_u8), None);
+    assert_eq!(text.find(b"\xC3\xB6".as_slice()), Some(2));
+    assert_eq!(text.find(b"\xC3".as_slice()), None);
+    assert_eq!(text.find(crate::pattern::AnyOf(*b"\xB6\xA5")), None);
+    assert_eq!(text.find(crate::pattern::AnyOf(*b"gr")), Some(4));
+    assert_eq!(text.find(b"rg"), Some(4));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pattern_split() {
+    use alloc::vec::Vec;
+
+    let data = crate::ByteData::from_static(b"a,b;c,,d;");
+    assert_eq!(
+        data.clone()
+            .split(crate::pattern::AnyOf(*b",;"))
+            .collect::<Vec<_>>(),
+        [b"a".as_slice(), b"b", b"c", b"", b"d", b""]
+    );
+    assert_eq!(
+        data.clone().splitn(3, b',').collect::<Vec<_>>(),
+        [b"a".as_slice(), b"b;c", b",d;"]
+    );
+    assert_eq!(data.clone().splitn(0, b',').count(), 0);
+    assert_eq!(
+        data.clone().rsplit(b';').collect::<Vec<_>>(),
+        [b"".as_slice(), b"c,,d", b"a,b"]
+    );
+    assert_eq!(
+        data.clone()
+            .split_inclusive(crate::pattern::AnyOf(*b",;"))
+            .collect::<Vec<_>>(),
+        [b"a,".as_slice(), b"b;", b"c,", b",", b"d;"]
+    );
+    assert_eq!(data.strip_prefix(b"a,".as_slice()), Some(data.sliced(2..)));
+    assert_eq!(data.strip_prefix(b','), None);
+    assert_eq!(data.strip_suffix(b';'), Some(data.sliced(..8)));
+
+    let text = crate::StringData::from_static("en: smörgåsbord, sv: smörgåsbord");
+    assert_eq!(
+        text.clone().split(", ").collect::<Vec<_>>(),
+        ["en: smörgåsbord", "sv: smörgåsbord"]
+    );
+    assert_eq!(
+        text.clone()
+            .rsplit(|ch: char| !ch.is_ascii())
+            .collect::<Vec<_>>(),
+        ["sbord", "rg", "sbord, sv: sm", "rg", "en: sm"]
+    );
+    assert_eq!(
+        text.clone().split_inclusive('å').collect::<Vec<_>>(),
+        ["en: smörgå", "sbord, sv: smörgå", "sbord"]
+    );
+    // byte patterns only split on char boundaries
+    assert_eq!(text.clone().split(b'\xA5').count(), 1);
+    assert_eq!(
+        text.strip_prefix("en: ").as_deref(),
+        Some("smörgåsbord, sv: smörgåsbord")
+    );
+    assert_eq!(
+        text.strip_suffix("bord").as_deref(),
+        Some("en: smörgåsbord, sv: smörgås")
+    );
+    assert_eq!(text.strip_suffix(b"\xA5sbord".as_slice()), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pattern_byte_array_matches_slice() {
+    use alloc::vec::Vec;
+
+    let request = crate::ByteData::from_static(b"GET /index");
+    assert_eq!(
+        request.strip_prefix(b"GET ").as_deref(),
+        request.as_slice().strip_prefix(b"GET ")
+    );
+    assert_eq!(
+        request.strip_prefix(b"GET ").as_deref(),
+        Some(b"/index".as_slice())
+    );
+    assert_eq!(
+        request.strip_suffix(b"dex").as_deref(),
+        Some(b"GET /in".as_slice())
+    );
+
+    let head = crate::ByteData::from_static(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n");
+    let lines: Vec<_> = head.clone().split(b"\r\n").collect();
+    let expected: Vec<_> = head
+        .as_slice()
+        .split(|&byte| byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .collect();
+    assert_eq!(lines, expected);
+    assert_eq!(lines, [b"GET / HTTP/1.1".as_slice(), b"Host: a", b"", b""]);
+
+    let text = crate::StringData::from_static("GET /index");
+    assert_eq!(
+        text.strip_prefix(b"GET ").as_deref(),
+        "GET /index".strip_prefix("GET ")
+    );
+    let parts: Vec<_> = crate::StringData::from_static("a\r\nb")
+        .split(b"\r\n")
+        .collect();
+    assert_eq!(parts, ["a", "b"]);
+}
+
+#[test]
+fn test_from_utf8() {
+    use crate::{ByteData, Utf8CharError};
+
+    let valid = StringData::from_utf8(ByteData::from_static("h\u{e9}llo".as_bytes()));
+    assert_eq!(
+        valid.map(|data| data.as_str() == "h\u{e9}llo").ok(),
+        Some(true)
+    );
+
+    let Err(err) = StringData::from_utf8(ByteData::from_static(b"ok \xE2\x9C bad")) else {
+        unreachable!("the input is not valid UTF-8");
+    };
+    assert_eq!(err.valid_up_to(), 3);
+    assert_eq!(
+        err.utf8_error(),
+        Utf8CharError {
+            valid_up_to: 3,
+            error_len: 2
+        }
+    );
+    assert_eq!(err.as_bytes(), b"ok \xE2\x9C bad");
+    assert_eq!(err.into_bytedata().as_slice(), b"ok \xE2\x9C bad");
+
+    assert_eq!(Utf8CharError::check(b"complete \xF0\x9F\x98\x8A"), Ok(()));
+    assert_eq!(
+        Utf8CharError::check(b"truncated \xF0\x9F\x98"),
+        Err(Utf8CharError {
+            valid_up_to: 10,
+            error_len: 3
+        })
+    );
+    assert_eq!(
+        Utf8CharError::check(b"\xC0\x80"),
+        Err(Utf8CharError {
+            valid_up_to: 0,
+            error_len: 1
+        })
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_from_utf8_lossy() {
+    use crate::ByteData;
+
+    let input = b"a log line that is long enough to be borrowed";
+    let borrowed = StringData::from_utf8_lossy(ByteData::from_borrowed(input));
+    assert_eq!(
+        borrowed.as_borrowed().map(str::as_ptr),
+        Some(input.as_ptr())
+    );
+
+    let shared = StringData::from_utf8_lossy(ByteData::from_static(b"caf\xC3\xA9"));
+    assert_eq!(shared.as_str(), "caf\u{e9}");
+
+    let replaced = StringData::from_utf8_lossy(ByteData::from_static(
+        b"Host: \xFFexample\xE2\x9C.com\xF0\x9F",
+    ));
+    assert_eq!(
+        replaced.as_str(),
+        "Host: \u{FFFD}example\u{FFFD}.com\u{FFFD}"
+    );
+    assert_eq!(
+        replaced.as_str(),
+        alloc::string::String::from_utf8_lossy(b"Host: \xFFexample\xE2\x9C.com\xF0\x9F")
+    );
+
+    let Err(err) = StringData::from_utf8(ByteData::from_static(b"\xED\xA0\x80 surrogate")) else {
+        unreachable!("surrogates are not valid UTF-8");
+    };
+    assert_eq!(
+        err.into_lossy().as_str(),
+        "\u{FFFD}\u{FFFD}\u{FFFD} surrogate"
+    );
+}
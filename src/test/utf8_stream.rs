@@ -0,0 +1,129 @@
+use crate::{ByteData, StringData, Utf8CharError, Utf8StreamDecoder};
+
+#[test]
+fn test_utf8_stream_pieces() {
+    let mut decoder = Utf8StreamDecoder::new();
+    let input = b"a long enough prefix to borrow \xE2\x9C";
+    let mut parts = decoder.decode(ByteData::from_borrowed(input));
+    let Some(Ok(first)) = parts.next() else {
+        unreachable!("the valid prefix is emitted");
+    };
+    assert_eq!(first.as_str(), "a long enough prefix to borrow ");
+    assert_eq!(first.as_borrowed().map(str::as_ptr), Some(input.as_ptr()));
+    assert!(parts.next().is_none());
+    assert_eq!(decoder.pending_len(), 2);
+
+    let mut completed = decoder.decode(ByteData::from_static(b"\x93!"));
+    assert_eq!(
+        completed.next().and_then(Result::ok),
+        Some(StringData::from_static("\u{2713}"))
+    );
+    assert_eq!(
+        completed.next().and_then(Result::ok),
+        Some(StringData::from_static("!"))
+    );
+    assert!(completed.next().is_none());
+    assert_eq!(decoder.pending_len(), 0);
+    assert_eq!(decoder.finish(), Ok(StringData::empty()));
+}
+
+#[test]
+fn test_utf8_stream_errors() {
+    let mut decoder = Utf8StreamDecoder::new();
+    let mut parts = decoder.decode(ByteData::from_static(b"ab\xFFcd\xF0\x9F"));
+    assert_eq!(
+        parts.next().and_then(Result::ok),
+        Some(StringData::from_static("ab"))
+    );
+    assert_eq!(
+        parts.next(),
+        Some(Err(Utf8CharError {
+            valid_up_to: 2,
+            error_len: 1
+        }))
+    );
+    assert_eq!(
+        parts.next().and_then(Result::ok),
+        Some(StringData::from_static("cd"))
+    );
+    assert!(parts.next().is_none());
+
+    // the held back bytes turn out to be invalid
+    let mut invalid = decoder.decode(ByteData::from_static(b"xyz"));
+    assert_eq!(
+        invalid.next(),
+        Some(Err(Utf8CharError {
+            valid_up_to: 5,
+            error_len: 2
+        }))
+    );
+    assert_eq!(
+        invalid.next().and_then(Result::ok),
+        Some(StringData::from_static("xyz"))
+    );
+    assert!(invalid.next().is_none());
+
+    assert_eq!(decoder.decode(ByteData::from_static(b"\xE2")).count(), 0);
+    assert_eq!(
+        decoder.finish(),
+        Err(Utf8CharError {
+            valid_up_to: 10,
+            error_len: 1
+        })
+    );
+    assert_eq!(decoder.pending_len(), 0);
+
+    let mut lossy = Utf8StreamDecoder::lossy();
+    assert_eq!(
+        lossy.decode(ByteData::from_static(b"\xF0\x9F\x98")).count(),
+        0
+    );
+    assert_eq!(lossy.finish(), Ok(StringData::from_static("\u{FFFD}")));
+    assert!(lossy.is_lossy());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_utf8_stream_split_everywhere() {
+    use alloc::string::String;
+
+    let input: &[u8] =
+        b"caf\xC3\xA9 \xE2\x9C\x93 \xF0\x9F\x98\x8A \xFF\xC3 \xED\xA0\x80 end \xF0\x9F";
+    let expected = String::from_utf8_lossy(input);
+    for first in 0..input.len() {
+        for second in first..input.len() {
+            let mut decoder = Utf8StreamDecoder::lossy();
+            let mut out = String::new();
+            for chunk in [&input[..first], &input[first..second], &input[second..]] {
+                for part in decoder.decode(ByteData::from_borrowed(chunk)) {
+                    out.push_str(part.as_ref().map_or("", StringData::as_str));
+                }
+            }
+            out.push_str(decoder.finish().as_ref().map_or("", StringData::as_str));
+            assert_eq!(out, expected, "split at {first} and {second}");
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "queue"))]
+#[test]
+fn test_utf8_stream_queue() {
+    use crate::{ByteQueue, StringQueue};
+
+    let mut input = ByteQueue::new();
+    input.push_back(b"\xE2\x9C".as_slice());
+    input.push_back(b"\x93 done \xC3".as_slice());
+    input.push_back(b"\xFF".as_slice());
+
+    let mut decoder = Utf8StreamDecoder::new();
+    let mut out = StringQueue::new();
+    assert_eq!(
+        decoder.decode_queue_into(input, &mut out),
+        Err(Utf8CharError {
+            valid_up_to: 9,
+            error_len: 1
+        })
+    );
+    assert_eq!(decoder.finish(), Ok(StringData::empty()));
+    assert_eq!(StringData::from(out).as_str(), "\u{2713} done ");
+}
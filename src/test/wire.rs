@@ -0,0 +1,208 @@
+use crate::wire::{
+    decode_varint, encode_varint, read_varint, varint_len, zigzag_decode_32, zigzag_decode_64,
+    zigzag_encode_32, zigzag_encode_64, Fields, WireError, WireValue,
+};
+use crate::{ByteCursor, ByteData, UnexpectedEnd};
+
+#[test]
+fn test_varint_roundtrip() {
+    for value in [
+        0,
+        1,
+        127,
+        128,
+        150,
+        0x3FFF,
+        0x4000,
+        u64::from(u32::MAX),
+        u64::MAX,
+    ] {
+        let encoded = encode_varint(value);
+        assert_eq!(encoded.len(), varint_len(value));
+        assert_eq!(
+            decode_varint(encoded.as_slice()),
+            Ok((value, encoded.len()))
+        );
+    }
+    assert_eq!(encode_varint(150).as_slice(), b"\x96\x01");
+    assert_eq!(encode_varint(u64::MAX).len(), 10);
+    assert_eq!(
+        decode_varint(b"\x96"),
+        Err(WireError::UnexpectedEnd(UnexpectedEnd {
+            needed: 2,
+            remaining: 1
+        }))
+    );
+    assert_eq!(
+        decode_varint(b"\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x02"),
+        Err(WireError::VarintOverflow { position: 0 })
+    );
+}
+
+#[test]
+fn test_zigzag() {
+    for value in [0_i32, -1_i32, 1_i32, -2_i32, i32::MAX, i32::MIN] {
+        assert_eq!(zigzag_decode_32(zigzag_encode_32(value)), value);
+    }
+    for value in [0_i64, -1_i64, 1_i64, -2_i64, i64::MAX, i64::MIN] {
+        assert_eq!(zigzag_decode_64(zigzag_encode_64(value)), value);
+    }
+    assert_eq!(zigzag_encode_32(-1), 1);
+    assert_eq!(zigzag_encode_32(1), 2);
+    assert_eq!(zigzag_encode_64(-2), 3);
+    assert_eq!(zigzag_encode_32(i32::MIN), u32::MAX);
+}
+
+#[test]
+#[allow(clippy::shadow_unrelated)]
+fn test_read_varint_restores_position() {
+    let mut cursor = ByteCursor::new(ByteData::from_static(b"\x01\x96"));
+    assert_eq!(read_varint(&mut cursor), Ok(1));
+    assert_eq!(
+        read_varint(&mut cursor),
+        Err(WireError::UnexpectedEnd(UnexpectedEnd {
+            needed: 2,
+            remaining: 1
+        }))
+    );
+    assert_eq!(cursor.position(), 1);
+
+    let mut cursor = ByteCursor::new(ByteData::from_static(&[0xFF; 11]));
+    assert_eq!(
+        read_varint(&mut cursor),
+        Err(WireError::VarintOverflow { position: 0 })
+    );
+    assert_eq!(cursor.position(), 0);
+}
+
+#[test]
+fn test_fields() {
+    let data: &'static [u8] =
+        b"\x08\x96\x01\x11\x01\x00\x00\x00\x00\x00\x00\x00\x1A\x03abc\x25\x00\x00\x80\x3F\x2B\x2C";
+    let mut fields = Fields::new(data);
+    assert_eq!(fields.next(), Some(Ok((1, WireValue::Varint(150)))));
+    assert_eq!(fields.next(), Some(Ok((2, WireValue::Fixed64(1)))));
+    assert_eq!(
+        fields.next(),
+        Some(Ok((
+            3,
+            WireValue::LengthDelimited(ByteData::from_static(b"abc"))
+        )))
+    );
+    assert_eq!(
+        fields.next(),
+        Some(Ok((4, WireValue::Fixed32(1.0_f32.to_bits()))))
+    );
+    assert_eq!(fields.next(), Some(Ok((5, WireValue::StartGroup))));
+    assert_eq!(fields.next(), Some(Ok((5, WireValue::EndGroup))));
+    assert_eq!(fields.next(), None);
+}
+
+#[test]
+#[allow(clippy::shadow_unrelated)]
+fn test_fields_errors() {
+    let mut fields = Fields::new(b"\x08\x01\x0E\x01".as_slice());
+    assert_eq!(fields.next(), Some(Ok((1, WireValue::Varint(1)))));
+    assert_eq!(
+        fields.next(),
+        Some(Err(WireError::InvalidWireType {
+            position: 2,
+            wire_type: 6
+        }))
+    );
+    assert_eq!(fields.next(), None);
+    assert_eq!(fields.position(), 2);
+
+    let mut fields = Fields::new(b"\x00\x01".as_slice());
+    assert_eq!(
+        fields.next(),
+        Some(Err(WireError::InvalidFieldNumber {
+            position: 0,
+            field_number: 0
+        }))
+    );
+
+    let mut fields = Fields::new(b"\x0A\x05abc".as_slice());
+    assert_eq!(
+        fields.next(),
+        Some(Err(WireError::UnexpectedEnd(UnexpectedEnd {
+            needed: 5,
+            remaining: 3
+        })))
+    );
+    assert_eq!(fields.position(), 0);
+}
+
+#[test]
+fn test_fields_zero_copy() {
+    let data: &'static [u8] = b"\x0A\x14twenty bytes of text";
+    let mut fields = Fields::new(data);
+    let Some(Ok((1, WireValue::LengthDelimited(value)))) = fields.next() else {
+        unreachable!()
+    };
+    assert_eq!(value.as_slice(), b"twenty bytes of text");
+    assert_eq!(value.as_slice().as_ptr(), data[2..].as_ptr());
+}
+
+#[cfg(all(feature = "queue", feature = "alloc"))]
+#[test]
+fn test_fields_split_queue() {
+    use crate::wire::WireWrite;
+    use crate::ByteQueue;
+
+    let text: &'static [u8] = b"a longer value that is not copied";
+    let mut queue = ByteQueue::new();
+    queue.write_uint64_field(1, 300);
+    queue.write_bytes_field(2, text);
+    queue.write_sint64_field(3, -1_000_000);
+    queue.write_double_field(4, 0.5);
+    queue.write_bool_field(5, true);
+    let encoded = ByteData::from(queue.clone());
+
+    // split the encoding at every position, so that each value is split between chunks somewhere
+    for split in 0..encoded.len() {
+        let mut split_queue = ByteQueue::new();
+        split_queue.push_back(encoded.sliced(..split));
+        split_queue.push_back(encoded.sliced(split..));
+        let fields = Fields::from_queue(split_queue).collect::<alloc::vec::Vec<_>>();
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields[0], Ok((1, WireValue::Varint(300))));
+        assert_eq!(
+            fields[1],
+            Ok((2, WireValue::LengthDelimited(ByteData::from_static(text))))
+        );
+        assert_eq!(
+            fields[2],
+            Ok((3, WireValue::Varint(zigzag_encode_64(-1_000_000))))
+        );
+        assert_eq!(fields[3], Ok((4, WireValue::Fixed64(0.5_f64.to_bits()))));
+        assert_eq!(fields[4], Ok((5, WireValue::Varint(1))));
+    }
+
+    // the length-delimited value is pushed without copying and read back without copying
+    let Some(Ok((2, WireValue::LengthDelimited(value)))) = Fields::from_queue(queue).nth(1) else {
+        unreachable!()
+    };
+    assert_eq!(value.as_slice().as_ptr(), text.as_ptr());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_write_builder() {
+    use crate::wire::WireWrite;
+    use crate::SharedBytesBuilder;
+
+    let mut builder = SharedBytesBuilder::new();
+    builder.write_uint32_field(1, 150);
+    builder.write_string_field(2, "hi");
+    builder.write_int32_field(3, -1);
+    builder.write_fixed32_field(4, 1);
+    builder.write_sfixed64_field(5, -1);
+    builder.write_float_field(6, 1.0);
+    let data: ByteData<'_> = builder.into();
+    assert_eq!(
+        data.as_slice(),
+        b"\x08\x96\x01\x12\x02hi\x18\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x01\x25\x01\x00\x00\x00\x29\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x35\x00\x00\x80\x3F"
+    );
+    assert_eq!(Fields::new(data).count(), 6);
+}
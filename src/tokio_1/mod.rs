@@ -0,0 +1,105 @@
+//! Support for the asynchronous I/O traits of `tokio@1`.
+//!
+//! `AsyncRead` and `AsyncBufRead` are implemented for [`ByteData`](crate::ByteData), [`SharedBytes`](crate::SharedBytes) and [`ByteQueue`](crate::ByteQueue),
+//! and `AsyncWrite` is implemented for [`SharedBytesBuilder`](crate::SharedBytesBuilder) and [`ByteQueue`](crate::ByteQueue).
+//! These are always ready, as all data is already in memory.
+//!
+//! The helpers in this module read from any `AsyncRead` into shared buffers without zero-initialising them first.
+
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::tokio_1::io::{AsyncRead, ReadBuf};
+
+use crate::{ByteData, SharedBytesBuilder};
+
+mod bytedata;
+#[cfg(feature = "queue")]
+mod queue;
+mod shared_bytes;
+mod shared_bytes_builder;
+
+#[cfg(test)]
+mod tests;
+
+/// Reads once from `reader` into the unfilled capacity of `builder`.
+fn poll_read_builder<R: AsyncRead + ?Sized>(
+    reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    builder: &mut SharedBytesBuilder,
+) -> Poll<std::io::Result<usize>> {
+    builder.apply_unfilled(|unfilled| {
+        let mut buf = ReadBuf::uninit(unfilled);
+        match reader.poll_read(cx, &mut buf) {
+            Poll::Ready(Ok(())) => {
+                let len = buf.filled().len();
+                (Poll::Ready(Ok(len)), len)
+            }
+            Poll::Ready(Err(err)) => (Poll::Ready(Err(err)), 0),
+            Poll::Pending => (Poll::Pending, 0),
+        }
+    })
+}
+
+#[cfg(feature = "queue")]
+/// Reads from `reader` until `builder` is full or the end of the input is reached.
+/// Returns `true` if the end of the input was reached.
+async fn fill_builder<R: AsyncRead + Unpin + ?Sized>(
+    reader: &mut R,
+    builder: &mut SharedBytesBuilder,
+) -> std::io::Result<bool> {
+    while builder.len() < builder.capacity() {
+        let len =
+            core::future::poll_fn(|cx| poll_read_builder(Pin::new(&mut *reader), cx, builder))
+                .await?;
+        if len == 0 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Performs a single read of at most `chunk_size` bytes from `reader` into a new shared buffer.
+///
+/// Returns empty data at the end of the input. Reads of at most [`ByteChunk::LEN`](crate::ByteChunk::LEN) bytes are returned as a chunk.
+/// A `chunk_size` of `0` is treated as `1`.
+///
+/// # Errors
+///
+/// Returns any error returned by the reader.
+#[inline]
+pub async fn read_chunk<R: AsyncRead + Unpin + ?Sized>(
+    reader: &mut R,
+    chunk_size: usize,
+) -> std::io::Result<ByteData<'static>> {
+    let mut builder = SharedBytesBuilder::with_capacity(chunk_size.max(1));
+    core::future::poll_fn(|cx| poll_read_builder(Pin::new(&mut *reader), cx, &mut builder)).await?;
+    Ok(builder.into())
+}
+
+/// Reads all of `reader` into a queue of shared buffers that each hold up to `chunk_size` bytes.
+///
+/// A `chunk_size` of `0` is treated as `1`.
+///
+/// # Errors
+///
+/// Returns any error returned by the reader. The data read before the error is dropped.
+#[cfg(feature = "queue")]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+#[inline]
+pub async fn read_into_queue<R: AsyncRead + Unpin + ?Sized>(
+    reader: &mut R,
+    chunk_size: usize,
+) -> std::io::Result<crate::ByteQueue<'static>> {
+    let mut queue = crate::ByteQueue::new();
+    loop {
+        let mut builder = SharedBytesBuilder::with_capacity(chunk_size.max(1));
+        let done = fill_builder(reader, &mut builder).await?;
+        queue.push_back(ByteData::from(builder));
+        if done {
+            return Ok(queue);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::tokio_1::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+use crate::SharedBytes;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio_1")))]
+impl AsyncRead for SharedBytes {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let len = buf.remaining().min(this.len());
+        buf.put_slice(&this.as_slice()[..len]);
+        this.make_sliced(len, this.len() - len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio_1")))]
+impl AsyncBufRead for SharedBytes {
+    #[inline]
+    fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().as_slice()))
+    }
+
+    #[inline]
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.make_sliced(amt, this.len() - amt);
+    }
+}
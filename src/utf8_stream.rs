@@ -0,0 +1,277 @@
+use crate::{ByteChunk, ByteData, StringData, Utf8CharError};
+
+/// The string emitted for an invalid sequence in lossy mode.
+const REPLACEMENT: StringData<'static> = StringData::from_static("\u{FFFD}");
+
+/// An incremental UTF-8 decoder for data that arrives in chunks, such as reads from a socket.
+///
+/// Every chunk passed to [`Utf8StreamDecoder::decode`] is split into slices of the chunk without copying.
+/// The start of a character that is split between chunks, at most 3 bytes, is held back in a [`ByteChunk`]
+/// and emitted on its own once the next chunk completes it.
+///
+/// In strict mode invalid sequences are reported as errors, in lossy mode they are replaced with `U+FFFD`.
+/// In both modes decoding continues after an invalid sequence.
+/// Error positions count from the start of the stream.
+///
+/// ```
+/// use ::bytedata::{ByteData, Utf8StreamDecoder};
+///
+/// let mut decoder = Utf8StreamDecoder::new();
+/// let mut out = String::new();
+/// for chunk in [b"caf\xC3".as_slice(), b"\xA9 \xE2\x9C", b"\x93"] {
+///     for part in decoder.decode(ByteData::from_borrowed(chunk)) {
+///         out.push_str(part.unwrap().as_str());
+///     }
+/// }
+/// assert!(decoder.finish().is_ok());
+/// assert_eq!(out, "caf\u{e9} \u{2713}");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Utf8StreamDecoder {
+    /// The start of an incomplete character at the end of the previous chunk.
+    pending: ByteChunk,
+    /// The number of bytes decoded so far, excluding the pending bytes.
+    position: usize,
+    /// Replace invalid sequences instead of reporting them.
+    lossy: bool,
+}
+
+impl Utf8StreamDecoder {
+    /// Creates a decoder that reports invalid sequences as errors.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            pending: ByteChunk {
+                len: 0,
+                data: [0; ByteChunk::LEN],
+            },
+            position: 0,
+            lossy: false,
+        }
+    }
+
+    /// Creates a decoder that replaces invalid sequences with `U+FFFD`.
+    #[inline]
+    #[must_use]
+    pub const fn lossy() -> Self {
+        let mut out = Self::new();
+        out.lossy = true;
+        out
+    }
+
+    /// Returns `true` if the decoder replaces invalid sequences instead of reporting them.
+    #[inline]
+    #[must_use]
+    pub const fn is_lossy(&self) -> bool {
+        self.lossy
+    }
+
+    /// Returns the number of bytes held back from the end of the previous chunk.
+    #[inline]
+    #[must_use]
+    pub const fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Decodes the next chunk of the stream.
+    ///
+    /// The returned iterator yields the decoded strings in order. Any part of the chunk that is
+    /// not consumed when the iterator is dropped is skipped, so it should be run to the end.
+    #[inline]
+    pub fn decode<'a>(&mut self, chunk: ByteData<'a>) -> Utf8StreamChunks<'a, '_> {
+        Utf8StreamChunks {
+            decoder: self,
+            rest: chunk,
+        }
+    }
+
+    /// Ends the stream and resets the decoder, so it can be used for a new stream.
+    ///
+    /// Returns `U+FFFD` in lossy mode if the stream ended with an incomplete character, otherwise an empty string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the position and length of the incomplete character in strict mode if the stream ended with one.
+    #[inline]
+    pub fn finish(&mut self) -> Result<StringData<'static>, Utf8CharError> {
+        let held = self.pending.len;
+        let error = Utf8CharError {
+            valid_up_to: self.position,
+            error_len: held,
+        };
+        let lossy = self.lossy;
+        *self = Self::new();
+        self.lossy = lossy;
+        if held == 0 {
+            return Ok(StringData::empty());
+        }
+        self.invalid(error)
+    }
+
+    /// Decodes a chunk and appends the decoded strings to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid sequence in strict mode. The whole chunk is still decoded and invalid sequences are skipped.
+    #[cfg(feature = "queue")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+    #[inline]
+    pub fn decode_into<'a>(
+        &mut self,
+        chunk: ByteData<'a>,
+        out: &mut crate::StringQueue<'a>,
+    ) -> Result<(), Utf8CharError> {
+        let mut result = Ok(());
+        for part in self.decode(chunk) {
+            match part {
+                Ok(part) => out.push_back(part),
+                Err(err) => {
+                    if result.is_ok() {
+                        result = Err(err);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Decodes all chunks of a queue and appends the decoded strings to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid sequence in strict mode. The whole queue is still decoded and invalid sequences are skipped.
+    #[cfg(feature = "queue")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+    #[inline]
+    pub fn decode_queue_into<'a>(
+        &mut self,
+        input: crate::ByteQueue<'a>,
+        out: &mut crate::StringQueue<'a>,
+    ) -> Result<(), Utf8CharError> {
+        let mut result = Ok(());
+        for chunk in input {
+            let chunk_result = self.decode_into(chunk, out);
+            if result.is_ok() {
+                result = chunk_result;
+            }
+        }
+        result
+    }
+
+    /// Reports an invalid sequence, or replaces it in lossy mode.
+    const fn invalid(&self, error: Utf8CharError) -> Result<StringData<'static>, Utf8CharError> {
+        if self.lossy {
+            Ok(REPLACEMENT)
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Completes the pending character with bytes from the start of `rest`.
+    /// Returns `None` if `rest` ends before the character is complete.
+    fn complete_pending<'a>(
+        &mut self,
+        rest: &mut ByteData<'a>,
+    ) -> Option<Result<StringData<'a>, Utf8CharError>> {
+        let held = self.pending.len();
+        let width = crate::pattern::utf8_width(self.pending.as_slice()[0]);
+        let take = rest.len().min(width - held);
+        let mut buf = [0_u8; 4];
+        buf[..held].copy_from_slice(self.pending.as_slice());
+        buf[held..held + take].copy_from_slice(&rest.as_slice()[..take]);
+        let buf = &buf[..held + take];
+        match core::str::from_utf8(buf) {
+            Ok(_) => {
+                rest.make_sliced(take..);
+                self.pending = ByteChunk::default();
+                self.position += width;
+                let data = ByteData::from_chunk_slice(buf);
+                // SAFETY: the bytes have been checked to be valid UTF-8.
+                Some(Ok(unsafe { StringData::from_bytedata_unchecked(data) }))
+            }
+            Err(err) => {
+                let Some(len) = err.error_len() else {
+                    // still incomplete, `take` is the whole rest
+                    *rest = ByteData::empty();
+                    self.pending = ByteChunk::from_slice(buf);
+                    return None;
+                };
+                // the pending bytes are a valid prefix, so the invalid sequence includes all of them
+                rest.make_sliced(len - held..);
+                self.pending = ByteChunk::default();
+                let error = self.error_at(len);
+                Some(self.invalid(error))
+            }
+        }
+    }
+
+    /// Returns the error for an invalid sequence of `len` bytes at the current position, and skips it.
+    #[allow(clippy::cast_possible_truncation)]
+    fn error_at(&mut self, len: usize) -> Utf8CharError {
+        let error = Utf8CharError {
+            valid_up_to: self.position,
+            // an invalid sequence is at most 3 bytes long
+            error_len: len as u8,
+        };
+        self.position += len;
+        error
+    }
+}
+
+impl Default for Utf8StreamDecoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator over the strings decoded from a chunk, returned by [`Utf8StreamDecoder::decode`].
+#[derive(Debug)]
+pub struct Utf8StreamChunks<'a, 'd> {
+    decoder: &'d mut Utf8StreamDecoder,
+    rest: ByteData<'a>,
+}
+
+impl<'a> Iterator for Utf8StreamChunks<'a, '_> {
+    type Item = Result<StringData<'a>, Utf8CharError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        if !self.decoder.pending.is_empty() {
+            return self.decoder.complete_pending(&mut self.rest);
+        }
+
+        let bytes = self.rest.as_slice();
+        let (valid, error_len) = match core::str::from_utf8(bytes) {
+            Ok(_) => (bytes.len(), None),
+            Err(err) => (err.valid_up_to(), err.error_len()),
+        };
+        if valid != 0 {
+            let data = if valid == bytes.len() {
+                core::mem::take(&mut self.rest)
+            } else {
+                let data = self.rest.sliced(..valid);
+                self.rest.make_sliced(valid..);
+                data
+            };
+            self.decoder.position += valid;
+            // SAFETY: the bytes have been checked to be valid UTF-8.
+            return Some(Ok(unsafe { StringData::from_bytedata_unchecked(data) }));
+        }
+        let Some(len) = error_len else {
+            // an incomplete character at the end of the chunk
+            self.decoder.pending = ByteChunk::from_slice(bytes);
+            self.rest = ByteData::empty();
+            return None;
+        };
+        self.rest.make_sliced(len..);
+        let error = self.decoder.error_at(len);
+        Some(self.decoder.invalid(error))
+    }
+}
+
+impl core::iter::FusedIterator for Utf8StreamChunks<'_, '_> {}
@@ -0,0 +1,189 @@
+use ::winnow_0::{
+    error::Needed,
+    stream::{
+        AsBStr, AsBytes, Compare, CompareResult, FindSlice, Offset, ParseSlice, SliceLen, Stream,
+        StreamIsPartial, UpdateSlice,
+    },
+};
+
+use crate::{ByteData, StringData};
+
+impl SliceLen for ByteData<'_> {
+    #[inline]
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Stream for ByteData<'_> {
+    type Token = u8;
+    type Slice = Self;
+    type IterOffsets = core::iter::Enumerate<Self>;
+    type Checkpoint = Self;
+
+    #[inline]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        Iterator::enumerate(self.clone())
+    }
+
+    #[inline]
+    fn eof_offset(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.peek_token()?;
+        self.make_sliced(1..);
+        Some(token)
+    }
+
+    #[inline]
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.as_slice().first().copied()
+    }
+
+    #[inline]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.as_slice().iter().position(|&token| predicate(token))
+    }
+
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.as_slice().offset_at(tokens)
+    }
+
+    #[inline]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let slice = self.sliced(..offset);
+        self.make_sliced(offset..);
+        slice
+    }
+
+    #[inline]
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        self.sliced(..offset)
+    }
+
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    #[inline]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.clone_from(checkpoint);
+    }
+
+    #[inline]
+    fn raw(&self) -> &dyn core::fmt::Debug {
+        self
+    }
+}
+
+impl StreamIsPartial for ByteData<'_> {
+    type PartialState = ();
+
+    #[inline]
+    fn complete(&mut self) -> Self::PartialState {}
+
+    #[inline]
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for ByteData<'_> {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        start.len() - self.len()
+    }
+}
+
+impl AsBytes for ByteData<'_> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsBStr for ByteData<'_> {
+    #[inline]
+    fn as_bstr(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<T> Compare<T> for ByteData<'_>
+where
+    for<'s> &'s [u8]: Compare<T>,
+{
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: T) -> CompareResult {
+        self.as_slice().compare(t)
+    }
+}
+
+impl<'b> Compare<ByteData<'b>> for &[u8] {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: ByteData<'b>) -> CompareResult {
+        self.compare(t.as_slice())
+    }
+}
+
+impl<'b> Compare<StringData<'b>> for &[u8] {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: StringData<'b>) -> CompareResult {
+        self.compare(t.as_str())
+    }
+}
+
+impl<T> FindSlice<T> for ByteData<'_>
+where
+    for<'s> &'s [u8]: FindSlice<T>,
+{
+    #[inline]
+    fn find_slice(&self, substr: T) -> Option<core::ops::Range<usize>> {
+        self.as_slice().find_slice(substr)
+    }
+}
+
+impl<'b> FindSlice<ByteData<'b>> for &[u8] {
+    #[inline]
+    fn find_slice(&self, substr: ByteData<'b>) -> Option<core::ops::Range<usize>> {
+        self.find_slice(substr.as_slice())
+    }
+}
+
+impl<'b> FindSlice<StringData<'b>> for &[u8] {
+    #[inline]
+    fn find_slice(&self, substr: StringData<'b>) -> Option<core::ops::Range<usize>> {
+        self.find_slice(substr.as_str())
+    }
+}
+
+impl<R> ParseSlice<R> for ByteData<'_>
+where
+    for<'s> &'s [u8]: ParseSlice<R>,
+{
+    #[inline]
+    fn parse_slice(&self) -> Option<R> {
+        self.as_slice().parse_slice()
+    }
+}
+
+impl UpdateSlice for ByteData<'_> {
+    #[inline]
+    fn update_slice(self, inner: Self::Slice) -> Self {
+        inner
+    }
+}
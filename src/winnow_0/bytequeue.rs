@@ -0,0 +1,169 @@
+use core::num::NonZeroUsize;
+
+use ::winnow_0::{
+    ascii::Caseless,
+    error::Needed,
+    stream::{
+        Compare, CompareResult, FindSlice, Offset, SliceLen, Stream, StreamIsPartial, UpdateSlice,
+    },
+};
+
+use crate::{ByteData, ByteQueue, StringData};
+
+impl SliceLen for ByteQueue<'_> {
+    #[inline]
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a> Stream for ByteQueue<'a> {
+    type Token = u8;
+    type Slice = Self;
+    type IterOffsets = core::iter::Enumerate<crate::queue::OwnedByteIter<'a>>;
+    type Checkpoint = Self;
+
+    #[inline]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        Iterator::enumerate(self.clone().into_bytes())
+    }
+
+    #[inline]
+    fn eof_offset(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.peek_token()?;
+        self.consume(1);
+        Some(token)
+    }
+
+    #[inline]
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.bytes().next()
+    }
+
+    #[inline]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.find_byte(predicate)
+    }
+
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        tokens
+            .checked_sub(self.len())
+            .and_then(NonZeroUsize::new)
+            .map_or(Ok(tokens), |needed| Err(Needed::Size(needed)))
+    }
+
+    #[inline]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let rest = self.split_off(offset);
+        core::mem::replace(self, rest)
+    }
+
+    #[inline]
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        self.slice(..offset)
+    }
+
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    #[inline]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.clone_from(checkpoint);
+    }
+
+    #[inline]
+    fn raw(&self) -> &dyn core::fmt::Debug {
+        self
+    }
+}
+
+impl StreamIsPartial for ByteQueue<'_> {
+    type PartialState = ();
+
+    #[inline]
+    fn complete(&mut self) -> Self::PartialState {}
+
+    #[inline]
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for ByteQueue<'_> {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        start.len() - self.len()
+    }
+}
+
+/// Implements `Compare` for literals that are converted to bytes by `$bytes`.
+macro_rules! impl_compare {
+    ($([$($gen:tt)*] $lit:ty => |$t:ident| $bytes:expr, $caseless:literal;)*) => {$(
+        impl<$($gen)*> Compare<$lit> for ByteQueue<'_> {
+            #[inline]
+            #[allow(clippy::min_ident_chars)]
+            fn compare(&self, $t: $lit) -> CompareResult {
+                super::compare_queue(self, $bytes, $caseless)
+            }
+        }
+    )*};
+}
+
+impl_compare! {
+    [] u8 => |t| &[t], false;
+    [] char => |t| t.encode_utf8(&mut [0; 4]).as_bytes(), false;
+    ['b] &'b [u8] => |t| t, false;
+    ['b, const N: usize] &'b [u8; N] => |t| t, false;
+    [const N: usize] [u8; N] => |t| &t, false;
+    ['b] &'b str => |t| t.as_bytes(), false;
+    ['b] ByteData<'b> => |t| t.as_slice(), false;
+    ['b] StringData<'b> => |t| t.as_bytes(), false;
+    [] Caseless<u8> => |t| &[t.0], true;
+    [] Caseless<char> => |t| t.0.encode_utf8(&mut [0; 4]).as_bytes(), true;
+    ['b] Caseless<&'b [u8]> => |t| t.0, true;
+    ['b, const N: usize] Caseless<&'b [u8; N]> => |t| t.0, true;
+    [const N: usize] Caseless<[u8; N]> => |t| &t.0, true;
+    ['b] Caseless<&'b str> => |t| t.0.as_bytes(), true;
+}
+
+/// Implements `FindSlice` for needles that are converted to bytes by `$bytes`.
+macro_rules! impl_find_slice {
+    ($([$($gen:tt)*] $needle:ty => |$t:ident| $bytes:expr;)*) => {$(
+        impl<$($gen)*> FindSlice<$needle> for ByteQueue<'_> {
+            #[inline]
+            fn find_slice(&self, $t: $needle) -> Option<core::ops::Range<usize>> {
+                super::find_queue(self, $bytes)
+            }
+        }
+    )*};
+}
+
+impl_find_slice! {
+    [] u8 => |substr| &[substr];
+    [] char => |substr| substr.encode_utf8(&mut [0; 4]).as_bytes();
+    ['b] &'b [u8] => |substr| substr;
+    ['b] &'b str => |substr| substr.as_bytes();
+    ['b] ByteData<'b> => |substr| substr.as_slice();
+    ['b] StringData<'b> => |substr| substr.as_bytes();
+}
+
+impl UpdateSlice for ByteQueue<'_> {
+    #[inline]
+    fn update_slice(self, inner: Self::Slice) -> Self {
+        inner
+    }
+}
@@ -0,0 +1,58 @@
+//! Support for parsing with `winnow@0.7`.
+//!
+//! [`Stream`](::winnow_0::stream::Stream) is implemented for [`ByteData`](crate::ByteData), [`StringData`](crate::StringData),
+//! [`ByteQueue`](crate::ByteQueue) and [`StringQueue`](crate::StringQueue), and each type is its own slice type,
+//! so the parsed outputs share the input instead of borrowing from it.
+//! A checkpoint is a clone of the input, and offsets between checkpoints are measured in remaining bytes,
+//! which also makes `LocatingSlice` work for all four types.
+//!
+//! The types are complete inputs on their own. Wrapping a queue in `Partial` lets parsers return `Incomplete`
+//! when the queue ends before a parser does, so more data can be pushed to the queue before parsing again.
+
+#[cfg(feature = "queue")]
+use ::winnow_0::stream::CompareResult;
+
+mod bytedata;
+mod stringdata;
+
+#[cfg(feature = "queue")]
+mod bytequeue;
+#[cfg(feature = "queue")]
+mod stringqueue;
+
+#[cfg(test)]
+mod tests;
+
+/// Compares the start of a queue with `literal`, which may span multiple chunks.
+#[cfg(feature = "queue")]
+fn compare_queue(queue: &crate::ByteQueue<'_>, literal: &[u8], caseless: bool) -> CompareResult {
+    let mut rest = literal;
+    for chunk in queue.chunks() {
+        if rest.is_empty() {
+            break;
+        }
+        let (head, tail) = rest.split_at(chunk.len().min(rest.len()));
+        let part = &chunk.as_slice()[..head.len()];
+        let matches = if caseless {
+            part.eq_ignore_ascii_case(head)
+        } else {
+            part == head
+        };
+        if !matches {
+            return CompareResult::Error;
+        }
+        rest = tail;
+    }
+    if rest.is_empty() {
+        CompareResult::Ok(literal.len())
+    } else {
+        CompareResult::Incomplete
+    }
+}
+
+/// Finds the first occurrence of `needle` in a queue, returning its range.
+#[cfg(feature = "queue")]
+fn find_queue(queue: &crate::ByteQueue<'_>, needle: &[u8]) -> Option<core::ops::Range<usize>> {
+    let start = queue.find_slice(needle)?;
+    Some(start..start + needle.len())
+}
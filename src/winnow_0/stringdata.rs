@@ -0,0 +1,167 @@
+use ::winnow_0::{
+    error::Needed,
+    stream::{
+        AsBStr, Compare, CompareResult, FindSlice, Offset, ParseSlice, SliceLen, Stream,
+        StreamIsPartial, UpdateSlice,
+    },
+};
+
+use crate::StringData;
+
+impl SliceLen for StringData<'_> {
+    #[inline]
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Stream for StringData<'_> {
+    type Token = char;
+    type Slice = Self;
+    type IterOffsets = crate::char_indicies::CharOffsets<Self>;
+    type Checkpoint = Self;
+
+    #[inline]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.clone().scan(0, crate::char_indicies::char_offset)
+    }
+
+    #[inline]
+    fn eof_offset(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.peek_token()?;
+        self.make_sliced(token.len_utf8()..);
+        Some(token)
+    }
+
+    #[inline]
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.as_str().chars().next()
+    }
+
+    #[inline]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.as_str().offset_for(predicate)
+    }
+
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.as_str().offset_at(tokens)
+    }
+
+    #[inline]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let slice = self.sliced(..offset);
+        self.make_sliced(offset..);
+        slice
+    }
+
+    #[inline]
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        self.sliced(..offset)
+    }
+
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    #[inline]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.clone_from(checkpoint);
+    }
+
+    #[inline]
+    fn raw(&self) -> &dyn core::fmt::Debug {
+        self
+    }
+}
+
+impl StreamIsPartial for StringData<'_> {
+    type PartialState = ();
+
+    #[inline]
+    fn complete(&mut self) -> Self::PartialState {}
+
+    #[inline]
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for StringData<'_> {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        start.len() - self.len()
+    }
+}
+
+impl AsBStr for StringData<'_> {
+    #[inline]
+    fn as_bstr(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<T> Compare<T> for StringData<'_>
+where
+    for<'s> &'s str: Compare<T>,
+{
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: T) -> CompareResult {
+        self.as_str().compare(t)
+    }
+}
+
+impl<'b> Compare<StringData<'b>> for &str {
+    #[inline]
+    #[allow(clippy::min_ident_chars)]
+    fn compare(&self, t: StringData<'b>) -> CompareResult {
+        self.compare(t.as_str())
+    }
+}
+
+impl<T> FindSlice<T> for StringData<'_>
+where
+    for<'s> &'s str: FindSlice<T>,
+{
+    #[inline]
+    fn find_slice(&self, substr: T) -> Option<core::ops::Range<usize>> {
+        self.as_str().find_slice(substr)
+    }
+}
+
+impl<'b> FindSlice<StringData<'b>> for &str {
+    #[inline]
+    fn find_slice(&self, substr: StringData<'b>) -> Option<core::ops::Range<usize>> {
+        self.find_slice(substr.as_str())
+    }
+}
+
+impl<R> ParseSlice<R> for StringData<'_>
+where
+    for<'s> &'s str: ParseSlice<R>,
+{
+    #[inline]
+    fn parse_slice(&self) -> Option<R> {
+        self.as_str().parse_slice()
+    }
+}
+
+impl UpdateSlice for StringData<'_> {
+    #[inline]
+    fn update_slice(self, inner: Self::Slice) -> Self {
+        inner
+    }
+}
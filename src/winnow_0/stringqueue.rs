@@ -0,0 +1,162 @@
+use ::winnow_0::{
+    ascii::Caseless,
+    error::Needed,
+    stream::{
+        Compare, CompareResult, FindSlice, Offset, SliceLen, Stream, StreamIsPartial, UpdateSlice,
+    },
+};
+
+use crate::{StringData, StringQueue};
+
+impl SliceLen for StringQueue<'_> {
+    #[inline]
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a> Stream for StringQueue<'a> {
+    type Token = char;
+    type Slice = Self;
+    type IterOffsets = crate::char_indicies::CharOffsets<crate::queue::OwnedCharIter<'a>>;
+    type Checkpoint = Self;
+
+    #[inline]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.clone()
+            .into_chars()
+            .scan(0, crate::char_indicies::char_offset)
+    }
+
+    #[inline]
+    fn eof_offset(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let token = self.peek_token()?;
+        let rest = self.split_off(token.len_utf8());
+        *self = rest;
+        Some(token)
+    }
+
+    #[inline]
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.chars().next()
+    }
+
+    #[inline]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.chars_indecies()
+            .find(|&(_, token)| predicate(token))
+            .map(|(offset, _)| offset)
+    }
+
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        let mut offsets = self.chars_indecies().map(|(offset, _)| offset);
+        match offsets.nth(tokens) {
+            Some(offset) => Ok(offset),
+            None if offsets.count() == 0 && self.chars().count() == tokens => Ok(self.len()),
+            None => Err(Needed::Unknown),
+        }
+    }
+
+    #[inline]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let rest = self.split_off(offset);
+        core::mem::replace(self, rest)
+    }
+
+    #[inline]
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        self.slice(..offset)
+    }
+
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    #[inline]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.clone_from(checkpoint);
+    }
+
+    #[inline]
+    fn raw(&self) -> &dyn core::fmt::Debug {
+        self
+    }
+}
+
+impl StreamIsPartial for StringQueue<'_> {
+    type PartialState = ();
+
+    #[inline]
+    fn complete(&mut self) -> Self::PartialState {}
+
+    #[inline]
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for StringQueue<'_> {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        start.len() - self.len()
+    }
+}
+
+/// Implements `Compare` for literals that are converted to bytes by `$bytes`.
+macro_rules! impl_compare {
+    ($([$($gen:tt)*] $lit:ty => |$t:ident| $bytes:expr, $caseless:literal;)*) => {$(
+        impl<$($gen)*> Compare<$lit> for StringQueue<'_> {
+            #[inline]
+            #[allow(clippy::min_ident_chars)]
+            fn compare(&self, $t: $lit) -> CompareResult {
+                super::compare_queue(self.as_bytequeue(), $bytes, $caseless)
+            }
+        }
+    )*};
+}
+
+impl_compare! {
+    [] char => |t| t.encode_utf8(&mut [0; 4]).as_bytes(), false;
+    ['b] &'b str => |t| t.as_bytes(), false;
+    ['b] StringData<'b> => |t| t.as_bytes(), false;
+    [] Caseless<char> => |t| t.0.encode_utf8(&mut [0; 4]).as_bytes(), true;
+    ['b] Caseless<&'b str> => |t| t.0.as_bytes(), true;
+}
+
+/// Implements `FindSlice` for needles that are converted to bytes by `$bytes`.
+macro_rules! impl_find_slice {
+    ($([$($gen:tt)*] $needle:ty => |$t:ident| $bytes:expr;)*) => {$(
+        impl<$($gen)*> FindSlice<$needle> for StringQueue<'_> {
+            #[inline]
+            fn find_slice(&self, $t: $needle) -> Option<core::ops::Range<usize>> {
+                super::find_queue(self.as_bytequeue(), $bytes)
+            }
+        }
+    )*};
+}
+
+impl_find_slice! {
+    [] char => |substr| substr.encode_utf8(&mut [0; 4]).as_bytes();
+    ['b] &'b str => |substr| substr.as_bytes();
+    ['b] StringData<'b> => |substr| substr.as_bytes();
+}
+
+impl UpdateSlice for StringQueue<'_> {
+    #[inline]
+    fn update_slice(self, inner: Self::Slice) -> Self {
+        inner
+    }
+}
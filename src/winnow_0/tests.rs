@@ -0,0 +1,149 @@
+use ::winnow_0::{
+    ascii::{alpha1, space1},
+    error::ContextError,
+    stream::{AsChar, Stream, StreamIsPartial},
+    token::{literal, take_until},
+    LocatingSlice, Parser,
+};
+
+fn read_word<I>(
+    input: &mut I,
+) -> winnow_0::ModalResult<(<I as Stream>::Slice, <I as Stream>::Slice)>
+where
+    I: Stream + StreamIsPartial,
+    <I as Stream>::Token: AsChar + Clone,
+{
+    (alpha1, space1).parse_next(input)
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_winnow_string() {
+    let mut st = crate::StringData::from_static("hello world");
+    let (word, space) = read_word(&mut st).unwrap();
+    assert_eq!(word, "hello");
+    assert_eq!(space, " ");
+    let word: crate::StringData<'_> = alpha1::<_, ContextError>.parse(st).unwrap();
+    assert_eq!(word, "world");
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_winnow_bytes_shared() {
+    let data = crate::ByteData::from_static(b"header: a value that is longer than a chunk\r\nrest");
+    let mut input = data.clone();
+    let name = take_until::<_, _, ContextError>(0.., b": ".as_slice())
+        .parse_next(&mut input)
+        .unwrap();
+    assert_eq!(name, b"header".as_slice());
+    literal::<_, _, ContextError>(": ")
+        .parse_next(&mut input)
+        .unwrap();
+    let value = take_until::<_, _, ContextError>(0.., "\r\n")
+        .parse_next(&mut input)
+        .unwrap();
+    assert_eq!(value, b"a value that is longer than a chunk".as_slice());
+    assert_eq!(value.as_slice().as_ptr(), data.as_slice()[8..].as_ptr());
+    assert_eq!(input, b"\r\nrest".as_slice());
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_winnow_locating() {
+    let mut input = LocatingSlice::new(crate::StringData::from_static("hello world"));
+    alpha1::<_, ContextError>.parse_next(&mut input).unwrap();
+    space1::<_, ContextError>.parse_next(&mut input).unwrap();
+    let (word, span) = alpha1::<_, ContextError>
+        .with_span()
+        .parse_next(&mut input)
+        .unwrap();
+    assert_eq!(word, "world");
+    assert_eq!(span, 6..11);
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_winnow_string_queue() {
+    let mut st = crate::StringQueue::from_iter([
+        crate::StringData::from_static("hel"),
+        crate::StringData::from_static("lo"),
+        crate::StringData::from_static(" w"),
+        crate::StringData::from_static("orld"),
+    ]);
+    let (word, space) = read_word(&mut st).unwrap();
+    assert_eq!(word, "hello");
+    assert_eq!(space, " ");
+    assert_eq!(
+        ::winnow_0::stream::Compare::compare(&st, "wor"),
+        ::winnow_0::stream::CompareResult::Ok(3)
+    );
+    let word = alpha1::<_, ContextError>.parse(st).unwrap();
+    assert_eq!(word, "world");
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_winnow_byte_queue() {
+    use ::winnow_0::token::take;
+
+    let mut queue = crate::ByteQueue::from_iter([
+        crate::ByteData::from_static(b"GET /in"),
+        crate::ByteData::from_static(b"dex HTTP/1.1\r"),
+        crate::ByteData::from_static(b"\n"),
+    ]);
+    let method = take_until::<_, _, ContextError>(1.., b' ')
+        .parse_next(&mut queue)
+        .unwrap();
+    assert_eq!(method, b"GET".as_slice());
+    take::<_, _, ContextError>(1_usize)
+        .parse_next(&mut queue)
+        .unwrap();
+    let path = take_until::<_, _, ContextError>(1.., " ")
+        .parse_next(&mut queue)
+        .unwrap();
+    assert_eq!(path, b"/index".as_slice());
+    assert_eq!(path.chunk_len(), 2);
+    let version = take_until::<_, _, ContextError>(0.., b"\r\n".as_slice())
+        .parse_next(&mut queue)
+        .unwrap();
+    assert_eq!(version, b" HTTP/1.1".as_slice());
+    assert_eq!(queue, b"\r\n".as_slice());
+}
+
+#[cfg(feature = "queue")]
+#[test]
+#[allow(clippy::unwrap_used, clippy::shadow_unrelated)]
+fn test_winnow_partial_queue() {
+    use ::winnow_0::{
+        error::{ErrMode, Needed},
+        Partial,
+    };
+
+    fn line(
+        input: &mut Partial<crate::ByteQueue<'static>>,
+    ) -> winnow_0::ModalResult<crate::ByteQueue<'static>> {
+        let line = take_until(0.., "\r\n").parse_next(input)?;
+        literal("\r\n").parse_next(input)?;
+        Ok(line)
+    }
+
+    let mut queue = crate::ByteQueue::new();
+    queue.push_back(crate::ByteData::from_static(b"first li"));
+    let mut input = Partial::new(queue.clone());
+    assert!(matches!(line(&mut input), Err(ErrMode::Incomplete(_))));
+
+    queue.push_back(crate::ByteData::from_static(b"ne\r"));
+    let mut input = Partial::new(queue.clone());
+    assert!(matches!(line(&mut input), Err(ErrMode::Incomplete(_))));
+    assert_eq!(
+        literal::<_, _, ErrMode<ContextError>>("first line\r\n").parse_next(&mut input),
+        Err(ErrMode::Incomplete(Needed::Unknown))
+    );
+
+    queue.push_back(crate::ByteData::from_static(b"\nsecond"));
+    let mut input = Partial::new(queue);
+    assert_eq!(line(&mut input).unwrap(), b"first line".as_slice());
+    assert_eq!(input.into_inner(), b"second".as_slice());
+}
@@ -0,0 +1,129 @@
+use crate::{ByteCursor, ByteData};
+
+use super::{WireError, WireType, WireValue};
+
+/// An iterator over the fields of a message in the protobuf wire format.
+///
+/// Each item is the field number and value of a field. Length-delimited values are slices of the input,
+/// which are only copied if the value is split between chunks of a [`ByteQueue`](crate::ByteQueue).
+/// The iterator stops after the first error.
+///
+/// ```
+/// use ::bytedata::wire::{Fields, WireValue};
+///
+/// let mut fields = Fields::new(b"\x08\x96\x01\x12\x02hi".as_slice());
+/// assert_eq!(fields.next(), Some(Ok((1, WireValue::Varint(150)))));
+/// let Some(Ok((2, WireValue::LengthDelimited(text)))) = fields.next() else {
+///     unreachable!()
+/// };
+/// assert_eq!(text.as_slice(), b"hi");
+/// assert_eq!(fields.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    cursor: ByteCursor<'a>,
+    failed: bool,
+}
+
+impl<'a> Fields<'a> {
+    /// Creates an iterator over the fields in `data`.
+    #[inline]
+    #[must_use]
+    pub fn new<T: Into<ByteData<'a>>>(data: T) -> Self {
+        Self::from_cursor(ByteCursor::new(data))
+    }
+
+    /// Creates an iterator over the fields in a queue.
+    #[cfg(all(feature = "queue", feature = "alloc"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "queue", feature = "alloc"))))]
+    #[inline]
+    #[must_use]
+    pub const fn from_queue(queue: crate::ByteQueue<'a>) -> Self {
+        Self::from_cursor(ByteCursor::from_queue(queue))
+    }
+
+    /// Creates an iterator over the fields after the position of `cursor`.
+    #[inline]
+    #[must_use]
+    pub const fn from_cursor(cursor: ByteCursor<'a>) -> Self {
+        Self {
+            cursor,
+            failed: false,
+        }
+    }
+
+    /// Returns the position of the next field in the input.
+    #[inline]
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.cursor.position()
+    }
+
+    /// Returns the cursor, positioned after the last field that was read.
+    #[inline]
+    #[must_use]
+    pub fn into_cursor(self) -> ByteCursor<'a> {
+        self.cursor
+    }
+
+    /// Reads the next field, leaving the position at the start of the field if it fails.
+    fn read_field(&mut self) -> Result<(u32, WireValue<'a>), WireError> {
+        let start = self.cursor.position();
+        let result = self.read_field_inner(start);
+        if result.is_err() {
+            self.cursor.set_position(start);
+        }
+        result
+    }
+
+    fn read_field_inner(&mut self, start: usize) -> Result<(u32, WireValue<'a>), WireError> {
+        let tag = super::read_varint(&mut self.cursor)?;
+        let field_number = tag >> 3_u32;
+        let field_number = match u32::try_from(field_number) {
+            Ok(number) if number != 0 && number <= super::MAX_FIELD_NUMBER => number,
+            _ => {
+                return Err(WireError::InvalidFieldNumber {
+                    position: start,
+                    field_number,
+                })
+            }
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let wire_type = (tag & 0x07) as u8;
+        let value = match WireType::from_u8(wire_type) {
+            Some(WireType::Varint) => WireValue::Varint(super::read_varint(&mut self.cursor)?),
+            Some(WireType::Fixed64) => WireValue::Fixed64(self.cursor.read_u64_le()?),
+            Some(WireType::LengthDelimited) => {
+                let len = super::read_varint(&mut self.cursor)?;
+                let len = usize::try_from(len).unwrap_or(usize::MAX);
+                WireValue::LengthDelimited(self.cursor.read_bytes(len)?)
+            }
+            Some(WireType::StartGroup) => WireValue::StartGroup,
+            Some(WireType::EndGroup) => WireValue::EndGroup,
+            Some(WireType::Fixed32) => WireValue::Fixed32(self.cursor.read_u32_le()?),
+            None => {
+                return Err(WireError::InvalidWireType {
+                    position: start,
+                    wire_type,
+                })
+            }
+        };
+        Ok((field_number, value))
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Result<(u32, WireValue<'a>), WireError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.cursor.remaining() == 0 {
+            return None;
+        }
+        let result = self.read_field();
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+impl core::iter::FusedIterator for Fields<'_> {}
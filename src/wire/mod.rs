@@ -0,0 +1,301 @@
+//! # Wire
+//!
+//! This module reads and writes the protobuf wire format: LEB128 varints, zigzag encoded signed integers,
+//! little-endian fixed-size values, and length-delimited bytes, each prefixed by a tag with the field number and wire type.
+//!
+//! [`Fields`] iterates over the fields of a message held in a [`ByteData`] or a [`ByteQueue`](crate::ByteQueue).
+//! Values may be split between chunks of a queue at any position, and length-delimited values are slices shared with the input.
+//! [`WireWrite`] pushes encoded fields into a [`ByteQueue`](crate::ByteQueue) or a [`SharedBytesBuilder`](crate::SharedBytesBuilder).
+//!
+#![cfg_attr(all(feature = "queue", feature = "alloc"), doc = "```")]
+#![cfg_attr(not(all(feature = "queue", feature = "alloc")), doc = "```ignore")]
+//! use ::bytedata::wire::{Fields, WireValue, WireWrite};
+//! use ::bytedata::{ByteData, ByteQueue};
+//!
+//! let mut queue = ByteQueue::new();
+//! queue.write_uint64_field(1, 150);
+//! queue.write_string_field(2, "testing");
+//! queue.write_sint32_field(3, -2);
+//!
+//! let mut fields = Fields::from_queue(queue);
+//! assert_eq!(fields.next(), Some(Ok((1, WireValue::Varint(150)))));
+//! assert_eq!(
+//!     fields.next(),
+//!     Some(Ok((2, WireValue::LengthDelimited(ByteData::from_static(b"testing")))))
+//! );
+//! let Some(Ok((3, WireValue::Varint(value)))) = fields.next() else { unreachable!() };
+//! assert_eq!(::bytedata::wire::zigzag_decode_32(value as u32), -2);
+//! assert_eq!(fields.next(), None);
+//! ```
+
+use crate::{ByteChunk, ByteCursor, ByteData, UnexpectedEnd};
+
+mod fields;
+pub use fields::Fields;
+
+mod write;
+pub use write::WireWrite;
+
+/// The largest valid field number.
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// The maximum length of an encoded varint in bytes.
+pub const MAX_VARINT_LEN: usize = 10;
+
+/// The wire type of a field, which tells how its value is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WireType {
+    /// A LEB128 varint.
+    Varint = 0,
+    /// A little-endian 64-bit value.
+    Fixed64 = 1,
+    /// A varint length followed by that many bytes.
+    LengthDelimited = 2,
+    /// The start of a group (deprecated).
+    StartGroup = 3,
+    /// The end of a group (deprecated).
+    EndGroup = 4,
+    /// A little-endian 32-bit value.
+    Fixed32 = 5,
+}
+
+impl WireType {
+    /// Returns the wire type with the given value, or `None` if it is not a valid wire type.
+    #[inline]
+    #[must_use]
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Varint),
+            1 => Some(Self::Fixed64),
+            2 => Some(Self::LengthDelimited),
+            3 => Some(Self::StartGroup),
+            4 => Some(Self::EndGroup),
+            5 => Some(Self::Fixed32),
+            _ => None,
+        }
+    }
+}
+
+/// The value of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WireValue<'a> {
+    /// A varint, which holds an unsigned, two's complement or zigzag encoded integer depending on the field type.
+    Varint(u64),
+    /// A 64-bit value, such as a `fixed64`, `sfixed64`, or `double`.
+    Fixed64(u64),
+    /// Length-delimited bytes, such as a string, bytes, embedded message, or packed repeated field.
+    LengthDelimited(ByteData<'a>),
+    /// The start of a group. The fields of the group follow up to the matching [`WireValue::EndGroup`].
+    StartGroup,
+    /// The end of a group.
+    EndGroup,
+    /// A 32-bit value, such as a `fixed32`, `sfixed32`, or `float`.
+    Fixed32(u32),
+}
+
+impl WireValue<'_> {
+    /// Returns the wire type of the value.
+    #[inline]
+    #[must_use]
+    pub const fn wire_type(&self) -> WireType {
+        match *self {
+            Self::Varint(_) => WireType::Varint,
+            Self::Fixed64(_) => WireType::Fixed64,
+            Self::LengthDelimited(_) => WireType::LengthDelimited,
+            Self::StartGroup => WireType::StartGroup,
+            Self::EndGroup => WireType::EndGroup,
+            Self::Fixed32(_) => WireType::Fixed32,
+        }
+    }
+}
+
+/// An error returned when decoding invalid wire format data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WireError {
+    /// The input ended in the middle of a field.
+    UnexpectedEnd(UnexpectedEnd),
+    /// The varint at `position` is longer than [`MAX_VARINT_LEN`] bytes or does not fit in 64 bits.
+    VarintOverflow {
+        /// The position of the varint in the input.
+        position: usize,
+    },
+    /// The tag at `position` has an unknown wire type.
+    InvalidWireType {
+        /// The position of the tag in the input.
+        position: usize,
+        /// The wire type.
+        wire_type: u8,
+    },
+    /// The tag at `position` has a field number of `0` or above [`MAX_FIELD_NUMBER`].
+    InvalidFieldNumber {
+        /// The position of the tag in the input.
+        position: usize,
+        /// The field number.
+        field_number: u64,
+    },
+}
+
+impl core::fmt::Display for WireError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Self::UnexpectedEnd(err) => core::fmt::Display::fmt(&err, f),
+            Self::VarintOverflow { position } => {
+                write!(f, "varint overflow at position {position}")
+            }
+            Self::InvalidWireType {
+                position,
+                wire_type,
+            } => write!(f, "invalid wire type {wire_type} at position {position}"),
+            Self::InvalidFieldNumber {
+                position,
+                field_number,
+            } => write!(
+                f,
+                "invalid field number {field_number} at position {position}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for WireError {}
+
+impl From<UnexpectedEnd> for WireError {
+    #[inline]
+    fn from(err: UnexpectedEnd) -> Self {
+        Self::UnexpectedEnd(err)
+    }
+}
+
+/// Encodes a signed 32-bit integer with zigzag encoding, so that values close to zero have short varints.
+#[allow(clippy::cast_sign_loss)]
+#[inline]
+#[must_use]
+pub const fn zigzag_encode_32(value: i32) -> u32 {
+    ((value << 1_u32) ^ (value >> 31_u32)) as u32
+}
+
+/// Decodes a zigzag encoded signed 32-bit integer.
+#[allow(clippy::cast_possible_wrap)]
+#[inline]
+#[must_use]
+pub const fn zigzag_decode_32(value: u32) -> i32 {
+    ((value >> 1_u32) as i32) ^ -((value & 1) as i32)
+}
+
+/// Encodes a signed 64-bit integer with zigzag encoding, so that values close to zero have short varints.
+#[allow(clippy::cast_sign_loss)]
+#[inline]
+#[must_use]
+pub const fn zigzag_encode_64(value: i64) -> u64 {
+    ((value << 1_u32) ^ (value >> 63_u32)) as u64
+}
+
+/// Decodes a zigzag encoded signed 64-bit integer.
+#[allow(clippy::cast_possible_wrap)]
+#[inline]
+#[must_use]
+pub const fn zigzag_decode_64(value: u64) -> i64 {
+    ((value >> 1_u32) as i64) ^ -((value & 1) as i64)
+}
+
+/// Returns the length of the varint encoding of `value` in bytes.
+#[inline]
+#[must_use]
+pub const fn varint_len(value: u64) -> usize {
+    // every 7 significant bits take a byte, and zero takes one byte
+    let bits = 64 - (value | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+/// Encodes `value` as a varint into a [`ByteChunk`].
+#[inline]
+#[must_use]
+pub const fn encode_varint(value: u64) -> ByteChunk {
+    let mut buf = [0_u8; MAX_VARINT_LEN];
+    let mut len = 0;
+    let mut rest = value;
+    while rest >= 0x80 {
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            buf[len] = (rest as u8) | 0x80;
+        };
+        rest >>= 7_u32;
+        len += 1;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        buf[len] = rest as u8;
+    };
+    let (head, _) = buf.split_at(len + 1);
+    ByteChunk::from_slice(head)
+}
+
+/// Decodes a varint from the start of `data`, returning the value and the length of its encoding.
+///
+/// # Errors
+///
+/// Returns an error if `data` ends before the varint does, or if the varint overflows.
+#[inline]
+pub const fn decode_varint(data: &[u8]) -> Result<(u64, usize), WireError> {
+    let mut value = 0_u64;
+    let mut index = 0;
+    while index < data.len() {
+        let byte = data[index];
+        if index == MAX_VARINT_LEN - 1 && byte > 1 {
+            return Err(WireError::VarintOverflow { position: 0 });
+        }
+        value |= ((byte & 0x7F) as u64) << (7 * index);
+        index += 1;
+        if byte < 0x80 {
+            return Ok((value, index));
+        }
+    }
+    Err(WireError::UnexpectedEnd(UnexpectedEnd {
+        needed: index + 1,
+        remaining: data.len(),
+    }))
+}
+
+/// Reads a varint at the position of `cursor`, which may be split between chunks of a queue.
+///
+/// The position is left unchanged if reading fails.
+///
+/// # Errors
+///
+/// Returns an error if the input ends before the varint does, or if the varint overflows.
+#[inline]
+pub fn read_varint(cursor: &mut ByteCursor<'_>) -> Result<u64, WireError> {
+    let start = cursor.position();
+    let mut value = 0_u64;
+    for index in 0..MAX_VARINT_LEN {
+        let Ok(byte) = cursor.read_u8() else {
+            cursor.set_position(start);
+            return Err(WireError::UnexpectedEnd(UnexpectedEnd {
+                needed: index + 1,
+                remaining: index,
+            }));
+        };
+        if index == MAX_VARINT_LEN - 1 && byte > 1 {
+            break;
+        }
+        value |= u64::from(byte & 0x7F) << (7 * index);
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+    cursor.set_position(start);
+    Err(WireError::VarintOverflow { position: start })
+}
+
+/// Encodes a tag from a field number and wire type.
+#[inline]
+#[must_use]
+pub const fn encode_tag(field_number: u32, wire_type: WireType) -> u64 {
+    ((field_number as u64) << 3_u32) | wire_type as u64
+}
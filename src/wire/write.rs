@@ -0,0 +1,176 @@
+use crate::{ByteData, StringData};
+
+use super::WireType;
+
+/// Writes fields in the protobuf wire format.
+///
+/// This is implemented for [`ByteQueue`](crate::ByteQueue), where length-delimited values are pushed without copying,
+/// and for [`SharedBytesBuilder`](crate::SharedBytesBuilder), where all values are copied into a single buffer.
+pub trait WireWrite<'a> {
+    /// Appends encoded bytes.
+    fn put_slice(&mut self, bytes: &[u8]);
+
+    /// Appends the bytes of `data`.
+    ///
+    /// The default implementation copies them with [`WireWrite::put_slice`].
+    #[inline]
+    fn put_data(&mut self, data: ByteData<'a>) {
+        self.put_slice(data.as_slice());
+    }
+
+    /// Appends a varint.
+    #[inline]
+    fn put_varint(&mut self, value: u64) {
+        self.put_slice(super::encode_varint(value).as_slice());
+    }
+
+    /// Appends the tag of a field. Field numbers above [`MAX_FIELD_NUMBER`](super::MAX_FIELD_NUMBER) are not valid.
+    #[inline]
+    fn put_tag(&mut self, field_number: u32, wire_type: WireType) {
+        debug_assert!(
+            field_number != 0 && field_number <= super::MAX_FIELD_NUMBER,
+            "invalid field number {field_number}"
+        );
+        self.put_varint(super::encode_tag(field_number, wire_type));
+    }
+
+    /// Writes a `uint64` field, or any other field with a varint value.
+    #[inline]
+    fn write_uint64_field(&mut self, field_number: u32, value: u64) {
+        self.put_tag(field_number, WireType::Varint);
+        self.put_varint(value);
+    }
+
+    /// Writes a `uint32` field.
+    #[inline]
+    fn write_uint32_field(&mut self, field_number: u32, value: u32) {
+        self.write_uint64_field(field_number, u64::from(value));
+    }
+
+    /// Writes an `int64` field, where negative values take 10 bytes.
+    #[inline]
+    fn write_int64_field(&mut self, field_number: u32, value: i64) {
+        #[allow(clippy::cast_sign_loss)]
+        self.write_uint64_field(field_number, value as u64);
+    }
+
+    /// Writes an `int32` field or an enum value, where negative values are sign-extended to 10 bytes.
+    #[inline]
+    fn write_int32_field(&mut self, field_number: u32, value: i32) {
+        self.write_int64_field(field_number, i64::from(value));
+    }
+
+    /// Writes a zigzag encoded `sint64` field.
+    #[inline]
+    fn write_sint64_field(&mut self, field_number: u32, value: i64) {
+        self.write_uint64_field(field_number, super::zigzag_encode_64(value));
+    }
+
+    /// Writes a zigzag encoded `sint32` field.
+    #[inline]
+    fn write_sint32_field(&mut self, field_number: u32, value: i32) {
+        self.write_uint32_field(field_number, super::zigzag_encode_32(value));
+    }
+
+    /// Writes a `bool` field.
+    #[inline]
+    fn write_bool_field(&mut self, field_number: u32, value: bool) {
+        self.write_uint64_field(field_number, u64::from(value));
+    }
+
+    /// Writes a `fixed64` field.
+    #[inline]
+    fn write_fixed64_field(&mut self, field_number: u32, value: u64) {
+        self.put_tag(field_number, WireType::Fixed64);
+        self.put_slice(&value.to_le_bytes());
+    }
+
+    /// Writes a `fixed32` field.
+    #[inline]
+    fn write_fixed32_field(&mut self, field_number: u32, value: u32) {
+        self.put_tag(field_number, WireType::Fixed32);
+        self.put_slice(&value.to_le_bytes());
+    }
+
+    /// Writes an `sfixed64` field.
+    #[inline]
+    fn write_sfixed64_field(&mut self, field_number: u32, value: i64) {
+        #[allow(clippy::cast_sign_loss)]
+        self.write_fixed64_field(field_number, value as u64);
+    }
+
+    /// Writes an `sfixed32` field.
+    #[inline]
+    fn write_sfixed32_field(&mut self, field_number: u32, value: i32) {
+        #[allow(clippy::cast_sign_loss)]
+        self.write_fixed32_field(field_number, value as u32);
+    }
+
+    /// Writes a `double` field.
+    #[inline]
+    fn write_double_field(&mut self, field_number: u32, value: f64) {
+        self.write_fixed64_field(field_number, value.to_bits());
+    }
+
+    /// Writes a `float` field.
+    #[inline]
+    fn write_float_field(&mut self, field_number: u32, value: f32) {
+        self.write_fixed32_field(field_number, value.to_bits());
+    }
+
+    /// Writes a `bytes` field or an embedded message that has already been encoded.
+    #[inline]
+    fn write_bytes_field<T: Into<ByteData<'a>>>(&mut self, field_number: u32, value: T)
+    where
+        Self: Sized,
+    {
+        let value = value.into();
+        self.put_tag(field_number, WireType::LengthDelimited);
+        self.put_varint(value.len() as u64);
+        self.put_data(value);
+    }
+
+    /// Writes a `string` field.
+    #[inline]
+    fn write_string_field<T: Into<StringData<'a>>>(&mut self, field_number: u32, value: T)
+    where
+        Self: Sized,
+    {
+        self.write_bytes_field(field_number, value.into().into_bytedata());
+    }
+}
+
+#[cfg(feature = "queue")]
+#[cfg_attr(docsrs, doc(cfg(feature = "queue")))]
+impl<'a> WireWrite<'a> for crate::ByteQueue<'a> {
+    #[inline]
+    fn put_slice(&mut self, bytes: &[u8]) {
+        // short values are copied into chunks, longer ones need a buffer of their own
+        if bytes.len() <= crate::ByteChunk::LEN {
+            self.push_back(crate::ByteData::from_chunk_slice(bytes));
+        } else {
+            #[cfg(feature = "alloc")]
+            self.push_back(crate::SharedBytes::from_slice(bytes));
+            #[cfg(not(feature = "alloc"))]
+            for part in bytes.chunks(crate::ByteChunk::LEN) {
+                self.push_back(crate::ByteData::from_chunk_slice(part));
+            }
+        }
+    }
+
+    #[inline]
+    fn put_data(&mut self, data: ByteData<'a>) {
+        if !data.is_empty() {
+            self.push_back(data);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl WireWrite<'_> for crate::SharedBytesBuilder {
+    #[inline]
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}